@@ -0,0 +1,904 @@
+# Antworten auf Deutsch. Fehlende Nachrichten fallen auf en-US.ftl zurück.
+#
+# `cmd-` Nachrichten übersetzen Slash-Befehle: der Wert ist der Name, `.description` die
+# Beschreibung und je Option `.<option>` und `.<option>-description`. Der Schlüssel ist der
+# englische Befehlspfad mit `-` statt Leerzeichen. Namen müssen klein geschrieben sein und
+# dürfen keine Leerzeichen enthalten.
+
+## Fehler und Prüfungen
+
+error-generic = Beim Ausführen des Befehls ist ein Fehler aufgetreten.
+check-module-disabled = Das Modul { $module } ist auf diesem Server ausgeschaltet
+check-permissions-unavailable = Deine Berechtigungen konnten nicht geprüft werden, versuche es später noch einmal
+check-not-allowed = Du darfst `{ $command }` auf diesem Server nicht verwenden
+check-missing-permission = Du brauchst die Berechtigung { $permission }, um `{ $command }` zu verwenden
+approval-pending = Dieser Server wartet auf die Freigabe durch die Betreiber des Bots. Gib ihnen die Server-ID `{ $guild_id }`.
+
+## Server-Freigabe
+
+approval-notice-leave = Danke, dass du mich hinzugefügt hast! Ich arbeite nur auf Servern, die meine Betreiber freigegeben haben, deshalb verlasse ich den Server wieder. Bitte sie, die Server-ID `{ $guild_id }` freizugeben, und füge mich dann erneut hinzu.
+approval-notice-dormant = Danke, dass du mich hinzugefügt hast! Ich arbeite nur auf Servern, die meine Betreiber freigegeben haben, und bleibe deshalb still, bis sie die Server-ID `{ $guild_id }` freigeben.
+
+## Vorschläge
+
+song-name-empty = Der Songname darf nicht leer sein
+song-artist-empty = Der Interpret darf nicht leer sein
+song-saved =
+    **Songvorschlag #{ $id }**
+    **Song:** { $song }
+    **Interpret:** { $artist }
+    **Vorgeschlagen von:** { $user }
+song-list-empty = Noch keine Songvorschläge! Schlag als Erste:r einen Song mit `/vorschlag song_vorschlagen` vor.
+song-list-title = **Die neuesten { $count } Songvorschläge**
+song-list-entry =
+    **{ $index }. { $song }** von { $artist }
+    { "   " }*Vorgeschlagen von { $user } (ID: { $id })*
+song-mine-empty = Du hast noch keine Songs vorgeschlagen! Mit `/vorschlag song_vorschlagen` fügst du deinen ersten Vorschlag hinzu.
+song-mine-title = **Deine { $count } Songvorschläge**
+song-mine-entry =
+    **{ $index }. { $song }** von { $artist }
+    { "   " }*Vorgeschlagen am { $date } (ID: { $id })*
+
+game-name-empty = Der Spielname darf nicht leer sein
+game-developer-empty = Der Entwickler darf nicht leer sein
+game-saved =
+    **Spielvorschlag #{ $id }**
+    **Spiel:** { $game }
+    **Entwickler:** { $developer }
+    **Vorgeschlagen von:** { $user }
+game-list-empty = Noch keine Spielvorschläge! Schlag als Erste:r ein Spiel mit `/vorschlag spiel_vorschlagen` vor.
+game-list-title = **Die neuesten { $count } Spielvorschläge**
+game-list-entry =
+    **{ $index }. { $game }** entwickelt von { $developer }
+    { "   " }*Vorgeschlagen von { $user } (ID: { $id })*
+game-mine-empty = Du hast noch keine Spiele vorgeschlagen! Mit `/vorschlag spiel_vorschlagen` fügst du deinen ersten Vorschlag hinzu.
+game-mine-title = **Deine { $count } Spielvorschläge**
+game-mine-entry =
+    **{ $index }. { $game }** entwickelt von { $developer }
+    { "   " }*Vorgeschlagen am { $date } (ID: { $id })*
+
+suggestion-list-more = *... und { $count } weitere Vorschläge*
+suggestion-deleted = Vorschlag #{ $id } wurde gelöscht
+suggestion-not-deleted = Der Vorschlag wurde nicht gefunden oder du darfst ihn nicht löschen.
+suggestion-not-found = Vorschlag #{ $id } wurde nicht gefunden
+suggestion-removed = Vorschlag #{ $id } wurde entfernt
+
+## Hilfe
+
+help-title = Befehle
+help-footer = Mit help <Befehl> bekommst du Details, z. B. help suggest request_song
+help-disabled = Auf diesem Server ausgeschaltet: { $modules }
+help-unknown-command = Es gibt keinen Befehl `{ $command }`
+help-no-description = Keine Beschreibung
+help-optional = (optional)
+help-options = Optionen
+help-subcommands = Unterbefehle
+
+category-music = Musik
+category-games = Spiele
+category-admin = Verwaltung
+category-settings = Einstellungen
+category-misc = Sonstiges
+category-other = Andere
+
+## Sprache
+
+language-set = Ich antworte dir ab jetzt auf **{ $language }**
+language-reset = Ich antworte dir wieder in der Sprache des Servers oder deiner Discord-Sprache
+
+## Servereinstellungen
+
+settings-show =
+    **Servereinstellungen**
+    **Präfix:** { $prefix }
+    **Sprache:** { $language }
+    **Vorschlagskanal:** { $suggestion_channel }
+    **Mod-Log-Kanal:** { $mod_log_channel }
+    **Nachrichtenlog-Kanal:** { $message_log_channel }
+    **Module:** { $modules }
+settings-default = Standard
+settings-not-set = nicht gesetzt
+settings-prefix-empty = Das Präfix darf nicht leer sein
+settings-prefix-too-long = Das Präfix darf höchstens { $max } Zeichen lang sein
+settings-prefix-whitespace = Das Präfix darf keine Leerzeichen enthalten
+settings-prefix-slash = Das Präfix darf nicht mit `/` beginnen, sonst kollidiert es mit Slash-Befehlen
+settings-prefix-set = Textbefehle verwenden jetzt `{ $prefix }`, z. B. `{ $prefix }suggest list_songs`
+settings-locale-set = Sprache auf **{ $language }** gesetzt
+settings-locale-reset = Sprache auf den Standard zurückgesetzt
+settings-channel-elsewhere = Dieser Kanal gehört nicht zu diesem Server
+settings-suggestions-set = Neue Vorschläge werden in { $channel } angekündigt
+settings-suggestions-off = Neue Vorschläge werden nicht mehr angekündigt
+settings-module-on = Das Modul **{ $module }** ist eingeschaltet
+settings-module-off = Das Modul **{ $module }** ist ausgeschaltet, seine Befehle werden abgelehnt und in `help` ausgeblendet
+
+## Allgemein
+
+error-guild-only = Dieser Befehl funktioniert nur auf einem Server
+error-invalid-duration = `{ $input }` ist keine gültige Dauer
+error-option-range = `{ $option }` muss zwischen { $min } und { $max } liegen
+error-guild-not-cached = Dieser Server ist noch nicht geladen, versuche es gleich noch einmal
+response-truncated = *... gekürzt*
+error-cannot-post = Ich kann nicht in { $channel } schreiben, prüfe, ob ich den Kanal sehen, Nachrichten senden und Links einbetten darf
+error-timeout-range = Timeouts müssen zwischen 1 Minute und { $max_days } Tagen liegen
+error-membership-unavailable = Deine Servermitgliedschaft konnte nicht geladen werden
+error-reason-empty = Der Grund darf nicht leer sein
+## Anti-Spam
+
+antispam-enabled = Anti-Spam eingeschaltet
+antispam-disabled = Anti-Spam ausgeschaltet
+antispam-duration-range = Die Dauer muss zwischen 1 Minute und { $max } liegen
+antispam-off = aus
+antispam-limit-messages = { $count } Nachrichten in { $seconds }s
+antispam-limit-duplicates = { $count } gleiche Nachrichten in { $seconds }s
+antispam-limit-joins = { $count } Beitritte in { $seconds }s
+antispam-limit-mentions = { $count } pro Nachricht
+antispam-raid-new-accounts = { $limit } von Konten, die jünger als { $days } Tage sind
+antispam-state-enabled = eingeschaltet
+antispam-state-disabled = ausgeschaltet, einschalten mit `/admin antispam toggle`
+antispam-show =
+    **Anti-Spam** ({ $state })
+
+    **Flut:** { $flood }
+    **Wiederholungen:** { $duplicates }
+    **Erwähnungen:** { $mentions }
+    **Aktion:** { $action } (Timeout { $timeout }, Sperre { $lockdown })
+    **Raid:** { $raid }, Aktion { $raid_action }
+    Mitglieder, die Nachrichten verwalten dürfen, sind nicht betroffen.
+
+## Audit-Log
+
+audit-empty = Keine Einträge im Audit-Log passen zu diesen Filtern.
+audit-title = **Die neuesten { $count } Einträge im Audit-Log**
+audit-more = *... und { $count } weitere Einträge*
+audit-invalid-since = `{ $input }` ist kein Datum (JJJJ-MM-TT) und keine Dauer
+audit-invalid-until = `{ $input }` ist kein Datum (JJJJ-MM-TT)
+audit-entry = **#{ $id }** { $date } `{ $action }` von **{ $actor }**
+audit-entry-target = **#{ $id }** { $date } `{ $action }` von **{ $actor }** bei { $target }
+audit-entry-counts = { "   " }*{ $outcome }: { $checked } geprüft, { $affected } betroffen, { $failed } fehlgeschlagen*
+
+## Muster
+
+pattern-no-flags = keine
+pattern-empty = Das Muster darf nicht leer sein
+pattern-too-long = Das Muster ist zu lang ({ $length } Zeichen, höchstens { $max })
+pattern-too-complex = Das Muster ist zu komplex, versuche einen einfacheren Ausdruck
+pattern-invalid =
+    Ungültiges Regex-Muster:
+    ```
+    { $error }
+    ```
+pattern-words-empty = Die Wortliste darf nicht leer sein
+pattern-words-too-long = Die Wortliste ist zu lang ({ $count } Einträge, höchstens { $max })
+pattern-words-too-large = Die Wortliste ist zu groß, versuche weniger oder kürzere Einträge
+
+## Automatische Bereinigung
+
+autoclean-interval-range = Das Intervall muss zwischen 5 Minuten und 7 Tagen liegen
+autoclean-max-age-range = Das Höchstalter muss zwischen 1 Minute und 365 Tagen liegen
+autoclean-needs-filter = Eine Regel braucht ein Muster, ein Höchstalter oder beides
+autoclean-created =
+    **Bereinigungsregel #{ $id }** erstellt
+    **Kanal:** { $channel }
+    **Alle:** { $interval }
+autoclean-created-max-age = **Älter als:** { $max_age }
+autoclean-created-pattern =
+    **Muster:** `{ $pattern }`
+    **Flags:** { $flags }
+autoclean-pinned = Angeheftete Nachrichten werden von der Bereinigung nie gelöscht.
+autoclean-list-empty = Keine Bereinigungsregeln eingerichtet. Füge eine mit `/admin autoclean add` hinzu.
+autoclean-list-title = **{ $count } Bereinigungsregeln**
+autoclean-list-more = *... und { $count } weitere Regeln*
+autoclean-resumed = Bereinigungsregel #{ $id } läuft wieder
+autoclean-paused = Bereinigungsregel #{ $id } pausiert
+autoclean-deleted = Bereinigungsregel #{ $id } wurde gelöscht
+autoclean-not-found = Bereinigungsregel nicht gefunden.
+autoclean-rule = **#{ $id }** { $channel } alle { $interval }
+autoclean-rule-max-age = , älter als { $max_age }
+autoclean-rule-pattern = , passend zu `{ $pattern }` (Flags: { $flags })
+autoclean-rule-paused = *(pausiert)*
+autoclean-rule-failed = { "   " }*Letzter Lauf fehlgeschlagen: { $error }*
+autoclean-rule-last-run = { "   " }*Letzter Lauf { $date }: { $checked } geprüft, { $deleted } gelöscht, { $failed } fehlgeschlagen*
+autoclean-rule-not-run = { "   " }*Noch nicht gelaufen*
+
+## Automod
+
+automod-name-length = Regelnamen müssen zwischen 1 und { $max } Zeichen lang sein
+automod-mentions-no-pattern = Erwähnungsregeln brauchen eine Anzahl `mentions`, kein Muster
+automod-mentions-needed = Erwähnungsregeln brauchen eine Anzahl `mentions`
+automod-pattern-needed = Diese Art von Regel braucht ein Muster
+automod-invalid-domain = `{ $domain }` ist keine Domain
+automod-domains-needed = Linkregeln brauchen mindestens eine Domain
+automod-action-needed = Wähle mindestens eine Aktion: delete, warn, timeout oder log
+automod-mentions = { $count }+ Erwähnungen
+automod-created =
+    **Automod-Regel #{ $id }** erstellt
+    **Name:** { $name }
+    **Art:** { $kind }
+automod-created-matches = **Trifft auf:** { $matches }
+automod-created-actions = **Aktionen:** { $actions }
+automod-created-timeout = **Timeout:** { $timeout }
+automod-exempt = Mitglieder, die Nachrichten verwalten dürfen, sind nicht betroffen.
+automod-list-empty = Keine Automod-Regeln eingerichtet. Füge eine mit `/admin automod add` hinzu.
+automod-list-title = **{ $count } Automod-Regeln**
+automod-resumed = Automod-Regel #{ $id } läuft wieder
+automod-paused = Automod-Regel #{ $id } pausiert
+automod-deleted = Automod-Regel #{ $id } wurde gelöscht
+automod-not-found = Automod-Regel nicht gefunden.
+automod-rule-case-insensitive = (ohne Groß-/Kleinschreibung)
+automod-rule-actions = { "   " }Aktionen: { $actions }
+automod-rule-paused = *(pausiert)*
+automod-rule-hits = { "   " }*{ $count } Treffer, zuletzt { $date }*
+automod-rule-no-hits = { "   " }*Noch keine Treffer*
+
+## Mod-Log
+
+audit-action-purge = Bereinigung
+audit-action-auto-clean = Automatische Bereinigung
+audit-action-warn = Verwarnung
+audit-action-unwarn = Verwarnung entfernt
+audit-action-timeout = Timeout
+audit-action-kick = Kick
+audit-action-ban = Bann
+audit-action-unban = Entbannung
+audit-action-automod = Automod-Treffer
+audit-action-antispam = Anti-Spam
+audit-action-lock = Kanalsperre
+audit-action-unlock = Kanal entsperrt
+audit-action-slowmode = Slowmode
+audit-outcome-success = erfolgreich
+audit-outcome-partial = teilweise
+audit-outcome-failure = fehlgeschlagen
+audit-field-moderator = Moderator
+audit-field-outcome = Ergebnis
+audit-field-target = Ziel
+audit-field-counts = Anzahl
+audit-counts = { $checked } geprüft, { $affected } betroffen, { $failed } fehlgeschlagen
+audit-footer = Audit #{ $id }
+audit-footer-unsaved = Audit-Eintrag nicht gespeichert
+audit-parameter-action = Aktion
+audit-parameter-actions = Aktionen
+audit-parameter-active-warnings = Aktive Verwarnungen
+audit-parameter-ban-reason = Bann-Grund
+audit-parameter-category-id = Kategorie
+audit-parameter-channel-id = Kanal
+audit-parameter-channel-lock-id = Kanalsperre
+audit-parameter-channel-lock-ids = Kanalsperren
+audit-parameter-channels = Kanäle
+audit-parameter-content = Inhalt
+audit-parameter-delete-message-days = Gelöschte Nachrichten (Tage)
+audit-parameter-duration-minutes = Dauer (Minuten)
+audit-parameter-error = Fehler
+audit-parameter-errors = Fehler
+audit-parameter-escalation-threshold = Eskalationsschwelle
+audit-parameter-everywhere = Überall
+audit-parameter-expires-at = Läuft ab
+audit-parameter-flags = Optionen
+audit-parameter-interval-minutes = Intervall (Minuten)
+audit-parameter-lock-reason = Sperrgrund
+audit-parameter-matched = Treffer
+audit-parameter-max-age-minutes = Höchstalter (Minuten)
+audit-parameter-message-id = Nachricht
+audit-parameter-messages = Nachrichten
+audit-parameter-name = Name
+audit-parameter-note = Notiz
+audit-parameter-notified = Benachrichtigt
+audit-parameter-pattern = Muster
+audit-parameter-reason = Grund
+audit-parameter-rule-id = Regel
+audit-parameter-rules = Regeln
+audit-parameter-seconds = Sekunden
+audit-parameter-server = Server
+audit-parameter-skipped-channels = Übersprungene Kanäle
+audit-parameter-temporary-ban-id = Temporärer Bann
+audit-parameter-trigger = Auslöser
+audit-parameter-users = Nutzer
+audit-parameter-warning-id = Verwarnung
+audit-parameter-warning-reason = Verwarnungsgrund
+
+## Fälle
+
+case-title = Fall #{ $number } · { $label }
+case-not-found = Fall #{ $number } existiert nicht
+case-no-reason = *Kein Grund angegeben*
+case-reason-edited = *Bearbeitet von { $user } { $time }*
+case-reason-too-long = Gründe dürfen höchstens { $max } Zeichen lang sein
+case-reason-updated-title = Fall #{ $number } · Grund geändert
+case-reason-updated =
+    Grund von **Fall #{ $number }** geändert
+    **Grund:** { $reason }
+case-field-previous-reason = Bisheriger Grund
+case-field-new-reason = Neuer Grund
+case-field-link = Link
+case-field-evidence = Beweise
+case-evidence-invalid = Beweise müssen ein http- oder https-Link sein
+case-evidence-added-title = Fall #{ $number } · Beweis hinzugefügt
+case-evidence-added = Beweis an **Fall #{ $number }** angehängt
+case-evidence-more = *... und { $count } weitere*
+
+## Bereinigung
+
+purge-duration-range = Die Dauer muss zwischen 1 Minute und 1 Woche (10080 Minuten) liegen
+purge-invalid-channel = `{ $token }` ist keine Kanalerwähnung oder -ID
+purge-dm-other-channels = Andere Kanäle können nur auf ihrem Server bereinigt werden
+purge-no-permitted-channels = Du darfst in keinem der gewählten Kanäle Nachrichten verwalten
+purge-no-channels = Keine lesbaren Textkanäle passen zur Auswahl
+purge-channel-elsewhere = { $channel } ist kein Kanal dieses Servers
+purge-not-text-channel = { $channel } ist kein Textkanal
+purge-not-permitted = Du darfst in { $channel } keine Nachrichten verwalten
+purge-starting = Bereinigung startet...
+purge-starting-many = Bereinigung von { $count } Kanälen startet...
+purge-scanning = Durchsuche Nachrichten... Geprüft: { $checked }, gefunden: { $matched }
+purge-deleting = Lösche { $total } Nachrichten...
+purge-deleting-progress = Lösche Nachrichten... Fortschritt: { $done }/{ $total }
+purge-done-no-matches =
+    Bereinigung abgeschlossen! Keine Nachricht passte zum Muster.
+    **Geprüft:** { $checked } Nachrichten
+    **Muster:** `{ $pattern }`
+purge-done-failures =
+    Bereinigung mit Fehlern abgeschlossen!
+    **Gelöscht:** { $deleted }
+    **Fehlgeschlagen:** { $failed }
+    **Insgesamt geprüft:** { $checked }
+    **Muster:** `{ $pattern }`
+    **Optionen:** { $flags }
+    **Zeitraum:** { $minutes } Minuten
+purge-done =
+    Bereinigung erfolgreich abgeschlossen!
+    **Gelöscht:** { $deleted }
+    **Insgesamt geprüft:** { $checked }
+    **Muster:** `{ $pattern }`
+    **Optionen:** { $flags }
+    **Zeitraum:** { $minutes } Minuten
+purge-skipped = **Übersprungen:** { $count } Kanäle, in denen du keine Nachrichten verwalten darfst
+purge-channels = **Kanäle:**
+purge-channel-line = { $channel }: { $checked } geprüft, { $deleted } gelöscht, { $failed } fehlgeschlagen
+purge-channel-unreadable = { $channel }: konnte nicht gelesen werden ({ $error })
+purge-channels-quiet = *{ $count } weitere Kanäle ohne Treffer ({ $checked } Nachrichten geprüft)*
+purge-channels-more = *... und { $count } weitere*
+
+## Kanalsperren
+
+lock-duration-range = Sperren müssen zwischen 1 Minute und { $max_days } Tagen dauern
+lock-channel-or-server = Wähle entweder einen Kanal oder den ganzen Server
+lock-no-public-channels = Hier gibt es keinen Kanal, in dem @everyone schreiben kann
+lock-done = Gesperrt: { $channels }
+lock-skipped-locked = bereits gesperrt
+lock-reason = **Grund:** { $reason }
+lock-expires = **Entsperrt:** { $time }
+lock-no-changes = Keine Kanäle geändert.
+lock-skipped = *Übersprungen ({ $label }):* { $channels }
+lock-failed = **Fehlgeschlagen:** { $errors }
+unlock-none-locked = Kein Kanal dieses Servers ist gesperrt
+unlock-not-locked = Dieser Kanal ist nicht gesperrt
+unlock-done = Entsperrt: { $channels }
+unlock-skipped-unlocked = nicht gesperrt
+slowmode-range = Slowmode muss zwischen 1 Sekunde und 6 Stunden liegen
+slowmode-off = Slowmode ausgeschaltet in { $channels }
+slowmode-set = Slowmode auf { $delay } gesetzt in { $channels }
+slowmode-skipped-unchanged = unverändert
+
+## Nachrichten-Log
+
+messagelog-announcement = Bearbeitete und gelöschte Nachrichten aus beobachteten Kanälen werden hier gepostet (eingerichtet von { $user }).
+messagelog-set = Nachrichten-Logs werden in { $channel } gepostet. Wähle Kanäle mit `/admin messagelog watch`.
+messagelog-cleared = Nachrichten-Log ausgeschaltet. Die beobachteten Kanäle bleiben gespeichert, falls es wieder eingerichtet wird.
+messagelog-watched = Bearbeitungen und Löschungen in { $channel } werden protokolliert. Nur ab jetzt gesendete Nachrichten können vollständig angezeigt werden.
+messagelog-already-watched = { $channel } wird bereits protokolliert.
+messagelog-unwatched = { $channel } wird nicht mehr protokolliert.
+messagelog-not-watched = { $channel } wurde nicht protokolliert.
+messagelog-show-channel = **Nachrichten-Log:** { $channel }
+messagelog-show-off = **Nachrichten-Log:** aus, richte einen Kanal mit `/admin messagelog set` ein
+messagelog-show-watched = **Beobachtete Kanäle:** { $channels }
+messagelog-show-no-watched = **Beobachtete Kanäle:** keine
+messagelog-edited-title = Nachricht bearbeitet
+messagelog-edited-description = { $user } hat eine Nachricht in { $channel } bearbeitet
+messagelog-deleted-title = Nachricht gelöscht
+messagelog-deleted-description = Eine Nachricht von { $user } wurde in { $channel } gelöscht
+messagelog-deleted-uncached = Eine nicht zwischengespeicherte Nachricht wurde in { $channel } gelöscht
+messagelog-bulk-title = Nachrichten gesammelt gelöscht
+messagelog-bulk-description = { $count } Nachrichten wurden in { $channel } gelöscht, { $cached } davon zwischengespeichert
+messagelog-bulk-attachments = *(+{ $count } Anhänge)*
+messagelog-field-before = Vorher
+messagelog-field-after = Nachher
+messagelog-field-content = Inhalt
+messagelog-field-author = Autor
+messagelog-field-sent = Gesendet
+messagelog-field-attachments = Anhänge
+messagelog-not-cached = *Nicht zwischengespeichert*
+messagelog-no-text = *Kein Text*
+messagelog-footer-message = Nachricht { $id }
+messagelog-footer-details = Nutzer { $user } · Nachricht { $id }
+
+## Mod-Log-Kanal
+
+modlog-announcement = Moderationsaktionen dieses Servers werden hier gepostet (eingerichtet von { $user }).
+modlog-set = Moderationsaktionen werden in { $channel } gepostet
+modlog-cleared = Moderationsaktionen werden nicht mehr gepostet. Das Audit-Log zeichnet sie weiterhin auf.
+modlog-show = Moderationsaktionen werden in { $channel } gepostet
+modlog-show-off = Kein Mod-Log-Kanal eingerichtet. Richte einen mit `/admin modlog set` ein.
+
+## Berechtigungsregeln
+
+permissions-unknown-command = Es gibt keinen Befehl namens `{ $command }`
+permissions-unknown-permission = `{ $permission }` ist keine Berechtigung
+permissions-one-subject = Gib genau eines von `role`, `user` oder `permission` an
+permissions-subject-permission = Mitglieder mit { $permissions }
+permissions-allowed = Regel #{ $id }: { $subject } darf jetzt `{ $command }` verwenden
+permissions-denied = Regel #{ $id }: { $subject } darf `{ $command }` nicht mehr verwenden
+permissions-integrations = Der Slash-Befehl `/{ $command }` muss für sie außerdem unter Servereinstellungen → Integrationen freigegeben werden, Textbefehle funktionieren sofort.
+permissions-list-empty = Keine Berechtigungsregeln, jeder Befehl braucht seine üblichen Berechtigungen. Füge eine mit `/admin permissions allow` hinzu.
+permissions-list-title = **{ $count } Berechtigungsregeln**
+permissions-effect-allow = erlaubt
+permissions-effect-deny = verboten
+permissions-removed = Berechtigungsregel #{ $id } entfernt
+permissions-not-found = Berechtigungsregel #{ $id } nicht gefunden
+
+## Rollenmenüs
+
+rolemenu-title-length = Titel müssen zwischen 1 und { $max } Zeichen lang sein
+rolemenu-exclusive-max = Exklusive Menüs erlauben bereits nur eine Rolle, lass `max_selections` weg
+rolemenu-created =
+    **Rollenmenü #{ $id }** erstellt
+    Füge Rollen mit `/admin rolemenu add` hinzu und poste es dann mit `/admin rolemenu publish`.
+rolemenu-not-found = Rollenmenü nicht gefunden.
+rolemenu-role-elsewhere = Diese Rolle gehört nicht zu diesem Server
+rolemenu-role-above-you = Du kannst nur Rollen unterhalb deiner höchsten Rolle hinzufügen
+rolemenu-role-managed = Diese Rolle kann nicht von Hand vergeben werden
+rolemenu-role-elevated = Diese Rolle hat Moderationsberechtigungen und kann nicht selbst vergeben werden
+rolemenu-role-too-high = Diese Rolle steht über meiner höchsten Rolle, schiebe meine Rolle zuerst nach oben
+rolemenu-role-deleted = Diese Rolle existiert nicht mehr
+rolemenu-label-too-long = Beschriftungen dürfen höchstens { $max } Zeichen lang sein
+rolemenu-invalid-emoji = `{ $emoji }` ist kein Emoji
+rolemenu-description-too-long = Beschreibungen dürfen höchstens { $max } Zeichen lang sein
+rolemenu-too-many-roles = Menüs können höchstens { $max } Rollen enthalten
+rolemenu-role-added = { $role } zu Rollenmenü #{ $id } hinzugefügt
+rolemenu-role-removed = { $role } aus Rollenmenü #{ $id } entfernt
+rolemenu-role-not-in-menu = { $role } gehört nicht zu Rollenmenü #{ $id }
+rolemenu-not-published = Poste es mit `/admin rolemenu publish`, sobald es fertig ist.
+rolemenu-refreshed = Das gepostete Menü wurde aktualisiert.
+rolemenu-refresh-failed = *Das gepostete Menü konnte nicht aktualisiert werden, veröffentliche es erneut.*
+rolemenu-empty = Füge zuerst mindestens eine Rolle mit `/admin rolemenu add` hinzu
+rolemenu-published = Rollenmenü #{ $id } ist online: { $link }
+rolemenu-list-empty = Noch keine Rollenmenüs. Erstelle eines mit `/admin rolemenu create`.
+rolemenu-list-title = **{ $count } Rollenmenüs**
+rolemenu-list-entry = **#{ $id }** { $title } ({ $style }, { $count } Rollen)
+rolemenu-list-pick-one = , eine Rolle
+rolemenu-list-up-to = , bis zu { $max }
+rolemenu-list-requires = , setzt { $role } voraus
+rolemenu-list-posted = { "   " }Gepostet in { $channel }
+rolemenu-list-unpublished = { "   " }*Nicht veröffentlicht*
+rolemenu-deleted = Rollenmenü #{ $id } wurde gelöscht
+rolemenu-requires = *Setzt { $role } voraus*
+rolemenu-pick-one = Wähle eine
+rolemenu-pick-up-to = Wähle bis zu { $max }
+rolemenu-placeholder = Wähle deine Rollen
+rolemenu-menu-gone = Dieses Rollenmenü existiert nicht mehr.
+rolemenu-role-gone = Diese Rolle gehört nicht mehr zu diesem Menü.
+rolemenu-hold-limit = Du kannst höchstens { $limit } Rollen aus diesem Menü haben, entferne zuerst eine.
+rolemenu-pick-limit = Du kannst höchstens { $limit } Rollen aus diesem Menü wählen.
+rolemenu-required-role = Du brauchst die Rolle { $role }, um dieses Menü zu verwenden.
+rolemenu-role-refused = { $role } kann gerade nicht vergeben werden, bitte ein Teammitglied, dieses Menü zu prüfen.
+rolemenu-unchanged = Nichts zu ändern.
+rolemenu-added = Hinzugefügt: { $roles }
+rolemenu-removed = Entfernt: { $roles }
+rolemenu-failed = { $roles } konnte nicht geändert werden, bitte ein Teammitglied, meine Rollenposition zu prüfen
+
+## Sanktionen
+
+sanction-timeout = { $duration }-Timeout
+sanction-kick = Kick
+sanction-ban = Bann
+sanction-no-reason = Kein Grund angegeben
+sanction-delete-days-range = Es können höchstens die Nachrichten der letzten 7 Tage gelöscht werden
+sanction-tempban-range = Temporäre Banns müssen zwischen 1 Minute und { $max_days } Tagen dauern
+sanction-self = Du kannst dir selbst keinen { $sanction } geben
+sanction-bot = Ich kann mir selbst keinen { $sanction } geben
+sanction-not-member = Diese Person ist kein Mitglied dieses Servers
+sanction-outranked-you = Du kannst niemandem einen { $sanction } geben, dessen höchste Rolle gleich hoch oder höher als deine ist
+sanction-outranked-bot = Ich kann niemandem einen { $sanction } geben, dessen höchste Rolle gleich hoch oder höher als meine ist
+sanction-failed = { $sanction } konnte nicht angewendet werden: { $error }
+sanction-applied =
+    **{ $sanction }** für **{ $user }** ({ $mention }) angewendet
+    **Grund:** { $reason }
+sanction-case = **Fall:** #{ $number }
+sanction-expires = **Läuft ab:** { $time }
+sanction-expiry-not-saved = *Das Ablaufdatum konnte nicht gespeichert werden, entbanne die Person am Ende von Hand.*
+sanction-not-notified = *Die Person konnte nicht per DM benachrichtigt werden.*
+sanction-not-banned = { $user } ist nicht gebannt
+sanction-unban-failed = { $user } konnte nicht entbannt werden: { $error }
+sanction-unbanned =
+    **{ $user }** ({ $mention }) entbannt
+    **Grund:** { $reason }
+sanction-dm-timeout =
+    Du hast auf **{ $server }** einen Timeout für { $duration } erhalten
+    **Grund:** { $reason }
+sanction-dm-kick =
+    Du wurdest von **{ $server }** gekickt
+    **Grund:** { $reason }
+sanction-dm-ban =
+    Du wurdest von **{ $server }** gebannt
+    **Grund:** { $reason }
+sanction-dm-tempban =
+    Du wurdest von **{ $server }** für { $duration } gebannt
+    **Grund:** { $reason }
+
+## Verwarnungen
+
+warn-bot = Bots können nicht verwarnt werden
+warn-self = Du kannst dich nicht selbst verwarnen
+warn-issued =
+    **Verwarnung #{ $id }** an { $user } erteilt
+    **Grund:** { $reason }
+    **Aktive Verwarnungen:** { $active }
+warn-escalated = **Eskalation:** { $sanction } angewendet, weil { $threshold } Verwarnungen erreicht wurden
+warn-escalation-failed = **Eskalation fehlgeschlagen:** { $sanction } konnte nicht angewendet werden ({ $error })
+warnings-none = { $user } hat keine Verwarnungen.
+warnings-title = **Verwarnungen von { $user }** ({ $active } aktiv)
+warnings-entry =
+    **#{ $id }** { $date } von { $moderator }
+    { "   " }{ $reason }
+warnings-entry-revoked =
+    **#{ $id }** { $date } von { $moderator } *(zurückgenommen)*
+    { "   " }{ $reason }
+warnings-more = *... und { $count } weitere Verwarnungen*
+unwarn-not-found = Verwarnung nicht gefunden oder bereits zurückgenommen.
+unwarn-revoked = Verwarnung #{ $id } für { $user } zurückgenommen
+escalation-duration-timeout-only = Nur Timeouts haben eine Dauer
+escalation-set = Mitglieder mit **{ $threshold }** Verwarnungen erhalten jetzt einen **{ $sanction }**
+escalation-list-empty = Keine Eskalationen eingerichtet. Füge eine mit `/admin escalation set` hinzu.
+escalation-list-title = **Verwarnungs-Eskalationen**
+escalation-list-entry = **{ $threshold } Verwarnungen** → { $sanction }
+escalation-removed = Eskalation für { $threshold } Verwarnungen entfernt
+escalation-not-found = Für diese Anzahl ist keine Eskalation eingerichtet.
+
+## Befehle für Betreiber
+
+reload-none = keine
+reload-applied = **Übernommen:** { $fields }
+reload-needs-restart = **Geändert, braucht einen Neustart:** { $fields }
+owner-reload-failed = Neu laden fehlgeschlagen, die laufende Konfiguration bleibt unverändert: { $error }
+owner-reloaded =
+    **Konfiguration neu geladen**
+    { $report }
+owner-sync-off = Die Befehlsregistrierung ist ausgeschaltet, es wurde nichts registriert
+owner-synced = { $count } Befehle registriert in: { $scopes }
+owner-cleared = Befehle entfernt in: { $scopes }. Textbefehle funktionieren weiter, registriere sie mit `sync` erneut
+owner-scope-none = nirgends
+owner-scope-global = global
+owner-scope-guild = Server { $id }
+owner-approved = { $guild } freigegeben
+owner-already-approved = { $guild } war bereits freigegeben
+owner-not-approved = { $guild } war nicht freigegeben
+owner-revoked = Freigabe von { $guild } zurückgezogen
+owner-revoked-open = Freigabe von { $guild } zurückgezogen. Freigaben sind ausgeschaltet, das wirkt sich erst aus, wenn sie eingeschaltet werden
+owner-revoked-leaving = Freigabe von { $guild } zurückgezogen, ich verlasse den Server
+owner-revoked-ignored = Freigabe von { $guild } zurückgezogen, der Bot ignoriert ihn ab jetzt
+owner-guilds-title = **{ $count } Server** (Freigabe: { $approval })
+owner-guild-approved = freigegeben
+owner-guild-not-approved = nicht freigegeben
+owner-guild-not-joined = freigegeben, nicht beigetreten
+
+## Slash-Befehle
+
+cmd-help = hilfe
+    .description = Zeigt die Befehle, die du hier verwenden kannst
+    .command = befehl
+    .command-description = Befehl, der erklärt werden soll, z. B. `suggest request_song`
+
+cmd-language = sprache
+    .description = Wähle die Sprache, in der der Bot dir antwortet
+    .locale = sprache
+    .locale-description = Sprache, leer lassen für die Sprache des Servers
+
+cmd-suggest = vorschlag
+    .description = Songs und Spiele vorschlagen
+cmd-suggest-request_song = song_vorschlagen
+    .description = Schlägt einen Song vor, den ich mir später anhöre
+    .song_name = songname
+    .song_name-description = Der Name des Songs
+    .artist = interpret
+    .artist-description = Der Interpret oder die Band
+cmd-suggest-list_songs = songs_anzeigen
+    .description = Zeigt alle offenen Songvorschläge
+    .limit = anzahl
+    .limit-description = Anzahl der angezeigten Vorschläge (höchstens 50)
+cmd-suggest-my_song_requests = meine_songs
+    .description = Zeigt deine Songvorschläge
+    .limit = anzahl
+    .limit-description = Anzahl der angezeigten Vorschläge (höchstens 20)
+cmd-suggest-delete_song_request = song_löschen
+    .description = Löscht einen deiner Songvorschläge anhand seiner ID
+    .suggestion_id = vorschlag_id
+    .suggestion_id-description = ID des Vorschlags, der gelöscht werden soll
+cmd-suggest-remove_song_request = song_entfernen
+    .description = Entfernt den Songvorschlag einer beliebigen Person, für Kurator:innen
+    .suggestion_id = vorschlag_id
+    .suggestion_id-description = ID des Vorschlags, der entfernt werden soll
+cmd-suggest-request_game = spiel_vorschlagen
+    .description = Schlägt ein Spiel vor, das ich später spiele
+    .game_name = spielname
+    .game_name-description = Der Name des Spiels
+    .developer = entwickler
+    .developer-description = Der Entwickler des Spiels
+cmd-suggest-list_games = spiele_anzeigen
+    .description = Zeigt alle offenen Spielvorschläge
+    .limit = anzahl
+    .limit-description = Anzahl der angezeigten Vorschläge (höchstens 50)
+cmd-suggest-my_game_requests = meine_spiele
+    .description = Zeigt deine Spielvorschläge
+    .limit = anzahl
+    .limit-description = Anzahl der angezeigten Vorschläge (höchstens 20)
+cmd-suggest-delete_game_request = spiel_löschen
+    .description = Löscht einen deiner Spielvorschläge anhand seiner ID
+    .suggestion_id = vorschlag_id
+    .suggestion_id-description = ID des Vorschlags, der gelöscht werden soll
+cmd-suggest-remove_game_request = spiel_entfernen
+    .description = Entfernt den Spielvorschlag einer beliebigen Person, für Kurator:innen
+    .suggestion_id = vorschlag_id
+    .suggestion_id-description = ID des Vorschlags, der entfernt werden soll
+
+cmd-admin-purge =
+    .description = Löscht Nachrichten, die auf ein Muster passen
+    .pattern-description = Regex-Muster, auf das die Nachrichten passen müssen
+    .channel-description = Kanal, der bereinigt wird (Standard: aktueller Kanal)
+    .channels-description = Weitere Kanäle als Erwähnungen oder IDs
+    .category-description = Bereinigt jeden lesbaren Kanal dieser Kategorie
+    .everywhere-description = Bereinigt jeden Textkanal, den der Bot lesen kann
+    .duration_minutes-description = Zeitraum in Minuten (z. B. 60 für 1 Stunde)
+    .case_insensitive-description = Groß- und Kleinschreibung ignorieren
+    .multi_line-description = ^ und $ passen an Zeilengrenzen
+    .literal-description = Muster als reinen Text statt als Regex behandeln
+cmd-admin-autoclean =
+    .description = Geplante automatische Bereinigungen verwalten
+cmd-admin-autoclean-add =
+    .description = Plant eine regelmäßige Bereinigung eines Kanals
+    .channel-description = Kanal, der bereinigt wird
+    .every-description = Wie oft sie läuft (z. B. 1h, 6h, 1d)
+    .older_than-description = Löscht Nachrichten, die älter sind (z. B. 24h)
+    .pattern-description = Löscht nur Nachrichten, die auf diese Regex passen
+    .case_insensitive-description = Groß- und Kleinschreibung ignorieren
+    .multi_line-description = ^ und $ passen an Zeilengrenzen
+    .literal-description = Muster als reinen Text statt als Regex behandeln
+cmd-admin-autoclean-list =
+    .description = Zeigt die Bereinigungsregeln dieses Servers
+cmd-admin-autoclean-toggle =
+    .description = Pausiert eine Bereinigungsregel oder setzt sie fort
+    .rule_id-description = ID der Regel
+    .enabled-description = Ob die Regel laufen soll
+cmd-admin-autoclean-remove =
+    .description = Löscht eine Bereinigungsregel
+    .rule_id-description = ID der Regel
+cmd-admin-automod =
+    .description = Regeln der automatischen Moderation für neue Nachrichten verwalten
+cmd-admin-automod-add =
+    .description = Fügt eine Regel hinzu, die jede neue Nachricht prüft
+    .name-description = Kurzer Name für die Logs
+    .kind-description = Wonach die Regel sucht
+    .pattern-description = Regex oder kommagetrennte Wörter oder Domains
+    .mentions-description = Erwähnungen, ab denen die Regel greift (Erwähnungsregeln)
+    .delete-description = Nachricht löschen
+    .warn-description = Autor:in verwarnen
+    .timeout-description = Autor:in so lange stummschalten (z. B. 10m)
+    .log-description = Treffer im Audit-Log festhalten
+    .case_insensitive-description = Groß- und Kleinschreibung ignorieren (Regex-Regeln)
+cmd-admin-automod-list =
+    .description = Zeigt die Automod-Regeln dieses Servers
+cmd-admin-automod-toggle =
+    .description = Pausiert eine Automod-Regel oder setzt sie fort
+    .rule_id-description = ID der Regel
+    .enabled-description = Ob die Regel laufen soll
+cmd-admin-automod-remove =
+    .description = Löscht eine Automod-Regel
+    .rule_id-description = ID der Regel
+cmd-admin-antispam =
+    .description = Spam- und Raid-Erkennung einrichten
+cmd-admin-antispam-show =
+    .description = Zeigt die Anti-Spam-Einstellungen dieses Servers
+cmd-admin-antispam-toggle =
+    .description = Schaltet die Spam- und Raid-Erkennung ein oder aus
+    .enabled-description = Ob die Erkennung laufen soll
+cmd-admin-antispam-messages =
+    .description = Legt die Nachrichtengrenzen fest und was mit Spammern passiert
+    .flood_messages-description = Nachrichten pro Zeitfenster, die als Flut gelten (0 zum Abschalten)
+    .flood_seconds-description = Zeitfenster für Fluten in Sekunden
+    .duplicate_messages-description = Gleiche Nachrichten, die als Spam gelten (0 zum Abschalten)
+    .duplicate_seconds-description = Zeitfenster für Wiederholungen in Sekunden
+    .mention_limit-description = Erwähnungen in einer Nachricht, die als Spam gelten (0 zum Abschalten)
+    .action-description = Was mit Spammern passiert
+    .timeout-description = Dauer der Stummschaltung (z. B. 10m)
+    .lockdown-description = Wie lange eine Sperre dauert (z. B. 15m)
+cmd-admin-antispam-raid =
+    .description = Legt fest, ab wie vielen Beitritten ein Raid erkannt wird und was dann passiert
+    .joins-description = Beitritte pro Zeitfenster, die als Raid gelten (0 zum Abschalten)
+    .seconds-description = Zeitfenster für Beitritte in Sekunden
+    .account_age_days-description = Nur Konten zählen, die jünger als so viele Tage sind (0 zählt alle)
+    .action-description = Was während eines Raids passiert
+cmd-admin-settings =
+    .description = Einstellungen des Bots für diesen Server ansehen und ändern
+cmd-admin-settings-show =
+    .description = Zeigt die Einstellungen dieses Servers
+cmd-admin-settings-prefix =
+    .description = Ändert das Präfix der Textbefehle
+    .prefix-description = Neues Präfix, leer lassen für den Standard
+cmd-admin-settings-locale =
+    .description = Ändert die Sprache, in der der Bot antwortet
+    .locale-description = Sprache, leer lassen für den Standard
+cmd-admin-settings-suggestions =
+    .description = Legt den Kanal fest, in dem neue Vorschläge angekündigt werden
+    .channel-description = Kanal für die Ankündigungen, leer lassen zum Beenden
+cmd-admin-settings-module =
+    .description = Schaltet eine Gruppe von Befehlen auf diesem Server ein oder aus
+    .module-description = Das Modul, das geändert werden soll
+    .enabled-description = Ob seine Befehle verwendet werden können
+cmd-admin-permissions =
+    .description = Befehle für Rollen, Mitglieder oder Berechtigungen erlauben oder verbieten
+cmd-admin-permissions-allow =
+    .description = Erlaubt einer Rolle, einem Mitglied oder einer Berechtigung einen Befehl oder eine Gruppe
+    .command-description = Befehl oder Gruppe, z. B. `suggest remove_song` oder `admin`
+    .role-description = Rolle, die es darf
+    .user-description = Mitglied, das es darf
+    .permission-description = Berechtigung, die Mitglieder brauchen, z. B. manage_nicknames
+cmd-admin-permissions-deny =
+    .description = Verbietet einer Rolle, einem Mitglied oder einer Berechtigung einen Befehl oder eine Gruppe
+    .command-description = Befehl oder Gruppe, z. B. `suggest remove_song` oder `admin`
+    .role-description = Rolle, die es nicht darf
+    .user-description = Mitglied, das es nicht darf
+    .permission-description = Berechtigung, die Mitglieder brauchen, z. B. manage_nicknames
+cmd-admin-permissions-list =
+    .description = Zeigt die Berechtigungsregeln dieses Servers
+cmd-admin-permissions-remove =
+    .description = Entfernt eine Berechtigungsregel
+    .rule_id-description = ID der Regel, siehe `/admin permissions list`
+cmd-admin-audit =
+    .description = Durchsucht das Audit-Log der Moderation
+    .actor-description = Nur Aktionen dieser Moderator:in
+    .action-description = Nur diese Art von Aktion
+    .since-description = Ab diesem Datum (JJJJ-MM-TT) oder vor so langer Zeit (z. B. 7d)
+    .until-description = Bis einschließlich zu diesem Datum (JJJJ-MM-TT)
+    .limit-description = Anzahl der angezeigten Einträge (höchstens 25)
+cmd-admin-case =
+    .description = Zeigt einen Moderationsfall
+    .number-description = Fallnummer
+cmd-admin-reason =
+    .description = Ändert die Begründung eines Moderationsfalls
+    .number-description = Fallnummer
+    .reason-description = Neue Begründung
+cmd-admin-evidence =
+    .description = Hängt einen Beweislink an einen Moderationsfall
+    .number-description = Fallnummer
+    .url-description = Link zu einem Screenshot, einer Nachricht oder einer Datei
+cmd-admin-modlog =
+    .description = Legt den Kanal fest, der jede Moderationsaktion spiegelt
+cmd-admin-modlog-set =
+    .description = Postet Moderationsaktionen in einen Kanal
+    .channel-description = Kanal, in den gepostet wird
+cmd-admin-modlog-clear =
+    .description = Hört auf, Moderationsaktionen zu posten
+cmd-admin-modlog-show =
+    .description = Zeigt, wohin Moderationsaktionen gepostet werden
+cmd-admin-messagelog =
+    .description = Protokolliert bearbeitete und gelöschte Nachrichten ausgewählter Kanäle
+cmd-admin-messagelog-set =
+    .description = Postet Bearbeitungs- und Lösch-Logs in einen Kanal
+    .channel-description = Kanal für die Logs
+cmd-admin-messagelog-clear =
+    .description = Hört auf, Bearbeitungs- und Lösch-Logs zu posten
+cmd-admin-messagelog-watch =
+    .description = Protokolliert Bearbeitungen und Löschungen in einem Kanal
+    .channel-description = Kanal, der protokolliert wird
+cmd-admin-messagelog-unwatch =
+    .description = Hört auf, einen Kanal zu protokollieren
+    .channel-description = Kanal, der nicht mehr protokolliert wird
+cmd-admin-messagelog-show =
+    .description = Zeigt die Einstellungen des Nachrichten-Logs
+cmd-admin-warn =
+    .description = Verwarnt ein Mitglied und wendet eingerichtete Eskalationen an
+    .user-description = Mitglied, das verwarnt wird
+    .reason-description = Warum es verwarnt wird
+cmd-admin-warnings =
+    .description = Zeigt die Verwarnungen eines Mitglieds
+    .user-description = Mitglied, das nachgeschlagen wird
+    .include_revoked-description = Auch zurückgenommene Verwarnungen zeigen
+cmd-admin-unwarn =
+    .description = Nimmt eine Verwarnung anhand ihrer ID zurück
+    .warning_id-description = ID der Verwarnung
+    .reason-description = Warum die Verwarnung zurückgenommen wird
+cmd-admin-escalation =
+    .description = Automatische Strafen für Verwarnungszahlen einrichten
+cmd-admin-escalation-set =
+    .description = Legt die Strafe fest, wenn ein Mitglied eine Zahl an Verwarnungen erreicht
+    .threshold-description = Anzahl aktiver Verwarnungen
+    .action-description = Strafe, die angewendet wird
+    .duration-description = Dauer der Stummschaltung (z. B. 1h, 1d), nur für Stummschaltungen
+cmd-admin-escalation-list =
+    .description = Zeigt die Eskalationen dieses Servers
+cmd-admin-escalation-remove =
+    .description = Entfernt die Eskalation für eine Zahl an Verwarnungen
+    .threshold-description = Anzahl aktiver Verwarnungen
+cmd-admin-timeout =
+    .description = Schaltet ein Mitglied stumm
+    .user-description = Mitglied, das stummgeschaltet wird
+    .duration-description = Wie lange (z. B. 10m, 1h, 7d)
+    .reason-description = Warum es stummgeschaltet wird
+cmd-admin-kick =
+    .description = Wirft ein Mitglied vom Server
+    .user-description = Mitglied, das rausgeworfen wird
+    .reason-description = Warum es rausgeworfen wird
+cmd-admin-ban =
+    .description = Bannt ein Konto vom Server
+    .user-description = Konto, das gebannt wird
+    .delete_message_days-description = Tage, deren Nachrichten gelöscht werden (0-7)
+    .reason-description = Warum es gebannt wird
+cmd-admin-tempban =
+    .description = Bannt ein Konto für begrenzte Zeit
+    .user-description = Konto, das gebannt wird
+    .duration-description = Wie lange (z. B. 12h, 7d, 2w)
+    .delete_message_days-description = Tage, deren Nachrichten gelöscht werden (0-7)
+    .reason-description = Warum es gebannt wird
+cmd-admin-unban =
+    .description = Hebt den Bann eines Kontos auf
+    .user-description = Konto, das entbannt wird (Erwähnung oder ID)
+    .reason-description = Warum der Bann aufgehoben wird
+cmd-admin-lock =
+    .description = Hindert @everyone daran, in einem Kanal oder auf dem ganzen Server zu schreiben
+    .channel-description = Kanal, der gesperrt wird (Standard: aktueller Kanal)
+    .server-description = Jeden Kanal sperren, in dem @everyone schreiben kann
+    .duration-description = Nach dieser Zeit automatisch entsperren (z. B. 30m, 2h)
+    .reason-description = Warum der Kanal gesperrt wird
+cmd-admin-unlock =
+    .description = Stellt die Berechtigungen eines Kanals oder des ganzen Servers von vor der Sperre wieder her
+    .channel-description = Kanal, der entsperrt wird (Standard: aktueller Kanal)
+    .server-description = Jeden gesperrten Kanal entsperren
+cmd-admin-slowmode =
+    .description = Begrenzt, wie oft Mitglieder in einem Kanal oder auf dem ganzen Server posten können
+    .delay-description = Abstand zwischen Nachrichten (z. B. 10s, 5m) oder off
+    .channel-description = Kanal, der geändert wird (Standard: aktueller Kanal)
+    .server-description = Jeden Textkanal ändern
+cmd-admin-rolemenu =
+    .description = Rollenmenüs zum Selbstzuweisen verwalten
+cmd-admin-rolemenu-create =
+    .description = Erstellt ein Rollenmenü, danach Rollen hinzufügen und es veröffentlichen
+    .title-description = Titel über dem Menü
+    .style-description = Buttons oder ein Auswahlmenü
+    .description-description = Text über den Rollen
+    .exclusive-description = Mitglieder können nur eine Rolle aus diesem Menü haben
+    .max_selections-description = Höchstzahl an Rollen aus diesem Menü pro Mitglied
+    .required_role-description = Rolle, die Mitglieder brauchen, um das Menü zu nutzen
+cmd-admin-rolemenu-add =
+    .description = Fügt einem Menü eine Rolle hinzu oder ändert, wie sie angezeigt wird
+    .menu_id-description = ID des Menüs
+    .role-description = Rolle, die Mitglieder wählen können
+    .label-description = Text des Buttons oder der Option (Standard: Name der Rolle)
+    .emoji-description = Emoji neben der Rolle
+    .description-description = Kurze Erklärung der Rolle
+cmd-admin-rolemenu-remove =
+    .description = Entfernt eine Rolle aus einem Menü
+    .menu_id-description = ID des Menüs
+    .role-description = Rolle, die entfernt wird
+cmd-admin-rolemenu-publish =
+    .description = Postet ein Rollenmenü oder verschiebt es in einen anderen Kanal
+    .menu_id-description = ID des Menüs
+    .channel-description = Kanal, in dem gepostet wird (Standard: aktueller Kanal)
+cmd-admin-rolemenu-list =
+    .description = Zeigt die Rollenmenüs dieses Servers
+cmd-admin-rolemenu-delete =
+    .description = Löscht ein Rollenmenü und seine Nachricht
+    .menu_id-description = ID des Menüs
+
+cmd-owner =
+    .description = Befehle für die Betreiber:innen des Bots
+cmd-owner-reload =
+    .description = Liest die Konfigurationsdatei neu ein und übernimmt, was ohne Neustart geht
+cmd-owner-sync =
+    .description = Registriert die Slash-Befehle erneut wie in `commands.registration` eingestellt
+cmd-owner-clear =
+    .description = Entfernt die registrierten Slash-Befehle
+    .guild-description = Nur dieser Server statt überall
+cmd-owner-approve =
+    .description = Lässt den Bot auf einem Server arbeiten, auch bevor er dort hinzugefügt wird
+    .guild-description = ID des Servers
+cmd-owner-revoke =
+    .description = Entzieht einem Server die Freigabe
+    .guild-description = ID des Servers
+cmd-owner-guilds =
+    .description = Zeigt die Server, auf denen der Bot ist, und welche freigegeben sind
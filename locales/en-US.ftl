@@ -0,0 +1,578 @@
+# Responses in English, the fallback for every other locale.
+#
+# Command names and descriptions come from the doc comments on the commands, so this file
+# only holds responses. Other locales add `cmd-` messages to translate those, see de.ftl.
+
+## Errors and checks
+
+error-generic = An error occurred while processing your command.
+check-module-disabled = The { $module } module is turned off in this server
+check-permissions-unavailable = Could not check your permissions, try again later
+check-not-allowed = You are not allowed to use `{ $command }` in this server
+check-missing-permission = You need the { $permission } permission to use `{ $command }`
+approval-pending = This server is waiting for approval by the bot's owners. Give them the server ID `{ $guild_id }`.
+
+## Server approval
+
+approval-notice-leave = Thanks for adding me! I only work in servers my owners approved, so I am leaving again. Ask them to approve the server ID `{ $guild_id }`, then add me again.
+approval-notice-dormant = Thanks for adding me! I only work in servers my owners approved, so I will stay quiet until they approve the server ID `{ $guild_id }`.
+
+## Suggestions
+
+song-name-empty = Song name cannot be empty
+song-artist-empty = Artist name cannot be empty
+song-saved =
+    **Song Suggestion #{ $id }**
+    **Song:** { $song }
+    **Artist:** { $artist }
+    **Suggested by:** { $user }
+song-list-empty = No song suggestions found! Be the first to suggest a song with `/suggest request_song`.
+song-list-title = **Latest { $count } Song Suggestions**
+song-list-entry =
+    **{ $index }. { $song }** by { $artist }
+    { "   " }*Suggested by { $user } (ID: { $id })*
+song-mine-empty = You haven't suggested any songs yet! Use `/suggest request_song` to add your first suggestion.
+song-mine-title = **Your { $count } Song Suggestions**
+song-mine-entry =
+    **{ $index }. { $song }** by { $artist }
+    { "   " }*Suggested on { $date } (ID: { $id })*
+
+game-name-empty = Game name cannot be empty
+game-developer-empty = Developer name cannot be empty
+game-saved =
+    **Game Suggestion #{ $id }**
+    **Game:** { $game }
+    **Developer:** { $developer }
+    **Suggested by:** { $user }
+game-list-empty = No game suggestions found! Be the first to suggest a game with `/suggest request_game`.
+game-list-title = **Latest { $count } Game Suggestions**
+game-list-entry =
+    **{ $index }. { $game }** developed by { $developer }
+    { "   " }*Suggested by { $user } (ID: { $id })*
+game-mine-empty = You haven't suggested any games yet! Use `/suggest request_game` to add your first suggestion.
+game-mine-title = **Your { $count } Game Suggestions**
+game-mine-entry =
+    **{ $index }. { $game }** developed by { $developer }
+    { "   " }*Suggested on { $date } (ID: { $id })*
+
+suggestion-list-more = *... and { $count } more suggestions*
+suggestion-deleted = Successfully deleted suggestion #{ $id }
+suggestion-not-deleted = Suggestion not found or you don't have permission to delete it.
+suggestion-not-found = Suggestion #{ $id } not found
+suggestion-removed = Removed suggestion #{ $id }
+
+## Help
+
+help-title = Commands
+help-footer = Use help <command> for details, e.g. help suggest request_song
+help-disabled = Turned off in this server: { $modules }
+help-unknown-command = There is no command called `{ $command }`
+help-no-description = No description
+help-optional = (optional)
+help-options = Options
+help-subcommands = Subcommands
+
+category-music = Music
+category-games = Games
+category-admin = Admin
+category-settings = Settings
+category-misc = Misc
+category-other = Other
+
+## Language
+
+language-set = I will answer you in **{ $language }** from now on
+language-reset = I will answer you in the server's language again, or in your Discord language
+
+## Server settings
+
+settings-show =
+    **Server Settings**
+    **Prefix:** { $prefix }
+    **Language:** { $language }
+    **Suggestion channel:** { $suggestion_channel }
+    **Mod-log channel:** { $mod_log_channel }
+    **Message log channel:** { $message_log_channel }
+    **Modules:** { $modules }
+settings-default = default
+settings-not-set = not set
+settings-prefix-empty = The prefix cannot be empty
+settings-prefix-too-long = The prefix can be at most { $max } characters
+settings-prefix-whitespace = The prefix cannot contain spaces
+settings-prefix-slash = The prefix cannot start with `/`, it would clash with slash commands
+settings-prefix-set = Text commands now use `{ $prefix }`, e.g. `{ $prefix }suggest list_songs`
+settings-locale-set = Language set to **{ $language }**
+settings-locale-reset = Language reset to the default
+settings-channel-elsewhere = That channel is not in this server
+settings-suggestions-set = New suggestions will be announced in { $channel }
+settings-suggestions-off = New suggestions will no longer be announced
+settings-module-on = The **{ $module }** module is on
+settings-module-off = The **{ $module }** module is off, its commands are refused and hidden from `help`
+
+## Common
+
+error-guild-only = This command only works in a server
+error-invalid-duration = `{ $input }` is not a valid duration
+error-option-range = `{ $option }` must be between { $min } and { $max }
+error-guild-not-cached = This server isn't cached yet, try again shortly
+response-truncated = *... truncated*
+error-cannot-post = I can't post in { $channel }, check that I can view it, send messages and embed links
+error-timeout-range = Timeouts must be between 1 minute and { $max_days } days
+error-membership-unavailable = Could not load your server membership
+error-reason-empty = Reason cannot be empty
+
+## Anti-spam
+
+antispam-enabled = Anti-spam enabled
+antispam-disabled = Anti-spam disabled
+antispam-duration-range = Durations must be between 1 minute and { $max }
+antispam-off = off
+antispam-limit-messages = { $count } messages in { $seconds }s
+antispam-limit-duplicates = { $count } identical messages in { $seconds }s
+antispam-limit-joins = { $count } joins in { $seconds }s
+antispam-limit-mentions = { $count } per message
+antispam-raid-new-accounts = { $limit } from accounts younger than { $days } days
+antispam-state-enabled = enabled
+antispam-state-disabled = disabled, turn it on with `/admin antispam toggle`
+antispam-show =
+    **Anti-spam** ({ $state })
+
+    **Flood:** { $flood }
+    **Duplicates:** { $duplicates }
+    **Mentions:** { $mentions }
+    **Action:** { $action } (timeout { $timeout }, lockdown { $lockdown })
+    **Raid:** { $raid }, action { $raid_action }
+    Members who can manage messages are not affected.
+
+## Audit log
+
+audit-empty = No audit entries matched those filters.
+audit-title = **Latest { $count } Audit Entries**
+audit-more = *... and { $count } more entries*
+audit-invalid-since = `{ $input }` is not a date (YYYY-MM-DD) or duration
+audit-invalid-until = `{ $input }` is not a date (YYYY-MM-DD)
+audit-entry = **#{ $id }** { $date } `{ $action }` by **{ $actor }**
+audit-entry-target = **#{ $id }** { $date } `{ $action }` by **{ $actor }** on { $target }
+audit-entry-counts = { "   " }*{ $outcome }: checked { $checked }, affected { $affected }, failed { $failed }*
+
+## Patterns
+
+pattern-no-flags = none
+pattern-empty = Pattern cannot be empty
+pattern-too-long = Pattern is too long ({ $length } characters, max { $max })
+pattern-too-complex = Pattern is too complex, try a simpler expression
+pattern-invalid =
+    Invalid regex pattern:
+    ```
+    { $error }
+    ```
+pattern-words-empty = Word list cannot be empty
+pattern-words-too-long = Word list is too long ({ $count } entries, max { $max })
+pattern-words-too-large = Word list is too large, try fewer or shorter entries
+
+## Auto-clean
+
+autoclean-interval-range = Interval must be between 5 minutes and 7 days
+autoclean-max-age-range = Maximum age must be between 1 minute and 365 days
+autoclean-needs-filter = A rule needs a pattern, a maximum age, or both
+autoclean-created =
+    **Auto-clean rule #{ $id }** created
+    **Channel:** { $channel }
+    **Every:** { $interval }
+autoclean-created-max-age = **Older than:** { $max_age }
+autoclean-created-pattern =
+    **Pattern:** `{ $pattern }`
+    **Flags:** { $flags }
+autoclean-pinned = Pinned messages are never deleted by auto-clean.
+autoclean-list-empty = No auto-clean rules configured. Add one with `/admin autoclean add`.
+autoclean-list-title = **{ $count } Auto-clean Rules**
+autoclean-list-more = *... and { $count } more rules*
+autoclean-resumed = Auto-clean rule #{ $id } resumed
+autoclean-paused = Auto-clean rule #{ $id } paused
+autoclean-deleted = Successfully deleted auto-clean rule #{ $id }
+autoclean-not-found = Auto-clean rule not found.
+autoclean-rule = **#{ $id }** { $channel } every { $interval }
+autoclean-rule-max-age = , older than { $max_age }
+autoclean-rule-pattern = , matching `{ $pattern }` (flags: { $flags })
+autoclean-rule-paused = *(paused)*
+autoclean-rule-failed = { "   " }*Last run failed: { $error }*
+autoclean-rule-last-run = { "   " }*Last run { $date }: checked { $checked }, deleted { $deleted }, failed { $failed }*
+autoclean-rule-not-run = { "   " }*Not run yet*
+
+## Automod
+
+automod-name-length = Rule names must be between 1 and { $max } characters
+automod-mentions-no-pattern = Mention rules take a `mentions` count, not a pattern
+automod-mentions-needed = Mention rules need a `mentions` count
+automod-pattern-needed = This kind of rule needs a pattern
+automod-invalid-domain = `{ $domain }` is not a domain
+automod-domains-needed = Link rules need at least one domain
+automod-action-needed = Pick at least one action: delete, warn, timeout or log
+automod-mentions = { $count }+ mentions
+automod-created =
+    **Automod rule #{ $id }** created
+    **Name:** { $name }
+    **Kind:** { $kind }
+automod-created-matches = **Matches:** { $matches }
+automod-created-actions = **Actions:** { $actions }
+automod-created-timeout = **Timeout:** { $timeout }
+automod-exempt = Members who can manage messages are not affected.
+automod-list-empty = No automod rules configured. Add one with `/admin automod add`.
+automod-list-title = **{ $count } Automod Rules**
+automod-resumed = Automod rule #{ $id } resumed
+automod-paused = Automod rule #{ $id } paused
+automod-deleted = Successfully deleted automod rule #{ $id }
+automod-not-found = Automod rule not found.
+automod-rule-case-insensitive = (case insensitive)
+automod-rule-actions = { "   " }Actions: { $actions }
+automod-rule-paused = *(paused)*
+automod-rule-hits = { "   " }*Matched { $count } times, last { $date }*
+automod-rule-no-hits = { "   " }*No matches yet*
+
+## Mod log
+
+audit-action-purge = Purge
+audit-action-auto-clean = Auto-clean
+audit-action-warn = Warning
+audit-action-unwarn = Warning removed
+audit-action-timeout = Timeout
+audit-action-kick = Kick
+audit-action-ban = Ban
+audit-action-unban = Unban
+audit-action-automod = Automod match
+audit-action-antispam = Anti-spam
+audit-action-lock = Channel lock
+audit-action-unlock = Channel unlock
+audit-action-slowmode = Slowmode
+audit-outcome-success = success
+audit-outcome-partial = partial
+audit-outcome-failure = failure
+audit-field-moderator = Moderator
+audit-field-outcome = Outcome
+audit-field-target = Target
+audit-field-counts = Counts
+audit-counts = { $checked } checked, { $affected } affected, { $failed } failed
+audit-footer = Audit #{ $id }
+audit-footer-unsaved = Audit record not saved
+audit-parameter-action = Action
+audit-parameter-actions = Actions
+audit-parameter-active-warnings = Active warnings
+audit-parameter-ban-reason = Ban reason
+audit-parameter-category-id = Category
+audit-parameter-channel-id = Channel
+audit-parameter-channel-lock-id = Channel lock
+audit-parameter-channel-lock-ids = Channel locks
+audit-parameter-channels = Channels
+audit-parameter-content = Content
+audit-parameter-delete-message-days = Deleted message days
+audit-parameter-duration-minutes = Duration (minutes)
+audit-parameter-error = Error
+audit-parameter-errors = Errors
+audit-parameter-escalation-threshold = Escalation threshold
+audit-parameter-everywhere = Everywhere
+audit-parameter-expires-at = Expires at
+audit-parameter-flags = Flags
+audit-parameter-interval-minutes = Interval (minutes)
+audit-parameter-lock-reason = Lock reason
+audit-parameter-matched = Matched
+audit-parameter-max-age-minutes = Max age (minutes)
+audit-parameter-message-id = Message
+audit-parameter-messages = Messages
+audit-parameter-name = Name
+audit-parameter-note = Note
+audit-parameter-notified = Notified
+audit-parameter-pattern = Pattern
+audit-parameter-reason = Reason
+audit-parameter-rule-id = Rule
+audit-parameter-rules = Rules
+audit-parameter-seconds = Seconds
+audit-parameter-server = Server
+audit-parameter-skipped-channels = Skipped channels
+audit-parameter-temporary-ban-id = Temporary ban
+audit-parameter-trigger = Trigger
+audit-parameter-users = Users
+audit-parameter-warning-id = Warning
+audit-parameter-warning-reason = Warning reason
+
+## Cases
+
+case-title = Case #{ $number } · { $label }
+case-not-found = Case #{ $number } does not exist
+case-no-reason = *No reason given*
+case-reason-edited = *Edited by { $user } { $time }*
+case-reason-too-long = Reasons can be at most { $max } characters
+case-reason-updated-title = Case #{ $number } · Reason updated
+case-reason-updated =
+    Updated the reason of **case #{ $number }**
+    **Reason:** { $reason }
+case-field-previous-reason = Previous reason
+case-field-new-reason = New reason
+case-field-link = Link
+case-field-evidence = Evidence
+case-evidence-invalid = Evidence must be an http or https link
+case-evidence-added-title = Case #{ $number } · Evidence added
+case-evidence-added = Attached evidence to **case #{ $number }**
+case-evidence-more = *... and { $count } more*
+
+## Purge
+
+purge-duration-range = Duration must be between 1 minute and 1 week (10080 minutes)
+purge-invalid-channel = `{ $token }` is not a channel mention or id
+purge-dm-other-channels = Other channels can only be purged from inside their server
+purge-no-permitted-channels = You can't manage messages in any of the selected channels
+purge-no-channels = No readable text channels matched the selection
+purge-channel-elsewhere = { $channel } is not a channel in this server
+purge-not-text-channel = { $channel } is not a text channel
+purge-not-permitted = You can't manage messages in { $channel }
+purge-starting = Starting the purge...
+purge-starting-many = Starting the purge across { $count } channels...
+purge-scanning = Scanning messages... Checked: { $checked }, Found: { $matched }
+purge-deleting = Deleting { $total } messages...
+purge-deleting-progress = Deleting messages... Progress: { $done }/{ $total }
+purge-done-no-matches =
+    Purge completed! No messages matched the pattern.
+    **Checked:** { $checked } messages
+    **Pattern:** `{ $pattern }`
+purge-done-failures =
+    Purge completed with some failures!
+    **Deleted:** { $deleted }
+    **Failed:** { $failed }
+    **Total checked:** { $checked }
+    **Pattern:** `{ $pattern }`
+    **Flags:** { $flags }
+    **Duration:** { $minutes } minutes
+purge-done =
+    Purge completed successfully!
+    **Deleted:** { $deleted }
+    **Total checked:** { $checked }
+    **Pattern:** `{ $pattern }`
+    **Flags:** { $flags }
+    **Duration:** { $minutes } minutes
+purge-skipped = **Skipped:** { $count } channels you can't manage messages in
+purge-channels = **Channels:**
+purge-channel-line = { $channel }: checked { $checked }, deleted { $deleted }, failed { $failed }
+purge-channel-unreadable = { $channel }: could not be read ({ $error })
+purge-channels-quiet = *{ $count } other channels had no matches ({ $checked } messages checked)*
+purge-channels-more = *... and { $count } more*
+
+## Channel locks
+
+lock-duration-range = Locks must last between 1 minute and { $max_days } days
+lock-channel-or-server = Pick either a channel or the whole server
+lock-no-public-channels = No channel here lets @everyone talk
+lock-done = Locked { $channels }
+lock-skipped-locked = already locked
+lock-reason = **Reason:** { $reason }
+lock-expires = **Unlocks:** { $time }
+lock-no-changes = No channels changed.
+lock-skipped = *Skipped ({ $label }):* { $channels }
+lock-failed = **Failed:** { $errors }
+unlock-none-locked = No channel in this server is locked
+unlock-not-locked = That channel is not locked
+unlock-done = Unlocked { $channels }
+unlock-skipped-unlocked = not locked
+slowmode-range = Slowmode must be between 1 second and 6 hours
+slowmode-off = Slowmode turned off in { $channels }
+slowmode-set = Slowmode set to { $delay } in { $channels }
+slowmode-skipped-unchanged = unchanged
+
+## Message log
+
+messagelog-announcement = Edited and deleted messages from watched channels will be posted here (set by { $user }).
+messagelog-set = Message logs will be posted to { $channel }. Pick channels to log with `/admin messagelog watch`.
+messagelog-cleared = Message logging turned off. Watched channels are kept for when it is set again.
+messagelog-watched = Edits and deletions in { $channel } will be logged. Only messages sent from now on can be shown in full.
+messagelog-already-watched = { $channel } is already logged.
+messagelog-unwatched = { $channel } is no longer logged.
+messagelog-not-watched = { $channel } was not logged.
+messagelog-show-channel = **Message log:** { $channel }
+messagelog-show-off = **Message log:** off, set a channel with `/admin messagelog set`
+messagelog-show-watched = **Watched channels:** { $channels }
+messagelog-show-no-watched = **Watched channels:** none
+messagelog-edited-title = Message edited
+messagelog-edited-description = { $user } edited a message in { $channel }
+messagelog-deleted-title = Message deleted
+messagelog-deleted-description = A message by { $user } was deleted in { $channel }
+messagelog-deleted-uncached = A message that was not cached was deleted in { $channel }
+messagelog-bulk-title = Messages bulk deleted
+messagelog-bulk-description = { $count } messages were deleted in { $channel }, { $cached } of them cached
+messagelog-bulk-attachments = *(+{ $count } attachments)*
+messagelog-field-before = Before
+messagelog-field-after = After
+messagelog-field-content = Content
+messagelog-field-author = Author
+messagelog-field-sent = Sent
+messagelog-field-attachments = Attachments
+messagelog-not-cached = *Not cached*
+messagelog-no-text = *No text*
+messagelog-footer-message = Message { $id }
+messagelog-footer-details = User { $user } · Message { $id }
+
+## Mod-log channel
+
+modlog-announcement = Moderation actions in this server will be posted here (set by { $user }).
+modlog-set = Moderation actions will be posted to { $channel }
+modlog-cleared = Moderation actions will no longer be posted. The audit log still records them.
+modlog-show = Moderation actions are posted to { $channel }
+modlog-show-off = No mod-log channel configured. Set one with `/admin modlog set`.
+
+## Permission rules
+
+permissions-unknown-command = There is no command called `{ $command }`
+permissions-unknown-permission = `{ $permission }` is not a permission
+permissions-one-subject = Give exactly one of `role`, `user` or `permission`
+permissions-subject-permission = members with { $permissions }
+permissions-allowed = Rule #{ $id }: { $subject } can now use `{ $command }`
+permissions-denied = Rule #{ $id }: { $subject } can no longer use `{ $command }`
+permissions-integrations = The `/{ $command }` slash command also needs to be allowed for them under Server Settings → Integrations, text commands work right away.
+permissions-list-empty = No permission rules, every command needs its usual permissions. Add one with `/admin permissions allow`.
+permissions-list-title = **{ $count } Permission Rules**
+permissions-effect-allow = allow
+permissions-effect-deny = deny
+permissions-removed = Permission rule #{ $id } removed
+permissions-not-found = Permission rule #{ $id } not found
+
+## Role menus
+
+rolemenu-title-length = Titles must be between 1 and { $max } characters
+rolemenu-exclusive-max = Exclusive menus already allow one role, leave out `max_selections`
+rolemenu-created =
+    **Role menu #{ $id }** created
+    Add roles with `/admin rolemenu add`, then post it with `/admin rolemenu publish`.
+rolemenu-not-found = Role menu not found.
+rolemenu-role-elsewhere = That role is not in this server
+rolemenu-role-above-you = You can only add roles below your highest role
+rolemenu-role-managed = That role can't be assigned by hand
+rolemenu-role-elevated = That role has moderation permissions and can't be self-assigned
+rolemenu-role-too-high = That role is above my highest role, move my role up first
+rolemenu-role-deleted = That role no longer exists
+rolemenu-label-too-long = Labels can be at most { $max } characters
+rolemenu-invalid-emoji = `{ $emoji }` is not an emoji
+rolemenu-description-too-long = Descriptions can be at most { $max } characters
+rolemenu-too-many-roles = Menus can hold at most { $max } roles
+rolemenu-role-added = Added { $role } to role menu #{ $id }
+rolemenu-role-removed = Removed { $role } from role menu #{ $id }
+rolemenu-role-not-in-menu = { $role } is not part of role menu #{ $id }
+rolemenu-not-published = Post it with `/admin rolemenu publish` when it's ready.
+rolemenu-refreshed = The posted menu was updated.
+rolemenu-refresh-failed = *The posted menu could not be updated, publish it again.*
+rolemenu-empty = Add at least one role with `/admin rolemenu add` first
+rolemenu-published = Role menu #{ $id } is live: { $link }
+rolemenu-list-empty = No role menus yet. Create one with `/admin rolemenu create`.
+rolemenu-list-title = **{ $count } Role Menus**
+rolemenu-list-entry = **#{ $id }** { $title } ({ $style }, { $count } roles)
+rolemenu-list-pick-one = , pick one
+rolemenu-list-up-to = , up to { $max }
+rolemenu-list-requires = , requires { $role }
+rolemenu-list-posted = { "   " }Posted in { $channel }
+rolemenu-list-unpublished = { "   " }*Not published*
+rolemenu-deleted = Successfully deleted role menu #{ $id }
+rolemenu-requires = *Requires { $role }*
+rolemenu-pick-one = Pick one
+rolemenu-pick-up-to = Pick up to { $max }
+rolemenu-placeholder = Choose your roles
+rolemenu-menu-gone = This role menu no longer exists.
+rolemenu-role-gone = That role is no longer part of this menu.
+rolemenu-hold-limit = You can hold at most { $limit } roles from this menu, remove one first.
+rolemenu-pick-limit = You can pick at most { $limit } roles from this menu.
+rolemenu-required-role = You need the { $role } role to use this menu.
+rolemenu-role-refused = { $role } can't be handed out right now, ask a moderator to check this menu.
+rolemenu-unchanged = Nothing to change.
+rolemenu-added = Added { $roles }
+rolemenu-removed = Removed { $roles }
+rolemenu-failed = Could not update { $roles }, ask a moderator to check my role position
+
+## Sanctions
+
+sanction-timeout = { $duration } timeout
+sanction-kick = kick
+sanction-ban = ban
+sanction-no-reason = No reason provided
+sanction-delete-days-range = Message deletion is limited to the last 7 days
+sanction-tempban-range = Temporary bans must be between 1 minute and { $max_days } days
+sanction-self = You can't apply a { $sanction } to yourself
+sanction-bot = I can't apply a { $sanction } to myself
+sanction-not-member = That user is not a member of this server
+sanction-outranked-you = You can't apply a { $sanction } to someone whose highest role is equal to or above yours
+sanction-outranked-bot = I can't apply a { $sanction } to someone whose highest role is equal to or above mine
+sanction-failed = Could not apply { $sanction }: { $error }
+sanction-applied =
+    Applied **{ $sanction }** to **{ $user }** ({ $mention })
+    **Reason:** { $reason }
+sanction-case = **Case:** #{ $number }
+sanction-expires = **Expires:** { $time }
+sanction-expiry-not-saved = *Could not save the expiry, unban them manually when it ends.*
+sanction-not-notified = *Could not DM them about it.*
+sanction-not-banned = { $user } is not banned
+sanction-unban-failed = Could not unban { $user }: { $error }
+sanction-unbanned =
+    Unbanned **{ $user }** ({ $mention })
+    **Reason:** { $reason }
+sanction-dm-timeout =
+    You have been timed out in **{ $server }** for { $duration }
+    **Reason:** { $reason }
+sanction-dm-kick =
+    You have been kicked from **{ $server }**
+    **Reason:** { $reason }
+sanction-dm-ban =
+    You have been banned from **{ $server }**
+    **Reason:** { $reason }
+sanction-dm-tempban =
+    You have been banned from **{ $server }** for { $duration }
+    **Reason:** { $reason }
+
+## Warnings
+
+warn-bot = Bots can't be warned
+warn-self = You can't warn yourself
+warn-issued =
+    **Warning #{ $id }** issued to { $user }
+    **Reason:** { $reason }
+    **Active warnings:** { $active }
+warn-escalated = **Escalation:** { $sanction } applied for reaching { $threshold } warnings
+warn-escalation-failed = **Escalation failed:** could not apply { $sanction } ({ $error })
+warnings-none = { $user } has no warnings.
+warnings-title = **Warnings for { $user }** ({ $active } active)
+warnings-entry =
+    **#{ $id }** { $date } by { $moderator }
+    { "   " }{ $reason }
+warnings-entry-revoked =
+    **#{ $id }** { $date } by { $moderator } *(revoked)*
+    { "   " }{ $reason }
+warnings-more = *... and { $count } more warnings*
+unwarn-not-found = Warning not found or already revoked.
+unwarn-revoked = Revoked warning #{ $id } for { $user }
+escalation-duration-timeout-only = Only timeouts take a duration
+escalation-set = Members reaching **{ $threshold }** warnings will now receive a **{ $sanction }**
+escalation-list-empty = No warning escalations configured. Add one with `/admin escalation set`.
+escalation-list-title = **Warning Escalations**
+escalation-list-entry = **{ $threshold } warnings** → { $sanction }
+escalation-removed = Removed the escalation for { $threshold } warnings
+escalation-not-found = No escalation is configured for that count.
+
+## Owner commands
+
+reload-none = none
+reload-applied = **Applied:** { $fields }
+reload-needs-restart = **Changed, needs a restart:** { $fields }
+owner-reload-failed = Reload failed, the running config is unchanged: { $error }
+owner-reloaded =
+    **Configuration reloaded**
+    { $report }
+owner-sync-off = Command registration is turned off, nothing was registered
+owner-synced = Registered { $count } commands in { $scopes }
+owner-cleared = Removed the commands in { $scopes }. Text commands keep working, use `sync` to register again
+owner-scope-none = nowhere
+owner-scope-global = global
+owner-scope-guild = guild { $id }
+owner-approved = Approved { $guild }
+owner-already-approved = { $guild } was already approved
+owner-not-approved = { $guild } was not approved
+owner-revoked = Revoked { $guild }
+owner-revoked-open = Revoked { $guild }. Approval is turned off, so this only matters once it is turned on
+owner-revoked-leaving = Revoked { $guild }, leaving it
+owner-revoked-ignored = Revoked { $guild }, the bot ignores it from now on
+owner-guilds-title = **{ $count } Servers** (approval: { $approval })
+owner-guild-approved = approved
+owner-guild-not-approved = not approved
+owner-guild-not-joined = approved, not joined
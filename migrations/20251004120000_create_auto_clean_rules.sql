@@ -0,0 +1,23 @@
+CREATE TABLE auto_clean_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    interval_minutes INTEGER NOT NULL,
+    max_age_minutes INTEGER,
+    pattern TEXT,
+    case_insensitive BOOLEAN NOT NULL DEFAULT 0,
+    multi_line BOOLEAN NOT NULL DEFAULT 0,
+    literal BOOLEAN NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    created_by_id TEXT NOT NULL,
+    created_by_name TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_run_at DATETIME,
+    last_checked INTEGER,
+    last_deleted INTEGER,
+    last_failed INTEGER,
+    last_error TEXT
+);
+
+CREATE INDEX idx_auto_clean_rules_guild ON auto_clean_rules(guild_id);
+CREATE INDEX idx_auto_clean_rules_enabled ON auto_clean_rules(enabled);
@@ -0,0 +1,18 @@
+CREATE TABLE moderation_audit (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    actor_id TEXT NOT NULL,
+    actor_name TEXT NOT NULL,
+    action TEXT NOT NULL,
+    target TEXT,
+    parameters TEXT NOT NULL DEFAULT '{}',
+    outcome TEXT NOT NULL,
+    checked_count INTEGER NOT NULL DEFAULT 0,
+    affected_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_moderation_audit_guild_created_at ON moderation_audit(guild_id, created_at DESC);
+CREATE INDEX idx_moderation_audit_actor ON moderation_audit(guild_id, actor_id);
+CREATE INDEX idx_moderation_audit_action ON moderation_audit(guild_id, action);
@@ -0,0 +1,26 @@
+CREATE TABLE warnings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    user_name TEXT NOT NULL,
+    moderator_id TEXT NOT NULL,
+    moderator_name TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT 1,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_by_id TEXT,
+    revoked_at DATETIME
+);
+
+CREATE INDEX idx_warnings_guild_user ON warnings(guild_id, user_id, active);
+
+CREATE TABLE warning_escalations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    threshold INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    duration_minutes INTEGER,
+    created_by_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (guild_id, threshold)
+);
@@ -0,0 +1,17 @@
+CREATE TABLE temporary_bans (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    user_name TEXT NOT NULL,
+    moderator_id TEXT NOT NULL,
+    moderator_name TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    expires_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    lifted_at DATETIME,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT
+);
+
+CREATE INDEX idx_temporary_bans_pending ON temporary_bans(lifted_at, expires_at);
+CREATE INDEX idx_temporary_bans_guild_user ON temporary_bans(guild_id, user_id);
@@ -0,0 +1,19 @@
+CREATE TABLE automod_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    pattern TEXT,
+    threshold INTEGER,
+    case_insensitive BOOLEAN NOT NULL DEFAULT 0,
+    actions TEXT NOT NULL,
+    timeout_minutes INTEGER,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    created_by_id TEXT NOT NULL,
+    created_by_name TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    hit_count INTEGER NOT NULL DEFAULT 0,
+    last_hit_at DATETIME
+);
+
+CREATE INDEX idx_automod_rules_guild ON automod_rules(guild_id, enabled);
@@ -0,0 +1,18 @@
+CREATE TABLE antispam_settings (
+    guild_id TEXT PRIMARY KEY NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 0,
+    flood_messages INTEGER NOT NULL,
+    flood_seconds INTEGER NOT NULL,
+    duplicate_messages INTEGER NOT NULL,
+    duplicate_seconds INTEGER NOT NULL,
+    mention_limit INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    timeout_minutes INTEGER NOT NULL,
+    lockdown_minutes INTEGER NOT NULL,
+    raid_joins INTEGER NOT NULL,
+    raid_seconds INTEGER NOT NULL,
+    raid_account_age_days INTEGER NOT NULL,
+    raid_action TEXT NOT NULL,
+    updated_by_id TEXT NOT NULL,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,5 @@
+CREATE TABLE guild_settings (
+    guild_id TEXT PRIMARY KEY NOT NULL,
+    mod_log_channel_id TEXT,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
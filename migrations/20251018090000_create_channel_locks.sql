@@ -0,0 +1,20 @@
+CREATE TABLE channel_locks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    -- @everyone overwrite before the lock; both NULL when the channel had none.
+    previous_allow TEXT,
+    previous_deny TEXT,
+    moderator_id TEXT NOT NULL,
+    moderator_name TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    expires_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    unlocked_at DATETIME,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT
+);
+
+CREATE UNIQUE INDEX idx_channel_locks_active ON channel_locks(channel_id) WHERE unlocked_at IS NULL;
+CREATE INDEX idx_channel_locks_pending ON channel_locks(unlocked_at, expires_at);
+CREATE INDEX idx_channel_locks_guild ON channel_locks(guild_id);
@@ -0,0 +1,27 @@
+CREATE TABLE role_menus (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    style TEXT NOT NULL,
+    exclusive BOOLEAN NOT NULL DEFAULT FALSE,
+    max_selections INTEGER,
+    required_role_id TEXT,
+    -- Set once the menu has been published.
+    channel_id TEXT,
+    message_id TEXT,
+    created_by_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_role_menus_guild ON role_menus(guild_id);
+
+CREATE TABLE role_menu_options (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    menu_id INTEGER NOT NULL REFERENCES role_menus(id) ON DELETE CASCADE,
+    role_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    emoji TEXT,
+    description TEXT,
+    UNIQUE (menu_id, role_id)
+);
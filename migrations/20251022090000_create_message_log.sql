@@ -0,0 +1,9 @@
+ALTER TABLE guild_settings ADD COLUMN message_log_channel_id TEXT;
+
+CREATE TABLE message_log_channels (
+    guild_id TEXT NOT NULL,
+    channel_id TEXT NOT NULL,
+    added_by_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (guild_id, channel_id)
+);
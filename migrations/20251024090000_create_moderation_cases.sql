@@ -0,0 +1,21 @@
+CREATE TABLE moderation_cases (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    case_number INTEGER NOT NULL,
+    audit_id INTEGER NOT NULL REFERENCES moderation_audit(id),
+    reason TEXT,
+    reason_updated_by_id TEXT,
+    reason_updated_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (guild_id, case_number)
+);
+
+CREATE TABLE case_evidence (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    case_id INTEGER NOT NULL REFERENCES moderation_cases(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    added_by_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_case_evidence_case ON case_evidence(case_id);
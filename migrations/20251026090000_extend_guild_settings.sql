@@ -0,0 +1,5 @@
+ALTER TABLE guild_settings ADD COLUMN prefix TEXT;
+ALTER TABLE guild_settings ADD COLUMN locale TEXT;
+ALTER TABLE guild_settings ADD COLUMN suggestion_channel_id TEXT;
+-- Comma separated, so modules added later start out enabled everywhere.
+ALTER TABLE guild_settings ADD COLUMN disabled_modules TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,13 @@
+CREATE TABLE permission_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    command TEXT NOT NULL,
+    subject_type TEXT NOT NULL,
+    subject_id TEXT NOT NULL,
+    effect TEXT NOT NULL,
+    created_by_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (guild_id, command, subject_type, subject_id)
+);
+
+CREATE INDEX idx_permission_rules_guild ON permission_rules(guild_id);
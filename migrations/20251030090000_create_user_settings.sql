@@ -0,0 +1,5 @@
+CREATE TABLE user_settings (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    locale TEXT,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,5 @@
+CREATE TABLE approved_guilds (
+    guild_id TEXT PRIMARY KEY NOT NULL,
+    approved_by_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+-- Every overwrite of the channel before the lock, as JSON. Locks taken before this column
+-- existed only have the @everyone snapshot in previous_allow and previous_deny.
+ALTER TABLE channel_locks ADD COLUMN previous_overwrites TEXT;
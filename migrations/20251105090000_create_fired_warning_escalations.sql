@@ -0,0 +1,9 @@
+CREATE TABLE fired_warning_escalations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    guild_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    threshold INTEGER NOT NULL,
+    warning_id INTEGER NOT NULL,
+    fired_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (guild_id, user_id, threshold)
+);
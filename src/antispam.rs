@@ -0,0 +1,673 @@
+use crate::{
+    audit::{self, AuditAction, AuditOutcome, AuditRecord},
+    automod,
+    bot::Data,
+    database::{self, AntispamSettings},
+    lockdown,
+    moderation::{self, Moderator},
+    purge,
+};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, GatewayIntents, GuildId, Member, Message, MessageId, UserId,
+};
+use sqlx::SqlitePool;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// How a guild responds to a member spamming messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum AntispamAction {
+    #[name = "timeout"]
+    Timeout,
+    #[name = "delete_recent"]
+    DeleteRecent,
+    #[name = "lockdown"]
+    Lockdown,
+}
+
+impl AntispamAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::DeleteRecent => "delete_recent",
+            Self::Lockdown => "lockdown",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "timeout" => Some(Self::Timeout),
+            "delete_recent" => Some(Self::DeleteRecent),
+            "lockdown" => Some(Self::Lockdown),
+            _ => None,
+        }
+    }
+}
+
+/// How a guild responds to a burst of new accounts joining.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum RaidAction {
+    #[name = "timeout"]
+    Timeout,
+    #[name = "lockdown"]
+    Lockdown,
+    #[name = "log"]
+    Log,
+}
+
+impl RaidAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Lockdown => "lockdown",
+            Self::Log => "log",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "timeout" => Some(Self::Timeout),
+            "lockdown" => Some(Self::Lockdown),
+            "log" => Some(Self::Log),
+            _ => None,
+        }
+    }
+}
+
+/// Settings used for guilds that have not configured anti-spam yet.
+pub fn default_settings(guild_id: GuildId) -> AntispamSettings {
+    AntispamSettings {
+        guild_id: guild_id.to_string(),
+        enabled: false,
+        flood_messages: 6,
+        flood_seconds: 5,
+        duplicate_messages: 4,
+        duplicate_seconds: 30,
+        mention_limit: 8,
+        action: AntispamAction::Timeout.as_str().to_string(),
+        timeout_minutes: 10,
+        lockdown_minutes: 10,
+        raid_joins: 10,
+        raid_seconds: 30,
+        raid_account_age_days: 7,
+        raid_action: RaidAction::Log.as_str().to_string(),
+    }
+}
+
+/// Why a member was flagged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpamTrigger {
+    Flood { messages: usize, seconds: i64 },
+    Duplicate { messages: usize, channels: usize },
+    Mentions { mentions: usize },
+}
+
+impl fmt::Display for SpamTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flood { messages, seconds } => {
+                write!(f, "{} messages in {}s", messages, seconds)
+            }
+            Self::Duplicate { messages, channels } => write!(
+                f,
+                "{} identical messages across {} channel(s)",
+                messages, channels
+            ),
+            Self::Mentions { mentions } => write!(f, "{} mentions in one message", mentions),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RecentMessage {
+    at: Instant,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    fingerprint: Option<u64>,
+}
+
+/// Hash of the normalised content, `None` for messages without text.
+fn fingerprint(content: &str) -> Option<u64> {
+    let normalized = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalized.is_empty() {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    normalized.to_lowercase().hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn window(seconds: i64) -> std::time::Duration {
+    std::time::Duration::from_secs(seconds.max(0) as u64)
+}
+
+/// Checks a member's recent messages, newest last, against the flood and duplicate limits.
+fn detect_spam(
+    messages: &VecDeque<RecentMessage>,
+    settings: &AntispamSettings,
+    now: Instant,
+) -> Option<SpamTrigger> {
+    if settings.flood_messages > 0 {
+        let recent = messages
+            .iter()
+            .filter(|m| now.duration_since(m.at) <= window(settings.flood_seconds))
+            .count();
+        if recent >= settings.flood_messages as usize {
+            return Some(SpamTrigger::Flood {
+                messages: recent,
+                seconds: settings.flood_seconds,
+            });
+        }
+    }
+
+    if settings.duplicate_messages > 0 {
+        let latest = messages.back()?.fingerprint?;
+        let duplicates: Vec<&RecentMessage> = messages
+            .iter()
+            .filter(|m| now.duration_since(m.at) <= window(settings.duplicate_seconds))
+            .filter(|m| m.fingerprint == Some(latest))
+            .collect();
+
+        if duplicates.len() >= settings.duplicate_messages as usize {
+            let mut channels: Vec<ChannelId> = duplicates.iter().map(|m| m.channel_id).collect();
+            channels.sort();
+            channels.dedup();
+
+            return Some(SpamTrigger::Duplicate {
+                messages: duplicates.len(),
+                channels: channels.len(),
+            });
+        }
+    }
+
+    None
+}
+
+/// Returns whether enough joins landed inside the raid window.
+fn detect_raid(
+    joins: &VecDeque<(Instant, UserId)>,
+    settings: &AntispamSettings,
+    now: Instant,
+) -> bool {
+    settings.raid_joins > 0
+        && joins
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= window(settings.raid_seconds))
+            .count()
+            >= settings.raid_joins as usize
+}
+
+#[derive(Debug, Default)]
+struct State {
+    settings: HashMap<GuildId, Arc<AntispamSettings>>,
+    messages: HashMap<(GuildId, UserId), VecDeque<RecentMessage>>,
+    joins: HashMap<GuildId, VecDeque<(Instant, UserId)>>,
+    recorded: u64,
+}
+
+impl State {
+    /// Drops windows that have gone quiet so the maps do not grow with every member seen.
+    fn prune(&mut self, now: Instant) {
+        let horizon = self
+            .settings
+            .values()
+            .map(|s| s.flood_seconds.max(s.duplicate_seconds).max(s.raid_seconds))
+            .max()
+            .unwrap_or(0);
+
+        self.messages.retain(|_, messages| {
+            messages
+                .back()
+                .is_some_and(|m| now.duration_since(m.at) <= window(horizon))
+        });
+        self.joins.retain(|_, joins| {
+            joins
+                .back()
+                .is_some_and(|(at, _)| now.duration_since(*at) <= window(horizon))
+        });
+    }
+}
+
+/// In-memory sliding windows of recent messages and joins, shared through [`Data`].
+#[derive(Debug, Clone, Default)]
+pub struct AntispamTracker {
+    state: Arc<Mutex<State>>,
+}
+
+impl AntispamTracker {
+    /// Settings of `guild_id`, loaded on first use. Guilds without settings get the defaults.
+    pub async fn settings(
+        &self,
+        database: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Arc<AntispamSettings>> {
+        if let Some(settings) = self.state.lock().unwrap().settings.get(&guild_id) {
+            return Ok(settings.clone());
+        }
+
+        let settings = database::get_antispam_settings(database, &guild_id.to_string())
+            .await?
+            .unwrap_or_else(|| default_settings(guild_id));
+        let settings = Arc::new(settings);
+
+        self.state
+            .lock()
+            .unwrap()
+            .settings
+            .insert(guild_id, settings.clone());
+
+        Ok(settings)
+    }
+
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.state.lock().unwrap().settings.remove(&guild_id);
+    }
+
+    /// Adds a message to its author's window. When it trips a limit, the window is cleared and
+    /// its messages are returned so the response can act on all of them.
+    fn record_message(
+        &self,
+        guild_id: GuildId,
+        message: &Message,
+        settings: &AntispamSettings,
+        now: Instant,
+    ) -> Option<(SpamTrigger, Vec<RecentMessage>)> {
+        let mut state = self.state.lock().unwrap();
+
+        state.recorded += 1;
+        if state.recorded.is_multiple_of(1000) {
+            state.prune(now);
+        }
+
+        let horizon = window(settings.flood_seconds.max(settings.duplicate_seconds));
+        let messages = state
+            .messages
+            .entry((guild_id, message.author.id))
+            .or_default();
+
+        messages.retain(|m| now.duration_since(m.at) <= horizon);
+        messages.push_back(RecentMessage {
+            at: now,
+            channel_id: message.channel_id,
+            message_id: message.id,
+            fingerprint: fingerprint(&message.content),
+        });
+
+        let trigger = detect_spam(messages, settings, now)?;
+        Some((trigger, messages.drain(..).collect()))
+    }
+
+    /// Adds a join to the guild's window, returning everyone in the burst when it trips.
+    fn record_join(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        settings: &AntispamSettings,
+        now: Instant,
+    ) -> Option<Vec<UserId>> {
+        let mut state = self.state.lock().unwrap();
+        let joins = state.joins.entry(guild_id).or_default();
+
+        joins.retain(|(at, _)| now.duration_since(*at) <= window(settings.raid_seconds));
+        joins.push_back((now, user_id));
+
+        if !detect_raid(joins, settings, now) {
+            return None;
+        }
+
+        Some(joins.drain(..).map(|(_, user_id)| user_id).collect())
+    }
+}
+
+fn bot_moderator(ctx: &serenity::Context) -> Moderator {
+    let user = ctx.cache.current_user();
+    Moderator::new(user.id, format!("{} (anti-spam)", user.name))
+}
+
+/// Tracks a new message and responds when its author floods, repeats or mass-mentions.
+#[tracing::instrument(skip(ctx, data, message), fields(message_id = %message.id))]
+pub async fn check_message(ctx: &serenity::Context, data: &Data, message: &Message) -> Result<()> {
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+    if message.author.bot {
+        return Ok(());
+    }
+
+    let settings = data.antispam.settings(&data.database, guild_id).await?;
+    if !settings.enabled || automod::is_exempt(ctx, guild_id, message) {
+        return Ok(());
+    }
+
+    let mentions = message.mentions.len() + message.mention_roles.len();
+    let detected = if settings.mention_limit > 0 && mentions >= settings.mention_limit as usize {
+        Some((
+            SpamTrigger::Mentions { mentions },
+            vec![RecentMessage {
+                at: Instant::now(),
+                channel_id: message.channel_id,
+                message_id: message.id,
+                fingerprint: None,
+            }],
+        ))
+    } else {
+        data.antispam
+            .record_message(guild_id, message, &settings, Instant::now())
+    };
+
+    let Some((trigger, recent)) = detected else {
+        return Ok(());
+    };
+
+    let action = AntispamAction::parse(&settings.action).unwrap_or(AntispamAction::Timeout);
+    let bot = bot_moderator(ctx);
+    let reason = format!("Anti-spam: {}", trigger);
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %message.author.id,
+        trigger = %trigger,
+        action = %action.as_str(),
+        "Spam detected"
+    );
+
+    let mut errors = Vec::new();
+    let mut affected = 0;
+
+    match action {
+        AntispamAction::Timeout => {
+            match moderation::timeout_member(
+                &ctx.http,
+                &data.database,
+                guild_id,
+                message.author.id,
+                Duration::minutes(settings.timeout_minutes),
+                &bot,
+                &reason,
+            )
+            .await
+            {
+                Some(error) => errors.push(error),
+                None => affected = 1,
+            }
+        }
+        AntispamAction::DeleteRecent => {
+            let mut by_channel: HashMap<ChannelId, Vec<MessageId>> = HashMap::new();
+            for m in &recent {
+                by_channel
+                    .entry(m.channel_id)
+                    .or_default()
+                    .push(m.message_id);
+            }
+
+            for (channel_id, ids) in by_channel {
+                let (deleted, failed) =
+                    purge::delete_messages(&ctx.http, channel_id, &ids, |_| async {}).await;
+                affected += deleted;
+                if failed > 0 {
+                    errors.push(format!(
+                        "{} message(s) in <#{}> not deleted",
+                        failed, channel_id
+                    ));
+                }
+            }
+        }
+        AntispamAction::Lockdown => {
+            let mut channels: Vec<ChannelId> = recent.iter().map(|m| m.channel_id).collect();
+            channels.sort();
+            channels.dedup();
+
+            let report = lockdown::lock_channels(
+                &ctx.http,
+                &data.database,
+                guild_id,
+                &channels,
+                &bot,
+                &reason,
+                Some(Utc::now() + Duration::minutes(settings.lockdown_minutes)),
+            )
+            .await;
+            affected = report.changed.len();
+            errors.extend(report.errors);
+        }
+    }
+
+    audit::record(
+        &ctx.http,
+        &data.database,
+        AuditRecord::new(guild_id, bot.id, &bot.name, AuditAction::Antispam)
+            .target(format!("<@{}>", message.author.id))
+            .parameters(serde_json::json!({
+                "trigger": trigger.to_string(),
+                "action": action.as_str(),
+                "channel_id": message.channel_id.to_string(),
+                "messages": recent.len(),
+                "errors": errors,
+            }))
+            .outcome(AuditOutcome::from_counts(
+                affected as i64,
+                errors.len() as i64,
+            ))
+            .counts(recent.len() as i64, affected as i64, errors.len() as i64),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Tracks a member join and responds when new accounts arrive in a burst. Skipped without the
+/// `guild_members` intent, which is what delivers joins in the first place.
+#[tracing::instrument(skip(ctx, data, member), fields(user_id = %member.user.id))]
+pub async fn check_join(ctx: &serenity::Context, data: &Data, member: &Member) -> Result<()> {
+    if member.user.bot
+        || !data
+            .config
+            .get()
+            .discord
+            .intents
+            .contains(GatewayIntents::GUILD_MEMBERS)
+    {
+        return Ok(());
+    }
+
+    let guild_id = member.guild_id;
+    let settings = data.antispam.settings(&data.database, guild_id).await?;
+    if !settings.enabled || settings.raid_joins == 0 {
+        return Ok(());
+    }
+
+    let account_age = Utc::now().timestamp() - member.user.created_at().unix_timestamp();
+    if settings.raid_account_age_days > 0
+        && account_age > Duration::days(settings.raid_account_age_days).num_seconds()
+    {
+        return Ok(());
+    }
+
+    let Some(raiders) =
+        data.antispam
+            .record_join(guild_id, member.user.id, &settings, Instant::now())
+    else {
+        return Ok(());
+    };
+
+    let action = RaidAction::parse(&settings.raid_action).unwrap_or(RaidAction::Log);
+    let bot = bot_moderator(ctx);
+    let reason = format!(
+        "Anti-raid: {} new accounts joined within {}s",
+        raiders.len(),
+        settings.raid_seconds
+    );
+
+    tracing::warn!(
+        guild_id = %guild_id,
+        joins = %raiders.len(),
+        action = %action.as_str(),
+        "Join burst detected"
+    );
+
+    let mut errors = Vec::new();
+    let mut affected = 0;
+
+    match action {
+        RaidAction::Timeout => {
+            for user_id in &raiders {
+                match moderation::timeout_member(
+                    &ctx.http,
+                    &data.database,
+                    guild_id,
+                    *user_id,
+                    Duration::minutes(settings.timeout_minutes),
+                    &bot,
+                    &reason,
+                )
+                .await
+                {
+                    Some(error) => errors.push(format!("<@{}>: {}", user_id, error)),
+                    None => affected += 1,
+                }
+            }
+        }
+        RaidAction::Lockdown => {
+            let channels = ctx
+                .cache
+                .guild(guild_id)
+                .map(|guild| lockdown::public_channels(&guild))
+                .unwrap_or_default();
+            let report = lockdown::lock_channels(
+                &ctx.http,
+                &data.database,
+                guild_id,
+                &channels,
+                &bot,
+                &reason,
+                Some(Utc::now() + Duration::minutes(settings.lockdown_minutes)),
+            )
+            .await;
+            affected = report.changed.len();
+            errors.extend(report.errors);
+        }
+        RaidAction::Log => {}
+    }
+
+    audit::record(
+        &ctx.http,
+        &data.database,
+        AuditRecord::new(guild_id, bot.id, &bot.name, AuditAction::Antispam)
+            .parameters(serde_json::json!({
+                "trigger": "raid",
+                "action": action.as_str(),
+                "users": raiders.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+                "errors": errors,
+            }))
+            .outcome(AuditOutcome::from_counts(
+                affected as i64,
+                errors.len() as i64,
+            ))
+            .counts(raiders.len() as i64, affected as i64, errors.len() as i64),
+    )
+    .await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> AntispamSettings {
+        AntispamSettings {
+            enabled: true,
+            ..default_settings(GuildId::new(42))
+        }
+    }
+
+    fn message(at: Instant, channel: u64, content: &str) -> RecentMessage {
+        RecentMessage {
+            at,
+            channel_id: ChannelId::new(channel),
+            message_id: MessageId::new(1),
+            fingerprint: fingerprint(content),
+        }
+    }
+
+    #[test]
+    fn test_flood_detection() {
+        let settings = settings();
+        let now = Instant::now();
+
+        let mut messages: VecDeque<RecentMessage> = (0..5)
+            .map(|i| message(now, 1, &format!("message {}", i)))
+            .collect();
+        assert_eq!(detect_spam(&messages, &settings, now), None);
+
+        messages.push_back(message(now, 1, "one more"));
+        assert_eq!(
+            detect_spam(&messages, &settings, now),
+            Some(SpamTrigger::Flood {
+                messages: 6,
+                seconds: 5
+            })
+        );
+
+        let later = now + std::time::Duration::from_secs(10);
+        assert_eq!(detect_spam(&messages, &settings, later), None);
+    }
+
+    #[test]
+    fn test_duplicate_detection() {
+        let settings = AntispamSettings {
+            flood_messages: 0,
+            ..settings()
+        };
+        let now = Instant::now();
+
+        let messages: VecDeque<RecentMessage> = [
+            message(now, 1, "Buy   cheap stuff"),
+            message(now, 2, "buy cheap stuff"),
+            message(now, 3, "hello"),
+            message(now, 3, "BUY CHEAP STUFF"),
+            message(now, 4, "buy cheap stuff"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            detect_spam(&messages, &settings, now),
+            Some(SpamTrigger::Duplicate {
+                messages: 4,
+                channels: 4
+            })
+        );
+
+        let attachments: VecDeque<RecentMessage> = (0..5).map(|_| message(now, 1, "")).collect();
+        assert_eq!(detect_spam(&attachments, &settings, now), None);
+    }
+
+    #[test]
+    fn test_raid_detection() {
+        let settings = AntispamSettings {
+            raid_joins: 3,
+            ..settings()
+        };
+        let now = Instant::now();
+
+        let mut joins: VecDeque<(Instant, UserId)> =
+            (1..=2).map(|id| (now, UserId::new(id))).collect();
+        assert!(!detect_raid(&joins, &settings, now));
+
+        joins.push_back((now, UserId::new(3)));
+        assert!(detect_raid(&joins, &settings, now));
+        assert!(!detect_raid(
+            &joins,
+            &settings,
+            now + std::time::Duration::from_secs(60)
+        ));
+    }
+}
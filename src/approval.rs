@@ -0,0 +1,152 @@
+use crate::bot::Data;
+use crate::config::Approval;
+use crate::database;
+use crate::error::{Context, Error, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings::Locale;
+use anyhow::{Context as _, Result};
+use poise::serenity_prelude::{self as serenity, CreateMessage, Guild, GuildId};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Whether guilds are approved, loaded on first use and dropped when an owner changes it.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalCache {
+    guilds: Arc<RwLock<HashMap<GuildId, bool>>>,
+}
+
+impl ApprovalCache {
+    pub async fn is_approved(&self, database: &SqlitePool, guild_id: GuildId) -> Result<bool> {
+        if let Some(approved) = self.guilds.read().unwrap().get(&guild_id) {
+            return Ok(*approved);
+        }
+
+        let approved = database::is_guild_approved(database, &guild_id.to_string()).await?;
+        self.guilds.write().unwrap().insert(guild_id, approved);
+
+        Ok(approved)
+    }
+
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.guilds.write().unwrap().remove(&guild_id);
+    }
+}
+
+/// Whether the bot should act in `guild_id`: always when approval is off, otherwise only once
+/// an owner approved the guild.
+pub async fn is_active(data: &Data, guild_id: GuildId) -> bool {
+    if data.config.get().guilds.approval == Approval::Open {
+        return true;
+    }
+
+    match data.approvals.is_approved(&data.database, guild_id).await {
+        Ok(approved) => approved,
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to check guild approval");
+            false
+        }
+    }
+}
+
+/// Global command check: refuses commands in guilds waiting for approval. Owners pass so they
+/// can approve a guild from inside it.
+pub async fn approval_check(ctx: Context<'_>) -> std::result::Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+    if ctx.data().config.get().guilds.approval == Approval::Open
+        || ctx.framework().options().owners.contains(&ctx.author().id)
+    {
+        return Ok(true);
+    }
+
+    if ctx
+        .data()
+        .approvals
+        .is_approved(&ctx.data().database, guild_id)
+        .await?
+    {
+        return Ok(true);
+    }
+
+    tracing::debug!(
+        guild_id = %guild_id,
+        command = %ctx.command().qualified_name,
+        "Command refused, guild not approved"
+    );
+    let locale = i18n::locale(ctx).await;
+    Err(bot_error(tr!(
+        locale,
+        "approval-pending",
+        guild_id = guild_id
+    )))
+}
+
+/// Greets a guild the bot was added to that no owner approved, then leaves it when approval is
+/// `leave`. Guilds the bot was already in when approval was turned on are only ignored, so a
+/// config change never makes it leave servers in bulk.
+#[tracing::instrument(skip(ctx, data, guild), fields(guild_id = %guild.id))]
+pub async fn check_guild(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild: &Guild,
+    is_new: bool,
+) -> Result<()> {
+    let approval = data.config.get().guilds.approval;
+    if approval == Approval::Open || data.approvals.is_approved(&data.database, guild.id).await? {
+        return Ok(());
+    }
+
+    if !is_new {
+        tracing::warn!(
+            guild_id = %guild.id,
+            guild_name = %guild.name,
+            "Guild is not approved, ignoring it until an owner approves it"
+        );
+        return Ok(());
+    }
+
+    tracing::warn!(
+        guild_id = %guild.id,
+        guild_name = %guild.name,
+        approval = ?approval,
+        "Added to a guild that is not approved"
+    );
+
+    let locale = Locale::from_discord(&guild.preferred_locale).unwrap_or_default();
+    let notice = match approval {
+        Approval::Leave => tr!(locale, "approval-notice-leave", guild_id = guild.id),
+        _ => tr!(locale, "approval-notice-dormant", guild_id = guild.id),
+    };
+    send_notice(ctx, guild, &notice).await;
+
+    if approval == Approval::Leave {
+        guild
+            .id
+            .leave(&ctx.http)
+            .await
+            .context("Failed to leave unapproved guild")?;
+        tracing::info!(guild_id = %guild.id, "Left unapproved guild");
+    }
+
+    Ok(())
+}
+
+/// Posts `notice` in the guild's system channel, or sends it to the guild owner when there is
+/// none or the bot can't post there.
+async fn send_notice(ctx: &serenity::Context, guild: &Guild, notice: &str) {
+    if let Some(channel_id) = guild.system_channel_id {
+        match channel_id.say(&ctx.http, notice).await {
+            Ok(_) => return,
+            Err(e) => {
+                tracing::debug!(guild_id = %guild.id, error = %e, "Failed to post approval notice")
+            }
+        }
+    }
+
+    let message = CreateMessage::new().content(notice);
+    if let Err(e) = guild.owner_id.direct_message(&ctx.http, message).await {
+        tracing::warn!(guild_id = %guild.id, error = %e, "Failed to send approval notice");
+    }
+}
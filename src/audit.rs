@@ -0,0 +1,472 @@
+use crate::database::{self, NewAuditEntry};
+use crate::i18n::{self, tr};
+use crate::scheduler::parse_id;
+use crate::settings::Locale;
+use poise::serenity_prelude::{
+    ChannelId, Colour, CreateEmbed, CreateEmbedFooter, CreateMessage, GuildId, Http, Timestamp,
+    UserId,
+};
+use sqlx::SqlitePool;
+use std::fmt;
+
+/// Longest value Discord accepts in an embed field.
+const MAX_FIELD_LENGTH: usize = 1024;
+
+/// Parameters beyond this many are left to the audit log itself.
+const MAX_PARAMETER_FIELDS: usize = 10;
+
+/// Every kind of moderation action the bot records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum AuditAction {
+    #[name = "purge"]
+    Purge,
+    #[name = "auto_clean"]
+    AutoClean,
+    #[name = "warn"]
+    Warn,
+    #[name = "unwarn"]
+    Unwarn,
+    #[name = "timeout"]
+    Timeout,
+    #[name = "kick"]
+    Kick,
+    #[name = "ban"]
+    Ban,
+    #[name = "unban"]
+    Unban,
+    #[name = "automod"]
+    Automod,
+    #[name = "antispam"]
+    Antispam,
+    #[name = "lock"]
+    Lock,
+    #[name = "unlock"]
+    Unlock,
+    #[name = "slowmode"]
+    Slowmode,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Purge => "purge",
+            Self::AutoClean => "auto_clean",
+            Self::Warn => "warn",
+            Self::Unwarn => "unwarn",
+            Self::Timeout => "timeout",
+            Self::Kick => "kick",
+            Self::Ban => "ban",
+            Self::Unban => "unban",
+            Self::Automod => "automod",
+            Self::Antispam => "antispam",
+            Self::Lock => "lock",
+            Self::Unlock => "unlock",
+            Self::Slowmode => "slowmode",
+        }
+    }
+
+    /// Human readable name used as the mod-log embed title.
+    pub fn label(&self, locale: Locale) -> String {
+        let id = match self {
+            Self::Purge => "audit-action-purge",
+            Self::AutoClean => "audit-action-auto-clean",
+            Self::Warn => "audit-action-warn",
+            Self::Unwarn => "audit-action-unwarn",
+            Self::Timeout => "audit-action-timeout",
+            Self::Kick => "audit-action-kick",
+            Self::Ban => "audit-action-ban",
+            Self::Unban => "audit-action-unban",
+            Self::Automod => "audit-action-automod",
+            Self::Antispam => "audit-action-antispam",
+            Self::Lock => "audit-action-lock",
+            Self::Unlock => "audit-action-unlock",
+            Self::Slowmode => "audit-action-slowmode",
+        };
+        tr!(locale, id)
+    }
+
+    /// Whether the action opens a numbered moderation case.
+    pub fn opens_case(&self) -> bool {
+        !matches!(self, Self::AutoClean)
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOutcome {
+    Success,
+    Partial,
+    Failure,
+}
+
+impl AuditOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Partial => "partial",
+            Self::Failure => "failure",
+        }
+    }
+
+    pub fn label(&self, locale: Locale) -> String {
+        match self {
+            Self::Success => tr!(locale, "audit-outcome-success"),
+            Self::Partial => tr!(locale, "audit-outcome-partial"),
+            Self::Failure => tr!(locale, "audit-outcome-failure"),
+        }
+    }
+
+    /// Reads back the outcome stored by [`AuditOutcome::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "success" => Some(Self::Success),
+            "partial" => Some(Self::Partial),
+            "failure" => Some(Self::Failure),
+            _ => None,
+        }
+    }
+
+    fn colour(&self) -> Colour {
+        match self {
+            Self::Success => Colour::DARK_GREEN,
+            Self::Partial => Colour::ORANGE,
+            Self::Failure => Colour::RED,
+        }
+    }
+
+    /// Derives the outcome from how many items were handled and how many failed.
+    pub fn from_counts(affected: i64, failed: i64) -> Self {
+        match (affected, failed) {
+            (_, 0) => Self::Success,
+            (0, _) => Self::Failure,
+            _ => Self::Partial,
+        }
+    }
+}
+
+/// A moderation action about to be written to the audit log.
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    pub guild_id: GuildId,
+    pub actor_id: UserId,
+    pub actor_name: String,
+    pub action: AuditAction,
+    /// Who or what the action was aimed at, stored as a mention so it renders in Discord.
+    pub target: Option<String>,
+    pub parameters: serde_json::Value,
+    pub outcome: AuditOutcome,
+    pub checked: i64,
+    pub affected: i64,
+    pub failed: i64,
+}
+
+impl AuditRecord {
+    pub fn new(
+        guild_id: GuildId,
+        actor_id: UserId,
+        actor_name: impl Into<String>,
+        action: AuditAction,
+    ) -> Self {
+        Self {
+            guild_id,
+            actor_id,
+            actor_name: actor_name.into(),
+            action,
+            target: None,
+            parameters: serde_json::Value::Object(Default::default()),
+            outcome: AuditOutcome::Success,
+            checked: 0,
+            affected: 0,
+            failed: 0,
+        }
+    }
+
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn parameters(mut self, parameters: serde_json::Value) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    pub fn outcome(mut self, outcome: AuditOutcome) -> Self {
+        self.outcome = outcome;
+        self
+    }
+
+    pub fn counts(mut self, checked: i64, affected: i64, failed: i64) -> Self {
+        self.checked = checked;
+        self.affected = affected;
+        self.failed = failed;
+        self
+    }
+}
+
+/// Where a recorded action can be looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recorded {
+    pub audit_id: i64,
+    /// Set when the action opened a moderation case.
+    pub case_number: Option<i64>,
+}
+
+/// Writes `record` to the audit log, opens a case for it when it is one moderators act on,
+/// and mirrors it to the guild's mod-log channel.
+///
+/// The action has already happened by the time it is recorded, so a failed write or post is
+/// logged rather than surfaced to the moderator.
+#[tracing::instrument(skip(http, database))]
+pub async fn record(http: &Http, database: &SqlitePool, record: AuditRecord) -> Option<Recorded> {
+    let guild_id = record.guild_id.to_string();
+    let actor_id = record.actor_id.to_string();
+    let parameters = record.parameters.to_string();
+
+    let entry = NewAuditEntry {
+        guild_id: &guild_id,
+        actor_id: &actor_id,
+        actor_name: &record.actor_name,
+        action: record.action.as_str(),
+        target: record.target.as_deref(),
+        parameters: &parameters,
+        outcome: record.outcome.as_str(),
+        checked_count: record.checked,
+        affected_count: record.affected,
+        failed_count: record.failed,
+    };
+
+    let recorded = match database::save_audit_entry(database, &entry).await {
+        Ok(audit_id) => Some(Recorded {
+            audit_id,
+            case_number: open_case(database, &record, audit_id).await,
+        }),
+        Err(e) => {
+            tracing::error!(
+                guild_id = %record.guild_id,
+                action = %record.action,
+                error = %e,
+                "Failed to record moderation action"
+            );
+            None
+        }
+    };
+
+    post_to_mod_log(http, database, record.guild_id, |locale| {
+        mod_log_embed(locale, &record, recorded)
+    })
+    .await;
+
+    recorded
+}
+
+/// Failed actions and routine housekeeping are only kept in the audit log.
+async fn open_case(database: &SqlitePool, record: &AuditRecord, audit_id: i64) -> Option<i64> {
+    if record.outcome == AuditOutcome::Failure || !record.action.opens_case() {
+        return None;
+    }
+
+    let reason = record
+        .parameters
+        .get("reason")
+        .and_then(serde_json::Value::as_str);
+
+    match database::open_case(database, &record.guild_id.to_string(), audit_id, reason).await {
+        Ok(case_number) => Some(case_number),
+        Err(e) => {
+            tracing::error!(guild_id = %record.guild_id, audit_id = %audit_id, error = %e, "Failed to open moderation case");
+            None
+        }
+    }
+}
+
+/// Posts the embed built by `embed` to the guild's mod-log channel, if one is set, in the
+/// guild's language.
+pub async fn post_to_mod_log(
+    http: &Http,
+    database: &SqlitePool,
+    guild_id: GuildId,
+    embed: impl FnOnce(Locale) -> CreateEmbed,
+) {
+    let settings = match database::get_guild_settings(database, &guild_id.to_string()).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load mod-log channel");
+            None
+        }
+    };
+    let Some(settings) = settings else {
+        return;
+    };
+    let Some(channel_id) = settings
+        .mod_log_channel_id
+        .as_deref()
+        .and_then(parse_id)
+        .map(ChannelId::new)
+    else {
+        return;
+    };
+    let locale = settings
+        .locale
+        .as_deref()
+        .and_then(Locale::from_code)
+        .unwrap_or_default();
+
+    let message = CreateMessage::new().embed(embed(locale));
+    if let Err(e) = channel_id.send_message(http, message).await {
+        tracing::warn!(
+            guild_id = %guild_id,
+            channel_id = %channel_id,
+            error = %e,
+            "Failed to post to mod-log channel"
+        );
+    }
+}
+
+/// Builds the structured summary posted to the mod-log channel.
+pub fn mod_log_embed(
+    locale: Locale,
+    record: &AuditRecord,
+    recorded: Option<Recorded>,
+) -> CreateEmbed {
+    let label = record.action.label(locale);
+    let title = match recorded.and_then(|r| r.case_number) {
+        Some(case_number) => tr!(locale, "case-title", number = case_number, label = label),
+        None => label,
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .colour(record.outcome.colour())
+        .field(
+            tr!(locale, "audit-field-moderator"),
+            format!("<@{}>", record.actor_id),
+            true,
+        )
+        .field(
+            tr!(locale, "audit-field-outcome"),
+            record.outcome.label(locale),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(match recorded {
+            Some(recorded) => tr!(locale, "audit-footer", id = recorded.audit_id),
+            None => tr!(locale, "audit-footer-unsaved"),
+        }))
+        .timestamp(Timestamp::now());
+
+    if let Some(target) = &record.target {
+        embed = embed.field(
+            tr!(locale, "audit-field-target"),
+            truncate_field(target),
+            true,
+        );
+    }
+    if record.checked > 0 || record.affected > 0 || record.failed > 0 {
+        embed = embed.field(
+            tr!(locale, "audit-field-counts"),
+            tr!(
+                locale,
+                "audit-counts",
+                checked = record.checked,
+                affected = record.affected,
+                failed = record.failed
+            ),
+            false,
+        );
+    }
+
+    for (name, value) in parameter_fields(locale, &record.parameters) {
+        embed = embed.field(name, value, false);
+    }
+
+    embed
+}
+
+/// Flattens the top level of the audit parameters into embed fields, skipping empty values.
+/// Parameters without an `audit-parameter-` message are named after their key.
+pub(crate) fn parameter_fields(
+    locale: Locale,
+    parameters: &serde_json::Value,
+) -> Vec<(String, String)> {
+    let Some(object) = parameters.as_object() else {
+        return Vec::new();
+    };
+
+    object
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                serde_json::Value::Null => return None,
+                serde_json::Value::String(s) if s.is_empty() => return None,
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+
+            let id = format!("audit-parameter-{}", key.replace('_', "-"));
+            let name = i18n::try_translate(locale, &id).unwrap_or_else(|| {
+                let mut name = key.replace('_', " ");
+                if let Some(first) = name.get_mut(0..1) {
+                    first.make_ascii_uppercase();
+                }
+                name
+            });
+
+            Some((name, truncate_field(&value)))
+        })
+        .take(MAX_PARAMETER_FIELDS)
+        .collect()
+}
+
+/// Shortens `value` to fit in an embed field.
+pub(crate) fn truncate_field(value: &str) -> String {
+    if value.chars().count() <= MAX_FIELD_LENGTH {
+        return value.to_string();
+    }
+
+    let mut truncated: String = value.chars().take(MAX_FIELD_LENGTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parameter_fields() {
+        let fields = parameter_fields(
+            Locale::default(),
+            &json!({
+            "reason": "spam",
+            "duration_minutes": 10,
+            "expires_at": null,
+                "note": "",
+                "some_flag": true,
+            }),
+        );
+
+        assert_eq!(
+            fields,
+            vec![
+                ("Duration (minutes)".to_string(), "10".to_string()),
+                ("Reason".to_string(), "spam".to_string()),
+                ("Some flag".to_string(), "true".to_string()),
+            ]
+        );
+        assert!(parameter_fields(Locale::default(), &json!("not an object")).is_empty());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate_field("short"), "short");
+
+        let long = "x".repeat(MAX_FIELD_LENGTH + 10);
+        assert_eq!(truncate_field(&long).chars().count(), MAX_FIELD_LENGTH);
+    }
+}
@@ -0,0 +1,486 @@
+use crate::{
+    audit::{self, AuditAction, AuditOutcome, AuditRecord},
+    bot::Data,
+    database::{self, AutomodRule},
+    moderation::{self, Moderator},
+    pattern::{self, PatternOptions},
+    settings::Locale,
+};
+use anyhow::Result;
+use chrono::Duration;
+use poise::serenity_prelude::{self as serenity, GuildId, Message, Permissions};
+use regex::Regex;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Timeout applied by rules that do not set their own length.
+pub const DEFAULT_TIMEOUT_MINUTES: i64 = 10;
+
+/// Host part of every http(s) link in a message.
+static LINK_HOST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)https?://(?:[^\s/@]+@)?([^\s/:?#<>]+)").unwrap());
+
+/// What a rule looks for in a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum AutomodKind {
+    #[name = "regex"]
+    Regex,
+    #[name = "words"]
+    Words,
+    #[name = "links"]
+    Links,
+    #[name = "mentions"]
+    Mentions,
+}
+
+impl AutomodKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Regex => "regex",
+            Self::Words => "words",
+            Self::Links => "links",
+            Self::Mentions => "mentions",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "regex" => Some(Self::Regex),
+            "words" => Some(Self::Words),
+            "links" => Some(Self::Links),
+            "mentions" => Some(Self::Mentions),
+            _ => None,
+        }
+    }
+}
+
+/// What happens to a message matching a rule. Stored as a comma separated list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AutomodActions {
+    pub delete: bool,
+    pub warn: bool,
+    pub timeout: bool,
+    pub log: bool,
+}
+
+impl AutomodActions {
+    pub fn parse(value: &str) -> Self {
+        let mut actions = Self::default();
+        for action in value.split(',').map(str::trim) {
+            match action {
+                "delete" => actions.delete = true,
+                "warn" => actions.warn = true,
+                "timeout" => actions.timeout = true,
+                "log" => actions.log = true,
+                _ => {}
+            }
+        }
+        actions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            delete: self.delete || other.delete,
+            warn: self.warn || other.warn,
+            timeout: self.timeout || other.timeout,
+            log: self.log || other.log,
+        }
+    }
+}
+
+impl fmt::Display for AutomodActions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actions: Vec<&str> = [
+            (self.delete, "delete"),
+            (self.warn, "warn"),
+            (self.timeout, "timeout"),
+            (self.log, "log"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect();
+
+        f.write_str(&actions.join(","))
+    }
+}
+
+/// Splits a comma or newline separated list, dropping empty entries.
+pub fn parse_list(input: &str) -> Vec<String> {
+    input
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Reduces a domain or URL to its lowercase host, without a leading `www.`.
+pub fn normalize_domain(input: &str) -> Option<String> {
+    let input = input.trim().to_lowercase();
+    let host = input
+        .split_once("://")
+        .map_or(input.as_str(), |(_, rest)| rest)
+        .split(['/', ':', '?', '#'])
+        .next()?
+        .trim_start_matches("www.")
+        .trim_end_matches('.');
+
+    (!host.is_empty() && host.contains('.') && !host.contains(char::is_whitespace))
+        .then(|| host.to_string())
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Pattern(Regex),
+    Domains(Vec<String>),
+    Mentions(usize),
+}
+
+/// A rule ready to be checked against messages.
+#[derive(Debug)]
+pub struct CompiledRule {
+    pub id: i64,
+    pub name: String,
+    pub actions: AutomodActions,
+    pub timeout: Duration,
+    matcher: Matcher,
+}
+
+impl CompiledRule {
+    pub fn compile(rule: &AutomodRule) -> crate::error::Result<Self> {
+        let kind = AutomodKind::parse(&rule.kind)
+            .ok_or_else(|| crate::error::bot_error(format!("Unknown rule kind `{}`", rule.kind)))?;
+        let source = rule.pattern.as_deref().unwrap_or_default();
+
+        let matcher = match kind {
+            AutomodKind::Regex => Matcher::Pattern(pattern::compile(
+                Locale::default(),
+                source,
+                PatternOptions {
+                    case_insensitive: rule.case_insensitive,
+                    ..Default::default()
+                },
+            )?),
+            AutomodKind::Words => Matcher::Pattern(pattern::compile_words(
+                Locale::default(),
+                &parse_list(source),
+            )?),
+            AutomodKind::Links => Matcher::Domains(
+                parse_list(source)
+                    .iter()
+                    .filter_map(|domain| normalize_domain(domain))
+                    .collect(),
+            ),
+            AutomodKind::Mentions => Matcher::Mentions(rule.threshold.unwrap_or(0).max(1) as usize),
+        };
+
+        Ok(Self {
+            id: rule.id,
+            name: rule.name.clone(),
+            actions: AutomodActions::parse(&rule.actions),
+            timeout: Duration::minutes(rule.timeout_minutes.unwrap_or(DEFAULT_TIMEOUT_MINUTES)),
+            matcher,
+        })
+    }
+
+    /// Returns a description of what matched, or `None` if the message passes this rule.
+    pub fn check(&self, content: &str, mentions: usize) -> Option<String> {
+        match &self.matcher {
+            Matcher::Pattern(regex) => regex
+                .find(content)
+                .map(|found| found.as_str().trim().chars().take(100).collect()),
+            Matcher::Domains(domains) => LINK_HOST
+                .captures_iter(content)
+                .filter_map(|captures| normalize_domain(&captures[1]))
+                .find(|host| {
+                    domains
+                        .iter()
+                        .any(|domain| host == domain || host.ends_with(&format!(".{}", domain)))
+                }),
+            Matcher::Mentions(threshold) => {
+                (mentions >= *threshold).then(|| format!("{} mentions", mentions))
+            }
+        }
+    }
+}
+
+/// Compiled automod rules per guild, loaded on first use and dropped when rules change.
+#[derive(Debug, Clone, Default)]
+pub struct AutomodCache {
+    rules: Arc<RwLock<HashMap<GuildId, Arc<Vec<CompiledRule>>>>>,
+}
+
+impl AutomodCache {
+    pub async fn rules(
+        &self,
+        database: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Arc<Vec<CompiledRule>>> {
+        if let Some(rules) = self.rules.read().unwrap().get(&guild_id) {
+            return Ok(rules.clone());
+        }
+
+        let rules = database::get_automod_rules(database, &guild_id.to_string()).await?;
+        let compiled: Vec<CompiledRule> = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| match CompiledRule::compile(rule) {
+                Ok(compiled) => Some(compiled),
+                Err(e) => {
+                    tracing::warn!(rule_id = %rule.id, error = %e, "Skipping invalid automod rule");
+                    None
+                }
+            })
+            .collect();
+
+        tracing::debug!(guild_id = %guild_id, count = %compiled.len(), "Loaded automod rules");
+
+        let compiled = Arc::new(compiled);
+        self.rules
+            .write()
+            .unwrap()
+            .insert(guild_id, compiled.clone());
+
+        Ok(compiled)
+    }
+
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.rules.write().unwrap().remove(&guild_id);
+    }
+}
+
+/// Members who can manage messages in the channel are not moderated.
+pub fn is_exempt(ctx: &serenity::Context, guild_id: GuildId, message: &Message) -> bool {
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        return false;
+    };
+    if guild.owner_id == message.author.id {
+        return true;
+    }
+
+    let (Some(member), Some(channel)) = (&message.member, guild.channels.get(&message.channel_id))
+    else {
+        return false;
+    };
+
+    let permissions = guild.partial_member_permissions_in(channel, message.author.id, member);
+    permissions.intersects(Permissions::ADMINISTRATOR | Permissions::MANAGE_MESSAGES)
+}
+
+/// Runs the guild's automod rules against a new message and applies the matching actions.
+///
+/// When several rules match, their actions are combined so a message is only deleted, warned
+/// or timed out once.
+#[tracing::instrument(skip(ctx, data, message), fields(message_id = %message.id))]
+pub async fn check_message(ctx: &serenity::Context, data: &Data, message: &Message) -> Result<()> {
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+    if message.author.bot {
+        return Ok(());
+    }
+
+    let rules = data.automod.rules(&data.database, guild_id).await?;
+    if rules.is_empty() || is_exempt(ctx, guild_id, message) {
+        return Ok(());
+    }
+
+    let mentions = message.mentions.len()
+        + message.mention_roles.len()
+        + usize::from(message.mention_everyone);
+
+    let hits: Vec<(&CompiledRule, String)> = rules
+        .iter()
+        .filter_map(|rule| {
+            rule.check(&message.content, mentions)
+                .map(|found| (rule, found))
+        })
+        .collect();
+
+    if hits.is_empty() {
+        return Ok(());
+    }
+
+    let actions = hits
+        .iter()
+        .fold(AutomodActions::default(), |actions, (rule, _)| {
+            actions.merge(rule.actions)
+        });
+    let timeout = hits
+        .iter()
+        .filter(|(rule, _)| rule.actions.timeout)
+        .map(|(rule, _)| rule.timeout)
+        .max();
+    let names: Vec<&str> = hits.iter().map(|(rule, _)| rule.name.as_str()).collect();
+    let reason = format!("Automod: {}", names.join(", "));
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %message.author.id,
+        rules = ?names,
+        actions = %actions,
+        "Automod rules matched"
+    );
+
+    let bot = {
+        let user = ctx.cache.current_user();
+        Moderator::new(user.id, format!("{} (automod)", user.name))
+    };
+
+    let mut errors = Vec::new();
+
+    if actions.delete
+        && let Err(e) = message.delete(ctx).await
+    {
+        errors.push(format!("delete: {}", e));
+    }
+
+    if actions.warn
+        && let Err(e) = moderation::issue_warning(
+            &ctx.http,
+            &data.database,
+            guild_id,
+            &message.author,
+            &bot,
+            &bot,
+            &reason,
+        )
+        .await
+    {
+        errors.push(format!("warn: {}", e));
+    }
+
+    if let Some(timeout) = timeout
+        && let Some(error) = moderation::timeout_member(
+            &ctx.http,
+            &data.database,
+            guild_id,
+            message.author.id,
+            timeout,
+            &bot,
+            &reason,
+        )
+        .await
+    {
+        errors.push(format!("timeout: {}", error));
+    }
+
+    if !errors.is_empty() {
+        tracing::warn!(guild_id = %guild_id, errors = ?errors, "Automod actions failed");
+    }
+
+    if actions.log {
+        audit::record(
+            &ctx.http,
+            &data.database,
+            AuditRecord::new(guild_id, bot.id, &bot.name, AuditAction::Automod)
+                .target(format!("<@{}>", message.author.id))
+                .parameters(serde_json::json!({
+                    "channel_id": message.channel_id.to_string(),
+                    "message_id": message.id.to_string(),
+                    "content": message.content.chars().take(500).collect::<String>(),
+                    "rules": hits
+                        .iter()
+                        .map(|(rule, found)| serde_json::json!({
+                            "id": rule.id,
+                            "name": rule.name,
+                            "matched": found,
+                        }))
+                        .collect::<Vec<_>>(),
+                    "actions": actions.to_string(),
+                    "errors": errors,
+                }))
+                .outcome(if errors.is_empty() {
+                    AuditOutcome::Success
+                } else {
+                    AuditOutcome::Partial
+                })
+                .counts(1, 1, 0),
+        )
+        .await;
+    }
+
+    for (rule, _) in &hits {
+        if let Err(e) = database::record_automod_hit(&data.database, rule.id).await {
+            tracing::error!(rule_id = %rule.id, error = %e, "Failed to record automod hit");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(kind: AutomodKind, pattern: Option<&str>, threshold: Option<i64>) -> CompiledRule {
+        CompiledRule::compile(&AutomodRule {
+            id: 1,
+            guild_id: "42".to_string(),
+            name: "test".to_string(),
+            kind: kind.as_str().to_string(),
+            pattern: pattern.map(str::to_string),
+            threshold,
+            case_insensitive: true,
+            actions: "delete,log".to_string(),
+            timeout_minutes: None,
+            enabled: true,
+            created_by_id: "1".to_string(),
+            created_by_name: "Mod".to_string(),
+            created_at: chrono::Utc::now(),
+            hit_count: 0,
+            last_hit_at: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_actions_round_trip() {
+        let actions = AutomodActions::parse("delete, log,unknown");
+        assert!(actions.delete && actions.log && !actions.warn && !actions.timeout);
+        assert_eq!(actions.to_string(), "delete,log");
+        assert!(AutomodActions::parse("").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(
+            normalize_domain("https://WWW.Example.com/path?q=1").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            normalize_domain("discord.gg").as_deref(),
+            Some("discord.gg")
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let regex = rule(AutomodKind::Regex, Some(r"free\s+nitro"), None);
+        assert!(regex.check("Get FREE  Nitro here", 0).is_some());
+        assert!(regex.check("nitro is not free", 0).is_none());
+
+        let words = rule(AutomodKind::Words, Some("scam, phishing"), None);
+        assert!(words.check("this is a SCAM", 0).is_some());
+        assert!(words.check("scampi", 0).is_none());
+
+        let links = rule(AutomodKind::Links, Some("discord.gg, bad.example"), None);
+        assert_eq!(
+            links.check("join https://discord.gg/abc", 0).as_deref(),
+            Some("discord.gg")
+        );
+        assert!(links.check("see http://cdn.bad.example/x", 0).is_some());
+        assert!(links.check("see https://notbad.example", 0).is_none());
+
+        let mentions = rule(AutomodKind::Mentions, None, Some(5));
+        assert!(mentions.check("hi", 5).is_some());
+        assert!(mentions.check("hi", 4).is_none());
+    }
+}
@@ -1,28 +1,84 @@
-use crate::{commands, config::Config, database, error::handle_error};
+use crate::{
+    antispam::AntispamTracker,
+    approval::{self, ApprovalCache},
+    automod::AutomodCache,
+    commands, database,
+    error::{self, handle_error},
+    events,
+    i18n::{self, LocaleCache},
+    message_log::MessageLog,
+    permissions::{self, PermissionCache},
+    registration,
+    reload::{self, ConfigHandle},
+    scheduler,
+    settings::{self, SettingsCache},
+};
 use anyhow::Result;
-use poise::serenity_prelude::{Client, ClientBuilder};
+use poise::serenity_prelude::{Client, ClientBuilder, GatewayIntents};
 use sqlx::SqlitePool;
 
 #[derive(Debug, Clone)]
 pub struct Data {
     pub database: SqlitePool,
+    pub config: ConfigHandle,
+    pub settings: SettingsCache,
+    pub permissions: PermissionCache,
+    pub approvals: ApprovalCache,
+    pub locales: LocaleCache,
+    pub automod: AutomodCache,
+    pub antispam: AntispamTracker,
+    pub message_log: MessageLog,
 }
 
 impl Data {
-    pub fn new(database: SqlitePool) -> Self {
+    pub fn new(database: SqlitePool, config: ConfigHandle) -> Self {
         tracing::debug!("Creating new bot data instance");
-        Self { database }
+        Self {
+            database,
+            settings: SettingsCache::default(),
+            permissions: PermissionCache::default(),
+            approvals: ApprovalCache::default(),
+            locales: LocaleCache::default(),
+            automod: AutomodCache::default(),
+            antispam: AntispamTracker::default(),
+            message_log: MessageLog::new(config.get().message_log.cache_capacity),
+            config,
+        }
     }
+
+    /// Pushes reloaded config values into the parts that copied them at startup.
+    pub fn apply_config(&self) {
+        let config = self.config.get();
+        self.message_log
+            .set_capacity(config.message_log.cache_capacity);
+    }
+}
+
+/// Checks run before every command: guild approval first, then module toggles, then permission
+/// rules.
+async fn command_check(ctx: error::Context<'_>) -> std::result::Result<bool, error::Error> {
+    Ok(approval::approval_check(ctx).await?
+        && settings::module_check(ctx).await?
+        && permissions::permission_check(ctx).await?)
 }
 
 #[tracing::instrument]
-pub async fn create_bot(config: Config) -> Result<Client> {
+pub async fn create_bot(handle: ConfigHandle) -> Result<Client> {
     tracing::info!("Creating bot with configuration");
-    tracing::debug!(prefix = %config.command_prefix, "Bot command prefix configured");
+    let config = handle.get();
+    tracing::debug!(prefix = %config.discord.prefix, "Bot command prefix configured");
+    if !config
+        .discord
+        .intents
+        .contains(GatewayIntents::GUILD_MEMBERS)
+    {
+        tracing::warn!("The guild_members intent is not enabled, raid detection is off");
+    }
 
     let database = database::init_database(
         config
-            .database_path
+            .database
+            .path
             .to_str()
             .ok_or_else(|| anyhow::anyhow!("Invalid database path"))?,
     )
@@ -30,12 +86,18 @@ pub async fn create_bot(config: Config) -> Result<Client> {
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: commands::get_commands(),
+            commands: i18n::localize_commands(permissions::take_over(commands::get_commands())),
+            owners: config.discord.owner_ids(),
+            // The configured prefix is the fallback of the per-guild one.
             prefix_options: poise::PrefixFrameworkOptions {
-                prefix: Some(config.command_prefix.clone()),
+                dynamic_prefix: Some(|ctx| Box::pin(settings::dynamic_prefix(ctx))),
                 ..Default::default()
             },
+            command_check: Some(|ctx| Box::pin(command_check(ctx))),
             on_error: |error| Box::pin(handle_error(error)),
+            event_handler: |ctx, event, framework, data| {
+                Box::pin(events::handle_event(ctx, event, framework, data))
+            },
             ..Default::default()
         })
         .setup(|ctx, ready, framework| {
@@ -47,15 +109,19 @@ pub async fn create_bot(config: Config) -> Result<Client> {
                     "Bot successfully logged in"
                 );
 
-                poise::builtins::register_globally(ctx, &framework.options().commands).await?;
-                tracing::info!("Global commands registered successfully");
+                let scopes = registration::scopes(&handle.get().commands);
+                registration::register(&ctx.http, &framework.options().commands, &scopes).await?;
+
+                let data = Data::new(database, handle);
+                scheduler::spawn(ctx.http.clone(), data.clone());
+                reload::spawn_sighup_handler(data.clone());
 
-                Ok(Data::new(database))
+                Ok(data)
             })
         })
         .build();
 
-    let client = ClientBuilder::new(config.discord_token, config.intents)
+    let client = ClientBuilder::new(config.token()?, config.discord.intents)
         .framework(framework)
         .await?;
 
@@ -0,0 +1,241 @@
+use crate::{
+    bot::create_bot,
+    commands,
+    config::Config,
+    database, i18n, permissions,
+    registration::{self, Scope},
+    reload::ConfigHandle,
+};
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use poise::serenity_prelude::{GuildId, Http};
+use std::path::PathBuf;
+
+/// Discord bot for song and game suggestions and server moderation.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// TOML config file. Defaults to ./bearobot.toml when it exists.
+    #[arg(short, long, env = "BEAROBOT_CONFIG", global = true)]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Start the bot (the default)
+    Run,
+    /// Check the configuration and exit
+    CheckConfig,
+    /// Inspect or apply database migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Write every song and game suggestion as JSON
+    Export {
+        /// File to write, stdout when left out
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Add the suggestions of an export to the database
+    Import {
+        /// File written by `export`
+        input: PathBuf,
+    },
+    /// Register the slash commands as configured in `commands.registration`
+    RegisterCommands {
+        /// Register in this guild only, which takes effect immediately
+        #[arg(long)]
+        guild: Option<u64>,
+    },
+    /// Remove the registered slash commands
+    UnregisterCommands {
+        /// Remove the commands of this guild only, instead of the global ones and those of
+        /// every configured guild
+        #[arg(long)]
+        guild: Option<u64>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateAction {
+    /// List migrations and whether they have been applied
+    Status,
+    /// Apply pending migrations
+    Up,
+}
+
+impl Cli {
+    pub async fn run(self) -> Result<()> {
+        // Logging is configured by the file, so errors before this point go to stderr via the
+        // returned error.
+        let config = Config::load(self.config.as_deref())?;
+        let log_filter = config.init_tracing();
+        tracing::info!(config = ?self.config, "Configuration loaded");
+
+        match self.command.unwrap_or(Command::Run) {
+            Command::Run => {
+                let handle = ConfigHandle::new(config, self.config, Some(log_filter));
+                let mut client = create_bot(handle).await?;
+                client.start().await?;
+            }
+            Command::CheckConfig => check_config(&config)?,
+            Command::Migrate { action } => migrate(&config, action).await?,
+            Command::Export { output } => export(&config, output).await?,
+            Command::Import { input } => import(&config, input).await?,
+            Command::RegisterCommands { guild } => {
+                let scopes = match guild {
+                    Some(guild) => vec![Scope::Guild(GuildId::new(guild))],
+                    None => registration::scopes(&config.commands),
+                };
+                set_commands(&config, &scopes, true).await?
+            }
+            Command::UnregisterCommands { guild } => {
+                let scopes = match guild {
+                    Some(guild) => vec![Scope::Guild(GuildId::new(guild))],
+                    None => registration::all_scopes(&config.commands),
+                };
+                set_commands(&config, &scopes, false).await?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_config(config: &Config) -> Result<()> {
+    config.token()?;
+
+    println!("Configuration is valid");
+    println!("  prefix:   {}", config.discord.prefix);
+    println!("  intents:  {:?}", config.discord.intents);
+    println!("  owners:   {:?}", config.discord.owners);
+    println!("  database: {}", config.database.path.display());
+    println!(
+        "  logging:  {} ({:?})",
+        config.logging.level, config.logging.format
+    );
+    println!(
+        "  commands: {:?} {:?}",
+        config.commands.registration, config.commands.guilds
+    );
+    println!("  guilds:   {:?} approval", config.guilds.approval);
+
+    Ok(())
+}
+
+async fn open_database(config: &Config) -> Result<sqlx::SqlitePool> {
+    let path = config
+        .database
+        .path
+        .to_str()
+        .context("Invalid database path")?;
+
+    database::connect_database(path).await
+}
+
+async fn migrate(config: &Config, action: MigrateAction) -> Result<()> {
+    let pool = open_database(config).await?;
+
+    match action {
+        MigrateAction::Status => {
+            for migration in database::migration_status(&pool).await? {
+                println!(
+                    "{} {} {}",
+                    if migration.applied {
+                        "applied"
+                    } else {
+                        "pending"
+                    },
+                    migration.version,
+                    migration.description
+                );
+            }
+        }
+        MigrateAction::Up => {
+            let pending = database::migration_status(&pool)
+                .await?
+                .iter()
+                .filter(|m| !m.applied)
+                .count();
+            database::run_migrations(&pool).await?;
+            println!("Applied {} migration(s)", pending);
+        }
+    }
+
+    Ok(())
+}
+
+async fn export(config: &Config, output: Option<PathBuf>) -> Result<()> {
+    let pool = open_database(config).await?;
+    database::run_migrations(&pool).await?;
+    let export = database::export_suggestions(&pool).await?;
+    let json = serde_json::to_string_pretty(&export)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, json)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!(
+                "Exported {} songs and {} games to {}",
+                export.songs.len(),
+                export.games.len(),
+                path.display()
+            );
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
+
+async fn import(config: &Config, input: PathBuf) -> Result<()> {
+    let contents = std::fs::read_to_string(&input)
+        .with_context(|| format!("Failed to read {}", input.display()))?;
+    let export: database::SuggestionExport = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not a suggestion export", input.display()))?;
+
+    let pool = open_database(config).await?;
+    database::run_migrations(&pool).await?;
+    let counts = database::import_suggestions(&pool, &export).await?;
+
+    println!(
+        "Imported {} songs and {} games, skipped {} already present",
+        counts.songs, counts.games, counts.skipped
+    );
+
+    Ok(())
+}
+
+/// Replaces the slash commands registered in `scopes` with ours, or with none when `register`
+/// is false.
+async fn set_commands(config: &Config, scopes: &[Scope], register: bool) -> Result<()> {
+    let http = Http::new(config.token()?);
+    let application = http
+        .get_current_application_info()
+        .await
+        .context("Failed to reach Discord, check the token")?;
+    http.set_application_id(application.id);
+
+    match register {
+        true => {
+            let commands =
+                i18n::localize_commands(permissions::take_over(commands::get_commands()));
+            registration::register(&http, &commands, scopes).await?;
+            println!(
+                "Registered {} command(s) in {}",
+                commands.len(),
+                registration::describe(scopes)
+            );
+        }
+        false => {
+            registration::clear(&http, scopes).await?;
+            println!("Removed the commands in {}", registration::describe(scopes));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use crate::antispam::{self, AntispamAction, RaidAction};
+use crate::database::{self, AntispamSettings};
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::moderation::MAX_TIMEOUT_DAYS;
+use crate::settings::Locale;
+use chrono::Duration;
+use poise::serenity_prelude::GuildId;
+
+/// Configure spam and raid detection
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "antispam_show",
+        "antispam_toggle",
+        "antispam_messages",
+        "antispam_raid"
+    ),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn antispam(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Show the anti-spam settings of this server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "show",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn antispam_show(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let settings = load_settings(ctx, guild_id).await?;
+    ctx.say(describe_settings(locale, &settings)).await?;
+
+    Ok(())
+}
+
+/// Turn spam and raid detection on or off
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "toggle",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn antispam_toggle(
+    ctx: Context<'_>,
+    #[description = "Whether detection should run"] enabled: bool,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let settings = AntispamSettings {
+        enabled,
+        ..load_settings(ctx, guild_id).await?
+    };
+    save_settings(ctx, guild_id, &settings).await?;
+
+    let response = match enabled {
+        true => tr!(locale, "antispam-enabled"),
+        false => tr!(locale, "antispam-disabled"),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Set the message limits and what happens to spammers
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "messages",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn antispam_messages(
+    ctx: Context<'_>,
+    #[description = "Messages per window that count as a flood (0 to disable)"]
+    flood_messages: Option<i64>,
+    #[description = "Flood window in seconds"] flood_seconds: Option<i64>,
+    #[description = "Identical messages that count as spam (0 to disable)"]
+    duplicate_messages: Option<i64>,
+    #[description = "Duplicate window in seconds"] duplicate_seconds: Option<i64>,
+    #[description = "Mentions in one message that count as spam (0 to disable)"]
+    mention_limit: Option<i64>,
+    #[description = "What to do with spammers"] action: Option<AntispamAction>,
+    #[description = "Timeout length (e.g. 10m)"] timeout: Option<String>,
+    #[description = "How long a lockdown lasts (e.g. 15m)"] lockdown: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let current = load_settings(ctx, guild_id).await?;
+    let settings = AntispamSettings {
+        flood_messages: check_range(locale, flood_messages, 0..=50, "flood_messages")?
+            .unwrap_or(current.flood_messages),
+        flood_seconds: check_range(locale, flood_seconds, 1..=60, "flood_seconds")?
+            .unwrap_or(current.flood_seconds),
+        duplicate_messages: check_range(locale, duplicate_messages, 0..=50, "duplicate_messages")?
+            .unwrap_or(current.duplicate_messages),
+        duplicate_seconds: check_range(locale, duplicate_seconds, 1..=600, "duplicate_seconds")?
+            .unwrap_or(current.duplicate_seconds),
+        mention_limit: check_range(locale, mention_limit, 0..=100, "mention_limit")?
+            .unwrap_or(current.mention_limit),
+        action: action.map_or(current.action.clone(), |a| a.as_str().to_string()),
+        timeout_minutes: parse_minutes(
+            locale,
+            timeout.as_deref(),
+            Duration::days(MAX_TIMEOUT_DAYS),
+        )?
+        .unwrap_or(current.timeout_minutes),
+        lockdown_minutes: parse_minutes(locale, lockdown.as_deref(), Duration::days(1))?
+            .unwrap_or(current.lockdown_minutes),
+        ..current
+    };
+
+    save_settings(ctx, guild_id, &settings).await?;
+    ctx.say(describe_settings(locale, &settings)).await?;
+
+    Ok(())
+}
+
+/// Set the join burst that counts as a raid and the response
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "raid",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn antispam_raid(
+    ctx: Context<'_>,
+    #[description = "Joins per window that count as a raid (0 to disable)"] joins: Option<i64>,
+    #[description = "Join window in seconds"] seconds: Option<i64>,
+    #[description = "Only count accounts younger than this many days (0 counts all)"]
+    account_age_days: Option<i64>,
+    #[description = "What to do during a raid"] action: Option<RaidAction>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let current = load_settings(ctx, guild_id).await?;
+    let settings = AntispamSettings {
+        raid_joins: check_range(locale, joins, 0..=100, "joins")?.unwrap_or(current.raid_joins),
+        raid_seconds: check_range(locale, seconds, 1..=600, "seconds")?
+            .unwrap_or(current.raid_seconds),
+        raid_account_age_days: check_range(locale, account_age_days, 0..=365, "account_age_days")?
+            .unwrap_or(current.raid_account_age_days),
+        raid_action: action.map_or(current.raid_action.clone(), |a| a.as_str().to_string()),
+        ..current
+    };
+
+    save_settings(ctx, guild_id, &settings).await?;
+    ctx.say(describe_settings(locale, &settings)).await?;
+
+    Ok(())
+}
+
+async fn load_settings(ctx: Context<'_>, guild_id: GuildId) -> Result<AntispamSettings> {
+    Ok(
+        database::get_antispam_settings(&ctx.data().database, &guild_id.to_string())
+            .await?
+            .unwrap_or_else(|| antispam::default_settings(guild_id)),
+    )
+}
+
+async fn save_settings(
+    ctx: Context<'_>,
+    guild_id: GuildId,
+    settings: &AntispamSettings,
+) -> Result<()> {
+    database::save_antispam_settings(&ctx.data().database, settings, &ctx.author().id.to_string())
+        .await?;
+    ctx.data().antispam.invalidate(guild_id);
+
+    Ok(())
+}
+
+/// Checks an option against `range`, naming the option as it is typed.
+fn check_range(
+    locale: Locale,
+    value: Option<i64>,
+    range: std::ops::RangeInclusive<i64>,
+    option: &str,
+) -> Result<Option<i64>> {
+    match value {
+        Some(value) if !range.contains(&value) => Err(bot_error(tr!(
+            locale,
+            "error-option-range",
+            option = option,
+            min = range.start(),
+            max = range.end()
+        ))),
+        other => Ok(other),
+    }
+}
+
+fn parse_minutes(locale: Locale, input: Option<&str>, max: Duration) -> Result<Option<i64>> {
+    let Some(input) = input else {
+        return Ok(None);
+    };
+
+    let parsed = duration::parse(input)
+        .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = input)))?;
+    if parsed < Duration::minutes(1) || parsed > max {
+        return Err(bot_error(tr!(
+            locale,
+            "antispam-duration-range",
+            max = duration::format(max)
+        )));
+    }
+
+    Ok(Some(parsed.num_minutes()))
+}
+
+fn describe_limit(locale: Locale, count: i64, seconds: i64, unit: &str) -> String {
+    match count {
+        0 => tr!(locale, "antispam-off"),
+        _ => tr!(locale, unit, count = count, seconds = seconds),
+    }
+}
+
+fn describe_settings(locale: Locale, settings: &AntispamSettings) -> String {
+    tr!(
+        locale,
+        "antispam-show",
+        state = match settings.enabled {
+            true => tr!(locale, "antispam-state-enabled"),
+            false => tr!(locale, "antispam-state-disabled"),
+        },
+        flood = describe_limit(
+            locale,
+            settings.flood_messages,
+            settings.flood_seconds,
+            "antispam-limit-messages"
+        ),
+        duplicates = describe_limit(
+            locale,
+            settings.duplicate_messages,
+            settings.duplicate_seconds,
+            "antispam-limit-duplicates"
+        ),
+        mentions = match settings.mention_limit {
+            0 => tr!(locale, "antispam-off"),
+            limit => tr!(locale, "antispam-limit-mentions", count = limit),
+        },
+        action = settings.action,
+        timeout = duration::format(Duration::minutes(settings.timeout_minutes)),
+        lockdown = duration::format(Duration::minutes(settings.lockdown_minutes)),
+        raid = match settings.raid_account_age_days {
+            0 => describe_limit(
+                locale,
+                settings.raid_joins,
+                settings.raid_seconds,
+                "antispam-limit-joins"
+            ),
+            days => tr!(
+                locale,
+                "antispam-raid-new-accounts",
+                limit = describe_limit(
+                    locale,
+                    settings.raid_joins,
+                    settings.raid_seconds,
+                    "antispam-limit-joins"
+                ),
+                days = days
+            ),
+        },
+        raid_action = settings.raid_action
+    )
+}
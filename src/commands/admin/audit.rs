@@ -0,0 +1,153 @@
+use crate::audit::{AuditAction, AuditOutcome};
+use crate::database::{self, AuditEntry, AuditQuery};
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings::Locale;
+use chrono::{DateTime, NaiveDate, Utc};
+use poise::serenity_prelude::User;
+
+/// Discord rejects message content longer than 2000 characters.
+const RESPONSE_LIMIT: usize = 1900;
+
+/// Search the moderation audit log
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn audit(
+    ctx: Context<'_>,
+    #[description = "Only actions taken by this moderator"] actor: Option<User>,
+    #[description = "Only this kind of action"] action: Option<AuditAction>,
+    #[description = "From this date (YYYY-MM-DD) or this long ago (e.g. 7d)"] since: Option<String>,
+    #[description = "Up to and including this date (YYYY-MM-DD)"] until: Option<String>,
+    #[description = "Number of entries to show (max 25)"] limit: Option<i32>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        actor_id = ?actor.as_ref().map(|u| u.id),
+        action = ?action,
+        since = ?since,
+        until = ?until,
+        "Audit command invoked"
+    );
+
+    let since = since
+        .as_deref()
+        .map(|since| parse_since(locale, since))
+        .transpose()?;
+    let until = until
+        .as_deref()
+        .map(|until| parse_until(locale, until))
+        .transpose()?;
+    let actor_id = actor.as_ref().map(|u| u.id.to_string());
+
+    let entries = database::get_audit_entries(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        &AuditQuery {
+            actor_id: actor_id.as_deref(),
+            action: action.map(|a| a.as_str()),
+            since,
+            until,
+            limit: Some(limit.unwrap_or(10).clamp(1, 25)),
+        },
+    )
+    .await?;
+
+    if entries.is_empty() {
+        ctx.say(tr!(locale, "audit-empty")).await?;
+        return Ok(());
+    }
+
+    let mut response = tr!(locale, "audit-title", count = entries.len()) + "\n\n";
+    for (index, entry) in entries.iter().enumerate() {
+        let line = describe_entry(locale, entry);
+        if response.len() + line.len() > RESPONSE_LIMIT {
+            response.push_str(&tr!(locale, "audit-more", count = entries.len() - index));
+            break;
+        }
+        response.push_str(&line);
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok()
+}
+
+fn parse_since(locale: Locale, input: &str) -> Result<DateTime<Utc>> {
+    if let Some(date) = parse_date(input) {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    duration::parse(input)
+        .and_then(|ago| Utc::now().checked_sub_signed(ago))
+        .ok_or_else(|| bot_error(tr!(locale, "audit-invalid-since", input = input)))
+}
+
+fn parse_until(locale: Locale, input: &str) -> Result<DateTime<Utc>> {
+    let date = parse_date(input)
+        .ok_or_else(|| bot_error(tr!(locale, "audit-invalid-until", input = input)))?;
+
+    Ok(date
+        .succ_opt()
+        .unwrap_or(date)
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc())
+}
+
+fn describe_entry(locale: Locale, entry: &AuditEntry) -> String {
+    let target = entry
+        .target
+        .as_deref()
+        .map(|target| target.chars().take(120).collect::<String>());
+
+    let mut line = match target {
+        Some(target) => tr!(
+            locale,
+            "audit-entry-target",
+            id = entry.id,
+            date = entry.created_at.format("%Y-%m-%d %H:%M UTC"),
+            action = entry.action,
+            actor = entry.actor_name,
+            target = target
+        ),
+        None => tr!(
+            locale,
+            "audit-entry",
+            id = entry.id,
+            date = entry.created_at.format("%Y-%m-%d %H:%M UTC"),
+            action = entry.action,
+            actor = entry.actor_name
+        ),
+    };
+    let outcome = AuditOutcome::from_name(&entry.outcome)
+        .map_or_else(|| entry.outcome.clone(), |o| o.label(locale));
+    line.push('\n');
+    line.push_str(&tr!(
+        locale,
+        "audit-entry-counts",
+        outcome = outcome,
+        checked = entry.checked_count,
+        affected = entry.affected_count,
+        failed = entry.failed_count
+    ));
+    line.push('\n');
+
+    line
+}
@@ -0,0 +1,319 @@
+use crate::database::{self, AutoCleanRule, NewAutoCleanRule};
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::pattern::{self, PatternOptions};
+use crate::settings::Locale;
+use chrono::Duration;
+use poise::serenity_prelude::{GuildChannel, Mentionable};
+
+/// Discord rejects message content longer than 2000 characters.
+const RESPONSE_LIMIT: usize = 1900;
+
+/// Manage scheduled auto-clean rules
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "autoclean_add",
+        "autoclean_list",
+        "autoclean_toggle",
+        "autoclean_remove"
+    ),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn autoclean(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Schedule a recurring clean-up of a channel
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "add",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn autoclean_add(
+    ctx: Context<'_>,
+    #[description = "Channel to clean"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+    #[description = "How often to run (e.g. 1h, 6h, 1d)"] every: String,
+    #[description = "Delete messages older than this (e.g. 24h)"] older_than: Option<String>,
+    #[description = "Only delete messages matching this regex"] pattern: Option<String>,
+    #[description = "Ignore letter case when matching"] case_insensitive: Option<bool>,
+    #[description = "Make ^ and $ match at line boundaries"] multi_line: Option<bool>,
+    #[description = "Match the pattern as plain text instead of a regex"] literal: Option<bool>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        channel_id = %channel.id,
+        every = %every,
+        older_than = ?older_than,
+        pattern = ?pattern,
+        "Auto-clean add command invoked"
+    );
+
+    if channel.guild_id != guild_id {
+        return Err(bot_error(tr!(locale, "settings-channel-elsewhere")));
+    }
+
+    let interval = duration::parse(&every)
+        .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = every)))?;
+    if interval < Duration::minutes(5) || interval > Duration::days(7) {
+        return Err(bot_error(tr!(locale, "autoclean-interval-range")));
+    }
+
+    let max_age = match older_than.as_deref() {
+        Some(input) => {
+            let max_age = duration::parse(input)
+                .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = input)))?;
+            if max_age < Duration::minutes(1) || max_age > Duration::days(365) {
+                return Err(bot_error(tr!(locale, "autoclean-max-age-range")));
+            }
+            Some(max_age)
+        }
+        None => None,
+    };
+
+    let pattern = pattern.filter(|p| !p.trim().is_empty());
+    if pattern.is_none() && max_age.is_none() {
+        return Err(bot_error(tr!(locale, "autoclean-needs-filter")));
+    }
+
+    let options = PatternOptions {
+        case_insensitive: case_insensitive.unwrap_or(false),
+        multi_line: multi_line.unwrap_or(false),
+        literal: literal.unwrap_or(false),
+    };
+    if let Some(pattern) = &pattern {
+        pattern::compile(locale, pattern, options)?;
+    }
+
+    let rule_id = database::save_auto_clean_rule(
+        &ctx.data().database,
+        &NewAutoCleanRule {
+            guild_id: &guild_id.to_string(),
+            channel_id: &channel.id.to_string(),
+            interval_minutes: interval.num_minutes(),
+            max_age_minutes: max_age.map(|d| d.num_minutes()),
+            pattern: pattern.as_deref(),
+            case_insensitive: options.case_insensitive,
+            multi_line: options.multi_line,
+            literal: options.literal,
+            created_by_id: &ctx.author().id.to_string(),
+            created_by_name: &ctx.author().name,
+        },
+    )
+    .await?;
+
+    let mut response = tr!(
+        locale,
+        "autoclean-created",
+        id = rule_id,
+        channel = channel.id.mention(),
+        interval = duration::format(interval)
+    );
+    if let Some(max_age) = max_age {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "autoclean-created-max-age",
+            max_age = duration::format(max_age)
+        ));
+    }
+    if let Some(pattern) = &pattern {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "autoclean-created-pattern",
+            pattern = pattern,
+            flags = options.label(locale)
+        ));
+    }
+    response.push('\n');
+    response.push_str(&tr!(locale, "autoclean-pinned"));
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// List the auto-clean rules of this server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "list",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn autoclean_list(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let rules = database::get_auto_clean_rules(&ctx.data().database, &guild_id.to_string()).await?;
+
+    if rules.is_empty() {
+        ctx.say(tr!(locale, "autoclean-list-empty")).await?;
+        return Ok(());
+    }
+
+    let mut response = tr!(locale, "autoclean-list-title", count = rules.len()) + "\n\n";
+    for (index, rule) in rules.iter().enumerate() {
+        let line = describe_rule(locale, rule);
+        if response.len() + line.len() + 2 > RESPONSE_LIMIT {
+            response.push_str(&tr!(
+                locale,
+                "autoclean-list-more",
+                count = rules.len() - index
+            ));
+            break;
+        }
+        response.push_str(&line);
+        response.push_str("\n\n");
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Pause or resume an auto-clean rule
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "toggle",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn autoclean_toggle(
+    ctx: Context<'_>,
+    #[description = "ID of the rule"] rule_id: i64,
+    #[description = "Whether the rule should run"] enabled: bool,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let updated = database::set_auto_clean_rule_enabled(
+        &ctx.data().database,
+        rule_id,
+        &guild_id.to_string(),
+        enabled,
+    )
+    .await?;
+
+    let response = match (updated, enabled) {
+        (true, true) => tr!(locale, "autoclean-resumed", id = rule_id),
+        (true, false) => tr!(locale, "autoclean-paused", id = rule_id),
+        (false, _) => tr!(locale, "autoclean-not-found"),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Delete an auto-clean rule
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "remove",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn autoclean_remove(
+    ctx: Context<'_>,
+    #[description = "ID of the rule"] rule_id: i64,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let deleted =
+        database::delete_auto_clean_rule(&ctx.data().database, rule_id, &guild_id.to_string())
+            .await?;
+
+    let response = match deleted {
+        true => tr!(locale, "autoclean-deleted", id = rule_id),
+        false => tr!(locale, "autoclean-not-found"),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+fn describe_rule(locale: Locale, rule: &AutoCleanRule) -> String {
+    let mut line = tr!(
+        locale,
+        "autoclean-rule",
+        id = rule.id,
+        channel = format!("<#{}>", rule.channel_id),
+        interval = duration::format(Duration::minutes(rule.interval_minutes))
+    );
+
+    if let Some(max_age) = rule.max_age_minutes {
+        line.push_str(&tr!(
+            locale,
+            "autoclean-rule-max-age",
+            max_age = duration::format(Duration::minutes(max_age))
+        ));
+    }
+
+    if let Some(pattern) = &rule.pattern {
+        let options = PatternOptions {
+            case_insensitive: rule.case_insensitive,
+            multi_line: rule.multi_line,
+            literal: rule.literal,
+        };
+        line.push_str(&tr!(
+            locale,
+            "autoclean-rule-pattern",
+            pattern = pattern,
+            flags = options.label(locale)
+        ));
+    }
+
+    if !rule.enabled {
+        line.push(' ');
+        line.push_str(&tr!(locale, "autoclean-rule-paused"));
+    }
+
+    line.push('\n');
+    line.push_str(&match (rule.last_run_at, &rule.last_error) {
+        (Some(_), Some(error)) => tr!(locale, "autoclean-rule-failed", error = error),
+        (Some(last_run), None) => tr!(
+            locale,
+            "autoclean-rule-last-run",
+            date = last_run.format("%Y-%m-%d %H:%M UTC"),
+            checked = rule.last_checked.unwrap_or(0),
+            deleted = rule.last_deleted.unwrap_or(0),
+            failed = rule.last_failed.unwrap_or(0)
+        ),
+        (None, _) => tr!(locale, "autoclean-rule-not-run"),
+    });
+
+    line
+}
@@ -0,0 +1,378 @@
+use crate::automod::{self, AutomodActions, AutomodKind, DEFAULT_TIMEOUT_MINUTES};
+use crate::database::{self, AutomodRule, NewAutomodRule};
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::moderation::MAX_TIMEOUT_DAYS;
+use crate::pattern::{self, PatternOptions};
+use crate::settings::Locale;
+use chrono::Duration;
+
+/// Longest rule name, in characters.
+const MAX_NAME_LENGTH: usize = 50;
+
+/// Manage auto-moderation rules for new messages
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands("automod_add", "automod_list", "automod_toggle", "automod_remove"),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn automod(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Add a rule checked against every new message
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "add",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn automod_add(
+    ctx: Context<'_>,
+    #[description = "Short name shown in logs"] name: String,
+    #[description = "What the rule looks for"] kind: AutomodKind,
+    #[description = "Regex, or comma separated words or domains"] pattern: Option<String>,
+    #[description = "Mentions that trigger the rule (mention rules)"] mentions: Option<i64>,
+    #[description = "Delete the message"] delete: Option<bool>,
+    #[description = "Warn the author"] warn: Option<bool>,
+    #[description = "Time the author out for this long (e.g. 10m)"] timeout: Option<String>,
+    #[description = "Record the match in the audit log"] log: Option<bool>,
+    #[description = "Ignore letter case (regex rules)"] case_insensitive: Option<bool>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        name = %name,
+        kind = %kind.as_str(),
+        pattern = ?pattern,
+        "Automod add command invoked"
+    );
+
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(bot_error(tr!(
+            locale,
+            "automod-name-length",
+            max = MAX_NAME_LENGTH
+        )));
+    }
+
+    let pattern = pattern.filter(|p| !p.trim().is_empty());
+    let case_insensitive = case_insensitive.unwrap_or(false);
+
+    let (pattern, threshold) = match (kind, pattern) {
+        (AutomodKind::Mentions, Some(_)) => {
+            return Err(bot_error(tr!(locale, "automod-mentions-no-pattern")));
+        }
+        (AutomodKind::Mentions, None) => {
+            let threshold =
+                mentions.ok_or_else(|| bot_error(tr!(locale, "automod-mentions-needed")))?;
+            if !(1..=50).contains(&threshold) {
+                return Err(bot_error(tr!(
+                    locale,
+                    "error-option-range",
+                    option = "mentions",
+                    min = 1,
+                    max = 50
+                )));
+            }
+            (None, Some(threshold))
+        }
+        (_, None) => return Err(bot_error(tr!(locale, "automod-pattern-needed"))),
+        (AutomodKind::Regex, Some(source)) => {
+            pattern::compile(
+                locale,
+                &source,
+                PatternOptions {
+                    case_insensitive,
+                    ..Default::default()
+                },
+            )?;
+            (Some(source), None)
+        }
+        (AutomodKind::Words, Some(source)) => {
+            let words = automod::parse_list(&source);
+            pattern::compile_words(locale, &words)?;
+            (Some(words.join(", ")), None)
+        }
+        (AutomodKind::Links, Some(source)) => {
+            let domains = automod::parse_list(&source)
+                .iter()
+                .map(|entry| {
+                    automod::normalize_domain(entry).ok_or_else(|| {
+                        bot_error(tr!(locale, "automod-invalid-domain", domain = entry))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if domains.is_empty() {
+                return Err(bot_error(tr!(locale, "automod-domains-needed")));
+            }
+            (Some(domains.join(", ")), None)
+        }
+    };
+
+    let timeout = match timeout.as_deref() {
+        Some(input) => {
+            let timeout = duration::parse(input)
+                .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = input)))?;
+            if timeout < Duration::minutes(1) || timeout > Duration::days(MAX_TIMEOUT_DAYS) {
+                return Err(bot_error(tr!(
+                    locale,
+                    "error-timeout-range",
+                    max_days = MAX_TIMEOUT_DAYS
+                )));
+            }
+            Some(timeout)
+        }
+        None => None,
+    };
+
+    let actions = AutomodActions {
+        delete: delete.unwrap_or(false),
+        warn: warn.unwrap_or(false),
+        timeout: timeout.is_some(),
+        log: log.unwrap_or(false),
+    };
+    if actions.is_empty() {
+        return Err(bot_error(tr!(locale, "automod-action-needed")));
+    }
+
+    let rule_id = database::save_automod_rule(
+        &ctx.data().database,
+        &NewAutomodRule {
+            guild_id: &guild_id.to_string(),
+            name,
+            kind: kind.as_str(),
+            pattern: pattern.as_deref(),
+            threshold,
+            case_insensitive,
+            actions: &actions.to_string(),
+            timeout_minutes: timeout.map(|t| t.num_minutes()),
+            created_by_id: &ctx.author().id.to_string(),
+            created_by_name: &ctx.author().name,
+        },
+    )
+    .await?;
+
+    ctx.data().automod.invalidate(guild_id);
+
+    let mut response = tr!(
+        locale,
+        "automod-created",
+        id = rule_id,
+        name = name,
+        kind = kind.as_str()
+    );
+    let matches = match (&pattern, threshold) {
+        (Some(pattern), _) => Some(format!("`{}`", pattern)),
+        (None, Some(threshold)) => Some(tr!(locale, "automod-mentions", count = threshold)),
+        (None, None) => None,
+    };
+    if let Some(matches) = matches {
+        response.push('\n');
+        response.push_str(&tr!(locale, "automod-created-matches", matches = matches));
+    }
+    response.push('\n');
+    response.push_str(&tr!(
+        locale,
+        "automod-created-actions",
+        actions = actions.to_string().replace(',', ", ")
+    ));
+    if let Some(timeout) = timeout {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "automod-created-timeout",
+            timeout = duration::format(timeout)
+        ));
+    }
+    response.push('\n');
+    response.push_str(&tr!(locale, "automod-exempt"));
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// List the automod rules of this server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "list",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn automod_list(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let rules = database::get_automod_rules(&ctx.data().database, &guild_id.to_string()).await?;
+
+    if rules.is_empty() {
+        ctx.say(tr!(locale, "automod-list-empty")).await?;
+        return Ok(());
+    }
+
+    let mut response = tr!(locale, "automod-list-title", count = rules.len()) + "\n\n";
+    for rule in &rules {
+        response.push_str(&describe_rule(locale, rule));
+        response.push_str("\n\n");
+    }
+
+    if response.len() > 1900 {
+        response = response.chars().take(1900).collect();
+        response.push('\n');
+        response.push_str(&tr!(locale, "response-truncated"));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Pause or resume an automod rule
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "toggle",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn automod_toggle(
+    ctx: Context<'_>,
+    #[description = "ID of the rule"] rule_id: i64,
+    #[description = "Whether the rule should run"] enabled: bool,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let updated = database::set_automod_rule_enabled(
+        &ctx.data().database,
+        rule_id,
+        &guild_id.to_string(),
+        enabled,
+    )
+    .await?;
+
+    if updated {
+        ctx.data().automod.invalidate(guild_id);
+        let response = match enabled {
+            true => tr!(locale, "automod-resumed", id = rule_id),
+            false => tr!(locale, "automod-paused", id = rule_id),
+        };
+        ctx.say(response).await?;
+    } else {
+        ctx.say(tr!(locale, "automod-not-found")).await?;
+    }
+
+    Ok(())
+}
+
+/// Delete an automod rule
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "remove",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn automod_remove(
+    ctx: Context<'_>,
+    #[description = "ID of the rule"] rule_id: i64,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let deleted =
+        database::delete_automod_rule(&ctx.data().database, rule_id, &guild_id.to_string()).await?;
+
+    if deleted {
+        ctx.data().automod.invalidate(guild_id);
+        ctx.say(tr!(locale, "automod-deleted", id = rule_id))
+            .await?;
+    } else {
+        ctx.say(tr!(locale, "automod-not-found")).await?;
+    }
+
+    Ok(())
+}
+
+fn describe_rule(locale: Locale, rule: &AutomodRule) -> String {
+    let mut line = format!("**#{}** {} ({})", rule.id, rule.name, rule.kind);
+
+    match (&rule.pattern, rule.threshold) {
+        (Some(pattern), _) if rule.kind == AutomodKind::Regex.as_str() => {
+            line.push_str(&format!(": `{}`", pattern));
+            if rule.case_insensitive {
+                line.push(' ');
+                line.push_str(&tr!(locale, "automod-rule-case-insensitive"));
+            }
+        }
+        (Some(pattern), _) => line.push_str(&format!(": {}", pattern)),
+        (None, Some(threshold)) => {
+            line.push_str(": ");
+            line.push_str(&tr!(locale, "automod-mentions", count = threshold));
+        }
+        (None, None) => {}
+    }
+
+    let actions = AutomodActions::parse(&rule.actions);
+    line.push('\n');
+    line.push_str(&tr!(
+        locale,
+        "automod-rule-actions",
+        actions = actions.to_string().replace(',', ", ")
+    ));
+    if actions.timeout {
+        line.push_str(&format!(
+            " ({})",
+            duration::format(Duration::minutes(
+                rule.timeout_minutes.unwrap_or(DEFAULT_TIMEOUT_MINUTES)
+            ))
+        ));
+    }
+
+    if !rule.enabled {
+        line.push(' ');
+        line.push_str(&tr!(locale, "automod-rule-paused"));
+    }
+
+    line.push('\n');
+    line.push_str(&match rule.last_hit_at {
+        Some(last_hit) => tr!(
+            locale,
+            "automod-rule-hits",
+            count = rule.hit_count,
+            date = last_hit.format("%Y-%m-%d %H:%M UTC")
+        ),
+        None => tr!(locale, "automod-rule-no-hits"),
+    });
+
+    line
+}
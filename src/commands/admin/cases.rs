@@ -0,0 +1,343 @@
+use crate::audit::{self, AuditAction, AuditOutcome};
+use crate::database::{self, CaseEvidence, ModerationCase};
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings::Locale;
+use poise::ChoiceParameter;
+use poise::serenity_prelude::{Colour, CreateEmbed, CreateEmbedFooter, GuildId, Timestamp};
+
+/// Longest reason a case can hold, matching what Discord keeps in its own audit log.
+const MAX_REASON_LENGTH: usize = 512;
+
+/// Longest evidence link accepted.
+const MAX_URL_LENGTH: usize = 500;
+
+/// Evidence links shown on a case before the rest are summarised.
+const MAX_EVIDENCE_SHOWN: usize = 10;
+
+/// Look up a moderation case
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn case(
+    ctx: Context<'_>,
+    #[description = "Case number"]
+    #[min = 1]
+    number: i64,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        case_number = %number,
+        "Case command invoked"
+    );
+
+    let case = find_case(ctx, locale, guild_id, number).await?;
+    let evidence = database::get_case_evidence(&ctx.data().database, case.id).await?;
+
+    ctx.send(poise::CreateReply::default().embed(case_embed(locale, &case, &evidence)))
+        .await?;
+
+    Ok(())
+}
+
+/// Change the reason of a moderation case
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn reason(
+    ctx: Context<'_>,
+    #[description = "Case number"]
+    #[min = 1]
+    number: i64,
+    #[description = "New reason"]
+    #[rest]
+    reason: String,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        case_number = %number,
+        "Reason command invoked"
+    );
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(bot_error(tr!(locale, "error-reason-empty")));
+    }
+    if reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(bot_error(tr!(
+            locale,
+            "case-reason-too-long",
+            max = MAX_REASON_LENGTH
+        )));
+    }
+
+    let case = find_case(ctx, locale, guild_id, number).await?;
+
+    database::update_case_reason(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        number,
+        reason,
+        &ctx.author().id.to_string(),
+    )
+    .await?;
+
+    let moderator = ctx.author().id;
+    audit::post_to_mod_log(ctx.http(), &ctx.data().database, guild_id, |locale| {
+        let previous = case
+            .reason
+            .clone()
+            .unwrap_or_else(|| tr!(locale, "case-no-reason"));
+        CreateEmbed::new()
+            .title(tr!(locale, "case-reason-updated-title", number = number))
+            .colour(Colour::BLUE)
+            .field(
+                tr!(locale, "audit-field-moderator"),
+                format!("<@{}>", moderator),
+                true,
+            )
+            .field(
+                tr!(locale, "case-field-previous-reason"),
+                audit::truncate_field(&previous),
+                false,
+            )
+            .field(
+                tr!(locale, "case-field-new-reason"),
+                audit::truncate_field(reason),
+                false,
+            )
+            .footer(CreateEmbedFooter::new(tr!(
+                locale,
+                "audit-footer",
+                id = case.audit_id
+            )))
+            .timestamp(Timestamp::now())
+    })
+    .await;
+
+    ctx.say(tr!(
+        locale,
+        "case-reason-updated",
+        number = number,
+        reason = reason
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Attach an evidence link to a moderation case
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn evidence(
+    ctx: Context<'_>,
+    #[description = "Case number"]
+    #[min = 1]
+    number: i64,
+    #[description = "Link to a screenshot, message or file"] url: String,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        case_number = %number,
+        "Evidence command invoked"
+    );
+
+    let url = url.trim();
+    if !is_link(url) {
+        return Err(bot_error(tr!(locale, "case-evidence-invalid")));
+    }
+
+    let case = find_case(ctx, locale, guild_id, number).await?;
+
+    database::add_case_evidence(
+        &ctx.data().database,
+        case.id,
+        url,
+        &ctx.author().id.to_string(),
+    )
+    .await?;
+
+    let moderator = ctx.author().id;
+    audit::post_to_mod_log(ctx.http(), &ctx.data().database, guild_id, |locale| {
+        CreateEmbed::new()
+            .title(tr!(locale, "case-evidence-added-title", number = number))
+            .colour(Colour::BLUE)
+            .field(
+                tr!(locale, "audit-field-moderator"),
+                format!("<@{}>", moderator),
+                true,
+            )
+            .field(tr!(locale, "case-field-link"), url, false)
+            .footer(CreateEmbedFooter::new(tr!(
+                locale,
+                "audit-footer",
+                id = case.audit_id
+            )))
+            .timestamp(Timestamp::now())
+    })
+    .await;
+
+    ctx.say(tr!(locale, "case-evidence-added", number = number))
+        .await?;
+
+    Ok(())
+}
+
+async fn find_case(
+    ctx: Context<'_>,
+    locale: Locale,
+    guild_id: GuildId,
+    number: i64,
+) -> Result<ModerationCase> {
+    database::get_case(&ctx.data().database, &guild_id.to_string(), number)
+        .await?
+        .ok_or_else(|| bot_error(tr!(locale, "case-not-found", number = number)))
+}
+
+fn is_link(url: &str) -> bool {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return false;
+    };
+
+    !rest.is_empty() && url.len() <= MAX_URL_LENGTH && !url.chars().any(char::is_whitespace)
+}
+
+fn case_embed(locale: Locale, case: &ModerationCase, evidence: &[CaseEvidence]) -> CreateEmbed {
+    let label = AuditAction::from_name(&case.action)
+        .map_or_else(|| case.action.clone(), |a| a.label(locale));
+    let outcome = AuditOutcome::from_name(&case.outcome)
+        .map_or_else(|| case.outcome.clone(), |o| o.label(locale));
+
+    let mut reason = case
+        .reason
+        .clone()
+        .unwrap_or_else(|| tr!(locale, "case-no-reason"));
+    if let (Some(by), Some(at)) = (&case.reason_updated_by_id, case.reason_updated_at) {
+        reason.push('\n');
+        reason.push_str(&tr!(
+            locale,
+            "case-reason-edited",
+            user = format!("<@{}>", by),
+            time = format!("<t:{}:R>", at.timestamp())
+        ));
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(tr!(
+            locale,
+            "case-title",
+            number = case.case_number,
+            label = label
+        ))
+        .colour(Colour::BLUE)
+        .field(
+            tr!(locale, "audit-field-moderator"),
+            format!("<@{}>", case.actor_id),
+            true,
+        )
+        .field(tr!(locale, "audit-field-outcome"), outcome, true)
+        .footer(CreateEmbedFooter::new(tr!(
+            locale,
+            "audit-footer",
+            id = case.audit_id
+        )))
+        .timestamp(Timestamp::from(case.created_at));
+
+    if let Some(target) = &case.target {
+        embed = embed.field(
+            tr!(locale, "audit-field-target"),
+            audit::truncate_field(target),
+            true,
+        );
+    }
+    embed = embed.field(
+        tr!(locale, "audit-parameter-reason"),
+        audit::truncate_field(&reason),
+        false,
+    );
+
+    // The reason is shown above, including edits made after the action.
+    let mut parameters: serde_json::Value =
+        serde_json::from_str(&case.parameters).unwrap_or_default();
+    if let Some(object) = parameters.as_object_mut() {
+        object.remove("reason");
+    }
+    for (name, value) in audit::parameter_fields(locale, &parameters) {
+        embed = embed.field(name, value, false);
+    }
+
+    if !evidence.is_empty() {
+        let mut links: Vec<String> = evidence
+            .iter()
+            .take(MAX_EVIDENCE_SHOWN)
+            .map(|e| format!("{} (<@{}>)", e.url, e.added_by_id))
+            .collect();
+        if evidence.len() > MAX_EVIDENCE_SHOWN {
+            links.push(tr!(
+                locale,
+                "case-evidence-more",
+                count = evidence.len() - MAX_EVIDENCE_SHOWN
+            ));
+        }
+        embed = embed.field(
+            tr!(locale, "case-field-evidence"),
+            audit::truncate_field(&links.join("\n")),
+            false,
+        );
+    }
+
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_link() {
+        assert!(is_link("https://example.com/a.png"));
+        assert!(is_link("http://example.com"));
+        assert!(!is_link("https://"));
+        assert!(!is_link("ftp://example.com"));
+        assert!(!is_link("example.com"));
+        assert!(!is_link("https://example.com/a b"));
+        assert!(!is_link(&format!("https://{}", "x".repeat(MAX_URL_LENGTH))));
+    }
+}
@@ -0,0 +1,413 @@
+use super::sanctions::normalize_reason;
+use crate::audit::{self, AuditAction, AuditOutcome, AuditRecord};
+use crate::database;
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::lockdown::{self, LockReport, MAX_SLOWMODE_SECONDS};
+use crate::moderation::{Moderator, truncate_reason};
+use crate::settings::Locale;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{ChannelId, ChannelType, EditChannel, GuildChannel};
+
+/// Longest timed lock.
+const MAX_LOCK_DAYS: i64 = 30;
+
+/// Stop @everyone from talking in a channel or the whole server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_CHANNELS",
+    required_bot_permissions = "MANAGE_CHANNELS | MANAGE_ROLES",
+    default_member_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn lock(
+    ctx: Context<'_>,
+    #[description = "Channel to lock (defaults to current)"]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Lock every channel @everyone can talk in"] server: Option<bool>,
+    #[description = "Unlock automatically after this long (e.g. 30m, 2h)"] duration: Option<String>,
+    #[description = "Why the channel is being locked"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let reason = normalize_reason(locale, reason);
+    let server = server.unwrap_or(false);
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        channel_id = ?channel.as_ref().map(|c| c.id),
+        server = %server,
+        duration = ?duration,
+        "Lock command invoked"
+    );
+
+    let length = match duration.as_deref() {
+        Some(input) => {
+            let length = duration::parse(input)
+                .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = input)))?;
+            if length < Duration::minutes(1) || length > Duration::days(MAX_LOCK_DAYS) {
+                return Err(bot_error(tr!(
+                    locale,
+                    "lock-duration-range",
+                    max_days = MAX_LOCK_DAYS
+                )));
+            }
+            Some(length)
+        }
+        None => None,
+    };
+    let expires_at = length.map(|length| Utc::now() + length);
+
+    let channels = if server {
+        if channel.is_some() {
+            return Err(bot_error(tr!(locale, "lock-channel-or-server")));
+        }
+        let guild = ctx
+            .guild()
+            .ok_or_else(|| bot_error(tr!(locale, "error-guild-not-cached")))?;
+        lockdown::public_channels(&guild)
+    } else {
+        vec![target_channel(ctx, locale, channel)?]
+    };
+
+    if channels.is_empty() {
+        return Err(bot_error(tr!(locale, "lock-no-public-channels")));
+    }
+
+    ctx.defer().await?;
+
+    let actor = Moderator::new(ctx.author().id, &ctx.author().name);
+    let report = lockdown::lock_channels(
+        ctx.http(),
+        &ctx.data().database,
+        guild_id,
+        &channels,
+        &actor,
+        &truncate_reason(&reason),
+        expires_at,
+    )
+    .await;
+
+    audit_report(
+        ctx,
+        AuditAction::Lock,
+        &report,
+        serde_json::json!({
+            "reason": reason,
+            "server": server,
+            "channels": report.changed.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            "duration_minutes": length.map(|l| l.num_minutes()),
+            "expires_at": expires_at.map(|t| t.to_rfc3339()),
+            "errors": report.errors,
+        }),
+    )
+    .await;
+
+    let mut response = describe_report(
+        locale,
+        &report,
+        |channels| tr!(locale, "lock-done", channels = channels),
+        &tr!(locale, "lock-skipped-locked"),
+    );
+    if !report.changed.is_empty() {
+        response.push('\n');
+        response.push_str(&tr!(locale, "lock-reason", reason = reason));
+        if let Some(expires_at) = expires_at {
+            response.push('\n');
+            response.push_str(&tr!(
+                locale,
+                "lock-expires",
+                time = format!("<t:{}:R>", expires_at.timestamp())
+            ));
+        }
+    }
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Restore the permissions a channel or the whole server had before it was locked
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_CHANNELS",
+    required_bot_permissions = "MANAGE_CHANNELS | MANAGE_ROLES",
+    default_member_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn unlock(
+    ctx: Context<'_>,
+    #[description = "Channel to unlock (defaults to current)"]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Unlock every locked channel"] server: Option<bool>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let server = server.unwrap_or(false);
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        channel_id = ?channel.as_ref().map(|c| c.id),
+        server = %server,
+        "Unlock command invoked"
+    );
+
+    let mut locks =
+        database::get_active_channel_locks(&ctx.data().database, &guild_id.to_string()).await?;
+    if server {
+        if channel.is_some() {
+            return Err(bot_error(tr!(locale, "lock-channel-or-server")));
+        }
+    } else {
+        let channel_id = target_channel(ctx, locale, channel)?.to_string();
+        locks.retain(|lock| lock.channel_id == channel_id);
+    }
+
+    if locks.is_empty() {
+        return Err(bot_error(match server {
+            true => tr!(locale, "unlock-none-locked"),
+            false => tr!(locale, "unlock-not-locked"),
+        }));
+    }
+
+    ctx.defer().await?;
+
+    let report = lockdown::unlock_channels(ctx.http(), &ctx.data().database, &locks).await;
+
+    audit_report(
+        ctx,
+        AuditAction::Unlock,
+        &report,
+        serde_json::json!({
+            "server": server,
+            "channels": report.changed.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            "channel_lock_ids": locks.iter().map(|lock| lock.id).collect::<Vec<_>>(),
+            "errors": report.errors,
+        }),
+    )
+    .await;
+
+    ctx.say(describe_report(
+        locale,
+        &report,
+        |channels| tr!(locale, "unlock-done", channels = channels),
+        &tr!(locale, "unlock-skipped-unlocked"),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Limit how often members can post in a channel or the whole server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_CHANNELS",
+    required_bot_permissions = "MANAGE_CHANNELS",
+    default_member_permissions = "MANAGE_CHANNELS"
+)]
+pub async fn slowmode(
+    ctx: Context<'_>,
+    #[description = "Delay between messages (e.g. 10s, 5m), or off"] delay: String,
+    #[description = "Channel to change (defaults to current)"]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+    #[description = "Change every text channel"] server: Option<bool>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let server = server.unwrap_or(false);
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        channel_id = ?channel.as_ref().map(|c| c.id),
+        server = %server,
+        delay = %delay,
+        "Slowmode command invoked"
+    );
+
+    let seconds = match delay.trim().to_ascii_lowercase().as_str() {
+        "off" | "0" | "0s" => 0,
+        input => {
+            let delay = duration::parse(input)
+                .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = input)))?;
+            u16::try_from(delay.num_seconds())
+                .ok()
+                .filter(|seconds| (1..=MAX_SLOWMODE_SECONDS).contains(seconds))
+                .ok_or_else(|| bot_error(tr!(locale, "slowmode-range")))?
+        }
+    };
+
+    let channels = if server {
+        if channel.is_some() {
+            return Err(bot_error(tr!(locale, "lock-channel-or-server")));
+        }
+        let guild = ctx
+            .guild()
+            .ok_or_else(|| bot_error(tr!(locale, "error-guild-not-cached")))?;
+        let mut channels: Vec<_> = guild
+            .channels
+            .values()
+            .filter(|channel| matches!(channel.kind, ChannelType::Text | ChannelType::News))
+            .map(|channel| channel.id)
+            .collect();
+        channels.sort();
+        channels
+    } else {
+        vec![target_channel(ctx, locale, channel)?]
+    };
+
+    ctx.defer().await?;
+
+    let audit_reason = truncate_reason(&format!("Slowmode set by {}", ctx.author().name));
+    let mut report = LockReport::default();
+    for channel_id in channels {
+        let edit = EditChannel::new()
+            .rate_limit_per_user(seconds)
+            .audit_log_reason(&audit_reason);
+        match channel_id.edit(ctx.http(), edit).await {
+            Ok(_) => report.changed.push(channel_id),
+            Err(e) => {
+                tracing::warn!(channel_id = %channel_id, error = %e, "Failed to set slowmode");
+                report.errors.push(format!("<#{}>: {}", channel_id, e));
+            }
+        }
+    }
+
+    audit_report(
+        ctx,
+        AuditAction::Slowmode,
+        &report,
+        serde_json::json!({
+            "seconds": seconds,
+            "server": server,
+            "channels": report.changed.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            "errors": report.errors,
+        }),
+    )
+    .await;
+
+    let delay = duration::format(Duration::seconds(seconds.into()));
+    ctx.say(describe_report(
+        locale,
+        &report,
+        |channels| match seconds {
+            0 => tr!(locale, "slowmode-off", channels = channels),
+            _ => tr!(locale, "slowmode-set", delay = delay, channels = channels),
+        },
+        &tr!(locale, "slowmode-skipped-unchanged"),
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// The channel a single-channel command applies to, defaulting to where it was invoked.
+fn target_channel(
+    ctx: Context<'_>,
+    locale: Locale,
+    channel: Option<GuildChannel>,
+) -> Result<ChannelId> {
+    match channel {
+        Some(channel) if Some(channel.guild_id) != ctx.guild_id() => {
+            Err(bot_error(tr!(locale, "settings-channel-elsewhere")))
+        }
+        Some(channel) => Ok(channel.id),
+        None => Ok(ctx.channel_id()),
+    }
+}
+
+async fn audit_report(
+    ctx: Context<'_>,
+    action: AuditAction,
+    report: &LockReport,
+    parameters: serde_json::Value,
+) {
+    let Some(guild_id) = ctx.guild_id() else {
+        return;
+    };
+
+    let checked = report.changed.len() + report.skipped.len() + report.errors.len();
+    let mut record = AuditRecord::new(guild_id, ctx.author().id, &ctx.author().name, action)
+        .parameters(parameters)
+        .outcome(AuditOutcome::from_counts(
+            report.changed.len() as i64,
+            report.errors.len() as i64,
+        ))
+        .counts(
+            checked as i64,
+            report.changed.len() as i64,
+            report.errors.len() as i64,
+        );
+    if let [channel_id] = report.changed.as_slice() {
+        record = record.target(format!("<#{}>", channel_id));
+    }
+
+    audit::record(ctx.http(), &ctx.data().database, record).await;
+}
+
+/// Summarises `report`, with `changed` phrasing the channels that were changed.
+fn describe_report(
+    locale: Locale,
+    report: &LockReport,
+    changed: impl Fn(String) -> String,
+    skipped: &str,
+) -> String {
+    let mentions = |channels: &[ChannelId]| {
+        channels
+            .iter()
+            .map(|id| format!("<#{}>", id))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut response = if report.changed.is_empty() {
+        tr!(locale, "lock-no-changes")
+    } else {
+        changed(mentions(&report.changed))
+    };
+    if !report.skipped.is_empty() {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "lock-skipped",
+            label = skipped,
+            channels = mentions(&report.skipped)
+        ));
+    }
+    if !report.errors.is_empty() {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "lock-failed",
+            errors = report.errors.join("; ")
+        ));
+    }
+
+    if response.len() > 1900 {
+        response = response.chars().take(1900).collect();
+        response.push('\n');
+        response.push_str(&tr!(locale, "response-truncated"));
+    }
+
+    response
+}
@@ -0,0 +1,270 @@
+use crate::database;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use poise::serenity_prelude::{CreateMessage, GuildChannel, Mentionable};
+
+/// Log edited and deleted messages from selected channels
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "messagelog_set",
+        "messagelog_clear",
+        "messagelog_watch",
+        "messagelog_unwatch",
+        "messagelog_show"
+    ),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn messagelog(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Post edit and delete logs to a channel
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "set",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn messagelog_set(
+    ctx: Context<'_>,
+    #[description = "Channel to post logs to"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        channel_id = %channel.id,
+        "Message log set command invoked"
+    );
+
+    if channel.guild_id != guild_id {
+        return Err(bot_error(tr!(locale, "settings-channel-elsewhere")));
+    }
+
+    let guild_locale = i18n::guild_locale(ctx.data(), guild_id).await;
+    let announcement = CreateMessage::new().content(tr!(
+        guild_locale,
+        "messagelog-announcement",
+        user = ctx.author().id.mention()
+    ));
+    if let Err(e) = channel.id.send_message(ctx.http(), announcement).await {
+        tracing::warn!(channel_id = %channel.id, error = %e, "Failed to post to message log channel");
+        return Err(bot_error(tr!(
+            locale,
+            "error-cannot-post",
+            channel = channel.id.mention()
+        )));
+    }
+
+    database::set_message_log_channel(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        Some(&channel.id.to_string()),
+    )
+    .await?;
+    ctx.data().message_log.invalidate(guild_id);
+    ctx.data().settings.invalidate(guild_id);
+
+    ctx.say(tr!(
+        locale,
+        "messagelog-set",
+        channel = channel.id.mention()
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Stop posting edit and delete logs
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "clear",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn messagelog_clear(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    database::set_message_log_channel(&ctx.data().database, &guild_id.to_string(), None).await?;
+    ctx.data().message_log.invalidate(guild_id);
+    ctx.data().settings.invalidate(guild_id);
+
+    ctx.say(tr!(locale, "messagelog-cleared")).await?;
+
+    Ok(())
+}
+
+/// Log edits and deletions in a channel
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "watch",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn messagelog_watch(
+    ctx: Context<'_>,
+    #[description = "Channel to log"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    if channel.guild_id != guild_id {
+        return Err(bot_error(tr!(locale, "settings-channel-elsewhere")));
+    }
+
+    let added = database::add_message_log_channel(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        &channel.id.to_string(),
+        &ctx.author().id.to_string(),
+    )
+    .await?;
+    ctx.data().message_log.invalidate(guild_id);
+
+    if added {
+        ctx.say(tr!(
+            locale,
+            "messagelog-watched",
+            channel = channel.id.mention()
+        ))
+        .await?;
+    } else {
+        ctx.say(tr!(
+            locale,
+            "messagelog-already-watched",
+            channel = channel.id.mention()
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Stop logging a channel
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "unwatch",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn messagelog_unwatch(
+    ctx: Context<'_>,
+    #[description = "Channel to stop logging"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let removed = database::remove_message_log_channel(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        &channel.id.to_string(),
+    )
+    .await?;
+    ctx.data().message_log.invalidate(guild_id);
+
+    if removed {
+        ctx.say(tr!(
+            locale,
+            "messagelog-unwatched",
+            channel = channel.id.mention()
+        ))
+        .await?;
+    } else {
+        ctx.say(tr!(
+            locale,
+            "messagelog-not-watched",
+            channel = channel.id.mention()
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Show the message log configuration
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "show",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn messagelog_show(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let config = ctx
+        .data()
+        .message_log
+        .config(&ctx.data().database, guild_id)
+        .await?;
+
+    let mut response = match config.log_channel {
+        Some(channel) => tr!(
+            locale,
+            "messagelog-show-channel",
+            channel = channel.mention()
+        ),
+        None => tr!(locale, "messagelog-show-off"),
+    };
+    response.push('\n');
+
+    let mut channels: Vec<_> = config.channels.iter().collect();
+    channels.sort();
+    if channels.is_empty() {
+        response.push_str(&tr!(locale, "messagelog-show-no-watched"));
+    } else {
+        response.push_str(&tr!(
+            locale,
+            "messagelog-show-watched",
+            channels = channels
+                .iter()
+                .map(|id| format!("<#{}>", id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+mod antispam;
+mod audit;
+mod auto_clean;
+mod automod;
+mod cases;
+mod lockdown;
+mod message_log;
+mod mod_log;
+mod permissions;
+mod purge;
+mod role_menus;
+mod sanctions;
+mod settings;
+mod warnings;
+
+use crate::error::{Context, Result};
+
+pub use antispam::*;
+pub use audit::*;
+pub use auto_clean::*;
+pub use automod::*;
+pub use cases::*;
+pub use lockdown::*;
+pub use message_log::*;
+pub use mod_log::*;
+pub use permissions::*;
+pub use purge::*;
+pub use role_menus::*;
+pub use sanctions::*;
+pub use settings::*;
+pub use warnings::*;
+
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "purge",
+        "autoclean",
+        "automod",
+        "antispam",
+        "settings",
+        "permissions",
+        "audit",
+        "case",
+        "reason",
+        "evidence",
+        "modlog",
+        "messagelog",
+        "warn",
+        "warnings",
+        "unwarn",
+        "escalation",
+        "timeout",
+        "kick",
+        "ban",
+        "tempban",
+        "unban",
+        "lock",
+        "unlock",
+        "slowmode",
+        "rolemenu"
+    ),
+    subcommand_required,
+    category = "Admin",
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn admin(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::database;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::scheduler::parse_id;
+use poise::serenity_prelude::{ChannelId, CreateMessage, GuildChannel, Mentionable};
+
+/// Configure the channel that mirrors every moderation action
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands("modlog_set", "modlog_clear", "modlog_show"),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn modlog(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Post moderation actions to a channel
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "set",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn modlog_set(
+    ctx: Context<'_>,
+    #[description = "Channel to post to"]
+    #[channel_types("Text", "News")]
+    channel: GuildChannel,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        channel_id = %channel.id,
+        "Mod-log set command invoked"
+    );
+
+    if channel.guild_id != guild_id {
+        return Err(bot_error(tr!(locale, "settings-channel-elsewhere")));
+    }
+
+    // Post first so a channel the bot cannot write to is rejected instead of silently dropping
+    // every log entry later.
+    let guild_locale = i18n::guild_locale(ctx.data(), guild_id).await;
+    let announcement = CreateMessage::new().content(tr!(
+        guild_locale,
+        "modlog-announcement",
+        user = ctx.author().id.mention()
+    ));
+    if let Err(e) = channel.id.send_message(ctx.http(), announcement).await {
+        tracing::warn!(channel_id = %channel.id, error = %e, "Failed to post to mod-log channel");
+        return Err(bot_error(tr!(
+            locale,
+            "error-cannot-post",
+            channel = channel.id.mention()
+        )));
+    }
+
+    database::set_mod_log_channel(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        Some(&channel.id.to_string()),
+    )
+    .await?;
+    ctx.data().settings.invalidate(guild_id);
+
+    ctx.say(tr!(locale, "modlog-set", channel = channel.id.mention()))
+        .await?;
+
+    Ok(())
+}
+
+/// Stop posting moderation actions
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "clear",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn modlog_clear(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    database::set_mod_log_channel(&ctx.data().database, &guild_id.to_string(), None).await?;
+    ctx.data().settings.invalidate(guild_id);
+
+    ctx.say(tr!(locale, "modlog-cleared")).await?;
+
+    Ok(())
+}
+
+/// Show where moderation actions are posted
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "show",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn modlog_show(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let channel_id = database::get_guild_settings(&ctx.data().database, &guild_id.to_string())
+        .await?
+        .and_then(|settings| settings.mod_log_channel_id)
+        .and_then(|id| parse_id(&id))
+        .map(ChannelId::new);
+
+    match channel_id {
+        Some(channel_id) => {
+            ctx.say(tr!(locale, "modlog-show", channel = channel_id.mention()))
+                .await?
+        }
+        None => ctx.say(tr!(locale, "modlog-show-off")).await?,
+    };
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+use crate::database::{self, NewPermissionRule};
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::permissions::{Effect, Subject, find_path};
+use crate::settings::Locale;
+use poise::serenity_prelude::{Permissions, Role, User};
+
+/// Allow or deny commands to roles, members or permissions
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "permissions_allow",
+        "permissions_deny",
+        "permissions_list",
+        "permissions_remove"
+    ),
+    subcommand_required,
+    // Like the settings, usable when the admin module is turned off.
+    category = "Settings",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn permissions(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Let a role, member or permission use a command or group
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "allow",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn permissions_allow(
+    ctx: Context<'_>,
+    #[description = "Command or group, e.g. `suggest remove_song` or `admin`"] command: String,
+    #[description = "Role to allow"] role: Option<Role>,
+    #[description = "Member to allow"] user: Option<User>,
+    #[description = "Permission members need, e.g. manage_nicknames"] permission: Option<String>,
+) -> Result<()> {
+    save_rule(ctx, Effect::Allow, command, role, user, permission).await
+}
+
+/// Keep a role, member or permission from using a command or group
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "deny",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn permissions_deny(
+    ctx: Context<'_>,
+    #[description = "Command or group, e.g. `suggest remove_song` or `admin`"] command: String,
+    #[description = "Role to deny"] role: Option<Role>,
+    #[description = "Member to deny"] user: Option<User>,
+    #[description = "Permission members need, e.g. manage_nicknames"] permission: Option<String>,
+) -> Result<()> {
+    save_rule(ctx, Effect::Deny, command, role, user, permission).await
+}
+
+async fn save_rule(
+    ctx: Context<'_>,
+    effect: Effect,
+    command: String,
+    role: Option<Role>,
+    user: Option<User>,
+    permission: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let command = find_path(&ctx.framework().options().commands, &command).ok_or_else(|| {
+        bot_error(tr!(
+            locale,
+            "permissions-unknown-command",
+            command = command.trim()
+        ))
+    })?;
+
+    let subject = match (role, user, permission) {
+        (Some(role), None, None) => Subject::Role(role.id),
+        (None, Some(user), None) => Subject::User(user.id),
+        (None, None, Some(permission)) => {
+            Subject::Permission(parse_permission(locale, &permission)?)
+        }
+        _ => {
+            return Err(bot_error(tr!(locale, "permissions-one-subject")));
+        }
+    };
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        command = %command,
+        subject = ?subject,
+        effect = %effect.as_str(),
+        "Permissions rule command invoked"
+    );
+
+    let rule_id = database::save_permission_rule(
+        &ctx.data().database,
+        &NewPermissionRule {
+            guild_id: &guild_id.to_string(),
+            command: &command,
+            subject_type: subject.kind(),
+            subject_id: &subject.id(),
+            effect: effect.as_str(),
+            created_by_id: &ctx.author().id.to_string(),
+        },
+    )
+    .await?;
+    ctx.data().permissions.invalidate(guild_id);
+
+    let subject = subject.label(locale);
+    let mut response = match effect {
+        Effect::Allow => tr!(
+            locale,
+            "permissions-allowed",
+            id = rule_id,
+            subject = subject,
+            command = command
+        ),
+        Effect::Deny => tr!(
+            locale,
+            "permissions-denied",
+            id = rule_id,
+            subject = subject,
+            command = command
+        ),
+    };
+    // Discord hides top-level commands with default member permissions from everyone else until
+    // the server changes that under Integrations, which the bot can't do.
+    let top_level = command.split(' ').next().unwrap_or_default();
+    if effect == Effect::Allow
+        && ctx
+            .framework()
+            .options()
+            .commands
+            .iter()
+            .any(|c| c.name == top_level && !c.default_member_permissions.is_empty())
+    {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "permissions-integrations",
+            command = top_level
+        ));
+    }
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Parses a permission name such as `manage_messages` or `Manage Messages`.
+fn parse_permission(locale: Locale, name: &str) -> Result<Permissions> {
+    let flag = name.trim().replace([' ', '-'], "_").to_ascii_uppercase();
+
+    Permissions::from_name(&flag)
+        .filter(|permissions| !permissions.is_empty())
+        .ok_or_else(|| {
+            bot_error(tr!(
+                locale,
+                "permissions-unknown-permission",
+                permission = name.trim()
+            ))
+        })
+}
+
+/// List this server's permission rules
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "list",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn permissions_list(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let rules = ctx
+        .data()
+        .permissions
+        .rules(&ctx.data().database, guild_id)
+        .await?;
+
+    if rules.is_empty() {
+        ctx.say(tr!(locale, "permissions-list-empty")).await?;
+        return Ok(());
+    }
+
+    let mut response = tr!(locale, "permissions-list-title", count = rules.len()) + "\n";
+    for rule in rules.iter() {
+        let effect = match rule.effect {
+            Effect::Allow => tr!(locale, "permissions-effect-allow"),
+            Effect::Deny => tr!(locale, "permissions-effect-deny"),
+        };
+        response.push_str(&format!(
+            "\n**#{}** `{}` {} {}",
+            rule.id,
+            rule.command,
+            effect,
+            rule.subject.label(locale)
+        ));
+    }
+
+    if response.len() > 1900 {
+        response = response.chars().take(1900).collect();
+        response.push('\n');
+        response.push_str(&tr!(locale, "response-truncated"));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Remove a permission rule
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "remove",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn permissions_remove(
+    ctx: Context<'_>,
+    #[description = "ID of the rule, see `/admin permissions list`"] rule_id: i64,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let deleted =
+        database::delete_permission_rule(&ctx.data().database, rule_id, &guild_id.to_string())
+            .await?;
+
+    if deleted {
+        ctx.data().permissions.invalidate(guild_id);
+        ctx.say(tr!(locale, "permissions-removed", id = rule_id))
+            .await?;
+    } else {
+        ctx.say(tr!(locale, "permissions-not-found", id = rule_id))
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_permission() {
+        assert_eq!(
+            parse_permission(Locale::default(), "manage_messages").unwrap(),
+            Permissions::MANAGE_MESSAGES
+        );
+        assert_eq!(
+            parse_permission(Locale::default(), " Manage Nicknames ").unwrap(),
+            Permissions::MANAGE_NICKNAMES
+        );
+        assert!(parse_permission(Locale::default(), "fly").is_err());
+    }
+}
@@ -0,0 +1,438 @@
+use crate::audit::{self, AuditAction, AuditOutcome, AuditRecord};
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::pattern::{self, PatternOptions};
+use crate::purge::{self, ChannelReport, PurgeFilter, PurgeProgress};
+use crate::settings::Locale;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{ChannelId, Guild, GuildChannel, Member};
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn purge(
+    ctx: Context<'_>,
+    #[description = "Regex pattern to match messages"] pattern: String,
+    #[description = "Channel to purge (defaults to current)"] channel: Option<ChannelId>,
+    #[description = "Additional channels to purge, as mentions or ids"] channels: Option<String>,
+    #[description = "Purge every readable channel in this category"]
+    #[channel_types("Category")]
+    category: Option<GuildChannel>,
+    #[description = "Purge every text channel the bot can read"] everywhere: Option<bool>,
+    #[description = "Duration in minutes (e.g., 60 for 1 hour)"] duration_minutes: Option<i64>,
+    #[description = "Ignore letter case when matching"] case_insensitive: Option<bool>,
+    #[description = "Make ^ and $ match at line boundaries"] multi_line: Option<bool>,
+    #[description = "Match the pattern as plain text instead of a regex"] literal: Option<bool>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let options = PatternOptions {
+        case_insensitive: case_insensitive.unwrap_or(false),
+        multi_line: multi_line.unwrap_or(false),
+        literal: literal.unwrap_or(false),
+    };
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        user_name = %ctx.author().name,
+        guild_id = ?ctx.guild_id(),
+        pattern = %pattern,
+        channel_id = ?channel,
+        channels = ?channels,
+        category_id = ?category.as_ref().map(|c| c.id),
+        everywhere = ?everywhere,
+        duration_minutes = ?duration_minutes,
+        options = ?options,
+        "Admin purge command invoked"
+    );
+
+    let duration_minutes = duration_minutes.unwrap_or(60);
+
+    if duration_minutes <= 0 || duration_minutes > 10080 {
+        return Err(bot_error(tr!(locale, "purge-duration-range")));
+    }
+
+    let regex = pattern::compile(locale, &pattern, options)?;
+
+    let explicit = match channels.as_deref() {
+        Some(list) => purge::parse_channel_list(list)
+            .map_err(|token| bot_error(tr!(locale, "purge-invalid-channel", token = token)))?,
+        None => Vec::new(),
+    };
+    let everywhere = everywhere.unwrap_or(false);
+    let category = category.map(|c| c.id);
+
+    let (targets, skipped) = match ctx.guild_id() {
+        Some(guild_id) => {
+            let bot_id = ctx.cache().current_user().id;
+            let bot_member = guild_id.member(ctx, bot_id).await?;
+            let invoker = guild_id.member(ctx, ctx.author().id).await?;
+            let guild = ctx
+                .guild()
+                .ok_or_else(|| bot_error(tr!(locale, "error-guild-not-cached")))?;
+
+            let mut requested = explicit;
+            requested.extend(channel);
+            if requested.is_empty() && category.is_none() && !everywhere {
+                requested.push(ctx.channel_id());
+            }
+
+            resolve_targets(
+                locale,
+                &guild,
+                &bot_member,
+                &invoker,
+                ctx.channel_id(),
+                &requested,
+                category,
+                everywhere,
+            )?
+        }
+        // No guild permissions apply in DMs, so only the DM itself can be purged from there.
+        None => {
+            if channel.is_some() || !explicit.is_empty() || category.is_some() || everywhere {
+                return Err(bot_error(tr!(locale, "purge-dm-other-channels")));
+            }
+            (vec![ctx.channel_id()], Vec::new())
+        }
+    };
+
+    if targets.is_empty() && !skipped.is_empty() {
+        return Err(bot_error(tr!(locale, "purge-no-permitted-channels")));
+    }
+    if targets.is_empty() {
+        return Err(bot_error(tr!(locale, "purge-no-channels")));
+    }
+
+    tracing::info!(
+        pattern = %pattern,
+        channel_count = %targets.len(),
+        duration_minutes = %duration_minutes,
+        "Starting message purge operation"
+    );
+
+    let response = if targets.len() > 1 {
+        ctx.say(tr!(locale, "purge-starting-many", count = targets.len()))
+            .await?
+    } else {
+        ctx.say(tr!(locale, "purge-starting")).await?
+    };
+
+    let filter = PurgeFilter {
+        regex: Some(regex),
+        since: Some(Utc::now() - Duration::minutes(duration_minutes)),
+        ..Default::default()
+    };
+
+    let mut reports = Vec::with_capacity(targets.len());
+    for (index, &channel_id) in targets.iter().enumerate() {
+        let label = if targets.len() > 1 {
+            format!("[{}/{}] <#{}> ", index + 1, targets.len(), channel_id)
+        } else {
+            String::new()
+        };
+        let response = &response;
+
+        let report = purge::purge_channel(ctx.http(), channel_id, &filter, |progress| {
+            let status = match progress {
+                PurgeProgress::Scanning { checked, matched } => tr!(
+                    locale,
+                    "purge-scanning",
+                    checked = checked,
+                    matched = matched
+                ),
+                PurgeProgress::Deleting { done: 0, total } => {
+                    tr!(locale, "purge-deleting", total = total)
+                }
+                PurgeProgress::Deleting { done, total } => {
+                    tr!(
+                        locale,
+                        "purge-deleting-progress",
+                        done = done,
+                        total = total
+                    )
+                }
+            };
+            let content = format!("{}{}", label, status);
+
+            async move {
+                if let Err(e) = response
+                    .edit(ctx, poise::CreateReply::default().content(content))
+                    .await
+                {
+                    tracing::warn!(error = %e, "Failed to update purge progress");
+                }
+            }
+        })
+        .await;
+
+        reports.push(report);
+    }
+
+    let final_message = format_report(
+        locale,
+        &reports,
+        &skipped,
+        &pattern,
+        options,
+        duration_minutes,
+    );
+
+    if let Some(guild_id) = ctx.guild_id() {
+        let deleted: usize = reports.iter().map(|r| r.deleted).sum();
+        let failed: usize = reports.iter().map(|r| r.failed).sum();
+        let checked: u32 = reports.iter().map(|r| r.checked).sum();
+        let unreadable = reports.iter().filter(|r| r.error.is_some()).count();
+
+        let outcome = match AuditOutcome::from_counts(deleted as i64, failed as i64) {
+            AuditOutcome::Success if unreadable == reports.len() => AuditOutcome::Failure,
+            AuditOutcome::Success if unreadable > 0 => AuditOutcome::Partial,
+            outcome => outcome,
+        };
+
+        let target = if everywhere {
+            "all channels".to_string()
+        } else {
+            reports
+                .iter()
+                .map(|r| format!("<#{}>", r.channel_id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        audit::record(
+            ctx.http(),
+            &ctx.data().database,
+            AuditRecord::new(
+                guild_id,
+                ctx.author().id,
+                &ctx.author().name,
+                AuditAction::Purge,
+            )
+            .target(target)
+            .parameters(serde_json::json!({
+                "pattern": pattern,
+                "flags": options.describe(),
+                "duration_minutes": duration_minutes,
+                "category_id": category,
+                "everywhere": everywhere,
+                "channels": reports,
+                "skipped_channels": skipped,
+            }))
+            .outcome(outcome)
+            .counts(checked as i64, deleted as i64, failed as i64),
+        )
+        .await;
+    }
+
+    response
+        .edit(ctx, poise::CreateReply::default().content(final_message))
+        .await?;
+
+    tracing::info!(
+        deleted_count = %reports.iter().map(|r| r.deleted).sum::<usize>(),
+        failed_deletes = %reports.iter().map(|r| r.failed).sum::<usize>(),
+        total_checked = %reports.iter().map(|r| r.checked).sum::<u32>(),
+        channel_count = %reports.len(),
+        pattern = %pattern,
+        duration_minutes = %duration_minutes,
+        "Purge operation completed"
+    );
+
+    Ok(())
+}
+
+/// Resolves the purge selection into a deduplicated list of channels in this guild, plus the
+/// channels from `category` or `everywhere` left out because `invoker` can't moderate them.
+///
+/// The command's own permission check only covers `current`, so every other channel needs the
+/// invoker to be able to read it and manage messages there. Channels named explicitly are
+/// refused instead of skipped.
+#[allow(clippy::too_many_arguments)]
+fn resolve_targets(
+    locale: Locale,
+    guild: &Guild,
+    bot_member: &Member,
+    invoker: &Member,
+    current: ChannelId,
+    requested: &[ChannelId],
+    category: Option<ChannelId>,
+    everywhere: bool,
+) -> Result<(Vec<ChannelId>, Vec<ChannelId>)> {
+    let allowed = |channel_id: &ChannelId| {
+        *channel_id == current
+            || guild
+                .channels
+                .get(channel_id)
+                .is_some_and(|channel| purge::can_moderate(guild, channel, invoker))
+    };
+
+    let mut targets: Vec<ChannelId> = Vec::new();
+
+    for channel_id in requested {
+        let channel = guild.channels.get(channel_id).ok_or_else(|| {
+            bot_error(tr!(
+                locale,
+                "purge-channel-elsewhere",
+                channel = format!("<#{}>", channel_id)
+            ))
+        })?;
+
+        if !purge::is_purgeable(channel) {
+            return Err(bot_error(tr!(
+                locale,
+                "purge-not-text-channel",
+                channel = format!("<#{}>", channel_id)
+            )));
+        }
+
+        if !allowed(channel_id) {
+            return Err(bot_error(tr!(
+                locale,
+                "purge-not-permitted",
+                channel = format!("<#{}>", channel_id)
+            )));
+        }
+
+        targets.push(*channel_id);
+    }
+
+    let mut skipped = Vec::new();
+    let mut selected = Vec::new();
+    if let Some(category) = category {
+        selected.extend(purge::category_channels(guild, category, bot_member));
+    }
+    if everywhere {
+        selected.extend(purge::readable_channels(guild, bot_member));
+    }
+    for channel_id in selected {
+        match allowed(&channel_id) {
+            true => targets.push(channel_id),
+            false => skipped.push(channel_id),
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    targets.retain(|channel_id| seen.insert(*channel_id));
+    skipped.retain(|channel_id| seen.insert(*channel_id));
+
+    Ok((targets, skipped))
+}
+
+/// Discord rejects message content longer than 2000 characters.
+const REPORT_LIMIT: usize = 1900;
+
+fn format_report(
+    locale: Locale,
+    reports: &[ChannelReport],
+    skipped: &[ChannelId],
+    pattern: &str,
+    options: PatternOptions,
+    duration_minutes: i64,
+) -> String {
+    let deleted_count: usize = reports.iter().map(|r| r.deleted).sum();
+    let failed_deletes: usize = reports.iter().map(|r| r.failed).sum();
+    let total_checked: u32 = reports.iter().map(|r| r.checked).sum();
+    let unreadable = reports.iter().filter(|r| r.error.is_some()).count();
+
+    let mut message = if deleted_count == 0 && failed_deletes == 0 && unreadable == 0 {
+        tr!(
+            locale,
+            "purge-done-no-matches",
+            checked = total_checked,
+            pattern = pattern
+        )
+    } else if failed_deletes > 0 || unreadable > 0 {
+        tr!(
+            locale,
+            "purge-done-failures",
+            deleted = deleted_count,
+            failed = failed_deletes,
+            checked = total_checked,
+            pattern = pattern,
+            flags = options.label(locale),
+            minutes = duration_minutes
+        )
+    } else {
+        tr!(
+            locale,
+            "purge-done",
+            deleted = deleted_count,
+            checked = total_checked,
+            pattern = pattern,
+            flags = options.label(locale),
+            minutes = duration_minutes
+        )
+    };
+
+    if !skipped.is_empty() {
+        message.push('\n');
+        message.push_str(&tr!(locale, "purge-skipped", count = skipped.len()));
+    }
+
+    if reports.len() == 1 && unreadable == 0 {
+        return message;
+    }
+
+    let line = |report: &ChannelReport| match &report.error {
+        Some(error) => tr!(
+            locale,
+            "purge-channel-unreadable",
+            channel = format!("<#{}>", report.channel_id),
+            error = error
+        ),
+        None => tr!(
+            locale,
+            "purge-channel-line",
+            channel = format!("<#{}>", report.channel_id),
+            checked = report.checked,
+            deleted = report.deleted,
+            failed = report.failed
+        ),
+    };
+
+    let mut lines: Vec<String> = reports.iter().map(line).collect();
+    if message.len() + lines.iter().map(|l| l.len() + 1).sum::<usize>() > REPORT_LIMIT {
+        let quiet: Vec<&ChannelReport> = reports
+            .iter()
+            .filter(|r| r.deleted == 0 && r.failed == 0 && r.error.is_none())
+            .collect();
+
+        lines = reports
+            .iter()
+            .filter(|r| r.deleted > 0 || r.failed > 0 || r.error.is_some())
+            .map(line)
+            .collect();
+
+        if !quiet.is_empty() {
+            lines.push(tr!(
+                locale,
+                "purge-channels-quiet",
+                count = quiet.len(),
+                checked = quiet.iter().map(|r| r.checked).sum::<u32>()
+            ));
+        }
+    }
+
+    message.push('\n');
+    message.push_str(&tr!(locale, "purge-channels"));
+    for (index, line) in lines.iter().enumerate() {
+        if message.len() + line.len() + 1 > REPORT_LIMIT {
+            message.push('\n');
+            message.push_str(&tr!(
+                locale,
+                "purge-channels-more",
+                count = lines.len() - index
+            ));
+            break;
+        }
+        message.push('\n');
+        message.push_str(line);
+    }
+
+    message
+}
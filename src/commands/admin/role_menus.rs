@@ -0,0 +1,502 @@
+use crate::database::{self, NewRoleMenu, NewRoleMenuOption, RoleMenu};
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::moderation::Hierarchy;
+use crate::role_menus::{self, MAX_OPTIONS, RoleMenuStyle};
+use crate::scheduler::parse_id;
+use crate::settings::Locale;
+use poise::serenity_prelude::{ChannelId, GuildChannel, Mentionable, MessageId, Role};
+
+/// Longest menu title, in characters.
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// Discord's limit for button labels and select option labels.
+const MAX_LABEL_LENGTH: usize = 80;
+
+/// Discord's limit for select option descriptions.
+const MAX_OPTION_DESCRIPTION_LENGTH: usize = 100;
+
+/// Manage self-assignable role menus
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "rolemenu_create",
+        "rolemenu_add",
+        "rolemenu_remove",
+        "rolemenu_publish",
+        "rolemenu_list",
+        "rolemenu_delete"
+    ),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Create a role menu, then add roles to it and publish it
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "create",
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu_create(
+    ctx: Context<'_>,
+    #[description = "Title shown above the menu"] title: String,
+    #[description = "Buttons or a select menu"] style: RoleMenuStyle,
+    #[description = "Text shown above the roles"] description: Option<String>,
+    #[description = "Members can hold only one role from this menu"] exclusive: Option<bool>,
+    #[description = "Most roles a member can hold from this menu"] max_selections: Option<i64>,
+    #[description = "Role members need before they can use the menu"] required_role: Option<Role>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        title = %title,
+        style = %style.as_str(),
+        "Role menu create command invoked"
+    );
+
+    let title = title.trim();
+    if title.is_empty() || title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(bot_error(tr!(
+            locale,
+            "rolemenu-title-length",
+            max = MAX_TITLE_LENGTH
+        )));
+    }
+
+    let exclusive = exclusive.unwrap_or(false);
+    if let Some(max) = max_selections {
+        if exclusive {
+            return Err(bot_error(tr!(locale, "rolemenu-exclusive-max")));
+        }
+        if !(1..=MAX_OPTIONS as i64).contains(&max) {
+            return Err(bot_error(tr!(
+                locale,
+                "error-option-range",
+                option = "max_selections",
+                min = 1,
+                max = MAX_OPTIONS
+            )));
+        }
+    }
+
+    let description = description.filter(|d| !d.trim().is_empty());
+    let required_role_id = required_role.map(|role| role.id.to_string());
+
+    let menu_id = database::save_role_menu(
+        &ctx.data().database,
+        &NewRoleMenu {
+            guild_id: &guild_id.to_string(),
+            title,
+            description: description.as_deref(),
+            style: style.as_str(),
+            exclusive,
+            max_selections,
+            required_role_id: required_role_id.as_deref(),
+            created_by_id: &ctx.author().id.to_string(),
+        },
+    )
+    .await?;
+
+    ctx.say(tr!(locale, "rolemenu-created", id = menu_id))
+        .await?;
+
+    Ok(())
+}
+
+/// Add a role to a menu, or update how it is shown
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "add",
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    required_bot_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu_add(
+    ctx: Context<'_>,
+    #[description = "ID of the menu"] menu_id: i64,
+    #[description = "Role members can pick"] role: Role,
+    #[description = "Button or option text (defaults to the role name)"] label: Option<String>,
+    #[description = "Emoji shown next to the role"] emoji: Option<String>,
+    #[description = "Short explanation of the role"] description: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        menu_id = %menu_id,
+        role_id = %role.id,
+        "Role menu add command invoked"
+    );
+
+    let menu = load_menu(ctx, locale, menu_id).await?;
+
+    if role.guild_id != guild_id {
+        return Err(bot_error(tr!(locale, "rolemenu-role-elsewhere")));
+    }
+
+    let hierarchy = ctx
+        .guild()
+        .map(|guild| Hierarchy::new(&guild))
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-not-cached")))?;
+    let bot_id = ctx.cache().current_user().id;
+    let bot = guild_id.member(ctx, bot_id).await?;
+    role_menus::check_role(&role, &hierarchy, &bot).map_err(|id| bot_error(tr!(locale, id)))?;
+
+    let moderator = ctx
+        .author_member()
+        .await
+        .ok_or_else(|| bot_error(tr!(locale, "error-membership-unavailable")))?;
+    if !hierarchy.outranks_role(moderator.user.id, &moderator.roles, role.id) {
+        return Err(bot_error(tr!(locale, "rolemenu-role-above-you")));
+    }
+
+    let label = label
+        .as_deref()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .unwrap_or(&role.name);
+    if label.chars().count() > MAX_LABEL_LENGTH {
+        return Err(bot_error(tr!(
+            locale,
+            "rolemenu-label-too-long",
+            max = MAX_LABEL_LENGTH
+        )));
+    }
+
+    let emoji = emoji.as_deref().map(str::trim).filter(|e| !e.is_empty());
+    if let Some(emoji) = emoji
+        && role_menus::parse_emoji(emoji).is_none()
+    {
+        return Err(bot_error(tr!(
+            locale,
+            "rolemenu-invalid-emoji",
+            emoji = emoji
+        )));
+    }
+
+    let description = description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty());
+    if description.is_some_and(|d| d.chars().count() > MAX_OPTION_DESCRIPTION_LENGTH) {
+        return Err(bot_error(tr!(
+            locale,
+            "rolemenu-description-too-long",
+            max = MAX_OPTION_DESCRIPTION_LENGTH
+        )));
+    }
+
+    let options = database::get_role_menu_options(&ctx.data().database, menu.id).await?;
+    let role_key = role.id.to_string();
+    if options.len() >= MAX_OPTIONS && !options.iter().any(|o| o.role_id == role_key) {
+        return Err(bot_error(tr!(
+            locale,
+            "rolemenu-too-many-roles",
+            max = MAX_OPTIONS
+        )));
+    }
+
+    database::save_role_menu_option(
+        &ctx.data().database,
+        &NewRoleMenuOption {
+            menu_id: menu.id,
+            role_id: &role_key,
+            label,
+            emoji,
+            description,
+        },
+    )
+    .await?;
+
+    let mut response = tr!(
+        locale,
+        "rolemenu-role-added",
+        role = role.id.mention(),
+        id = menu.id
+    );
+    response.push('\n');
+    response.push_str(&refresh_note(ctx, locale, &menu).await);
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Remove a role from a menu
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "remove",
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu_remove(
+    ctx: Context<'_>,
+    #[description = "ID of the menu"] menu_id: i64,
+    #[description = "Role to remove"] role: Role,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let menu = load_menu(ctx, locale, menu_id).await?;
+
+    let removed =
+        database::delete_role_menu_option(&ctx.data().database, menu.id, &role.id.to_string())
+            .await?;
+    if !removed {
+        return Err(bot_error(tr!(
+            locale,
+            "rolemenu-role-not-in-menu",
+            role = role.id.mention(),
+            id = menu.id
+        )));
+    }
+
+    let mut response = tr!(
+        locale,
+        "rolemenu-role-removed",
+        role = role.id.mention(),
+        id = menu.id
+    );
+    response.push('\n');
+    response.push_str(&refresh_note(ctx, locale, &menu).await);
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Post a role menu, or move it to another channel
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "publish",
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu_publish(
+    ctx: Context<'_>,
+    #[description = "ID of the menu"] menu_id: i64,
+    #[description = "Channel to post in (defaults to current)"]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let menu = load_menu(ctx, locale, menu_id).await?;
+
+    let channel_id = match channel {
+        Some(channel) if channel.guild_id != guild_id => {
+            return Err(bot_error(tr!(locale, "settings-channel-elsewhere")));
+        }
+        Some(channel) => channel.id,
+        None => ctx.channel_id(),
+    };
+
+    let options = database::get_role_menu_options(&ctx.data().database, menu.id).await?;
+    if options.is_empty() {
+        return Err(bot_error(tr!(locale, "rolemenu-empty")));
+    }
+
+    let previous = published_message(&menu).filter(|(channel, _)| *channel != channel_id);
+
+    let guild_locale = i18n::guild_locale(ctx.data(), guild_id).await;
+    let message_id = role_menus::publish(
+        ctx.http(),
+        &ctx.data().database,
+        guild_locale,
+        &menu,
+        &options,
+        channel_id,
+    )
+    .await
+    .map_err(|e| {
+        tracing::warn!(menu_id = %menu.id, error = %e, "Failed to publish role menu");
+        bot_error(tr!(
+            locale,
+            "error-cannot-post",
+            channel = channel_id.mention()
+        ))
+    })?;
+
+    // The menu moved, so the old copy would show stale roles.
+    if let Some((channel, message)) = previous
+        && let Err(e) = channel.delete_message(ctx.http(), message).await
+    {
+        tracing::info!(menu_id = %menu.id, error = %e, "Failed to delete previous role menu message");
+    }
+
+    ctx.say(tr!(
+        locale,
+        "rolemenu-published",
+        id = menu.id,
+        link = message_id.link(channel_id, Some(guild_id))
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List the role menus of this server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "list",
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu_list(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let menus = database::get_role_menus(&ctx.data().database, &guild_id.to_string()).await?;
+
+    if menus.is_empty() {
+        ctx.say(tr!(locale, "rolemenu-list-empty")).await?;
+        return Ok(());
+    }
+
+    let mut response = tr!(locale, "rolemenu-list-title", count = menus.len()) + "\n\n";
+    for menu in &menus {
+        let options = database::get_role_menu_options(&ctx.data().database, menu.id).await?;
+        response.push_str(&tr!(
+            locale,
+            "rolemenu-list-entry",
+            id = menu.id,
+            title = menu.title,
+            style = menu.style,
+            count = options.len()
+        ));
+        if menu.exclusive {
+            response.push_str(&tr!(locale, "rolemenu-list-pick-one"));
+        } else if let Some(max) = menu.max_selections {
+            response.push_str(&tr!(locale, "rolemenu-list-up-to", max = max));
+        }
+        if let Some(required) = &menu.required_role_id {
+            response.push_str(&tr!(
+                locale,
+                "rolemenu-list-requires",
+                role = format!("<@&{}>", required)
+            ));
+        }
+        response.push('\n');
+        match published_message(menu) {
+            Some((channel, _)) => response.push_str(&tr!(
+                locale,
+                "rolemenu-list-posted",
+                channel = channel.mention()
+            )),
+            None => response.push_str(&tr!(locale, "rolemenu-list-unpublished")),
+        }
+        response.push_str("\n\n");
+    }
+
+    if response.len() > 1900 {
+        response = response.chars().take(1900).collect();
+        response.push('\n');
+        response.push_str(&tr!(locale, "response-truncated"));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Delete a role menu and its message
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "delete",
+    guild_only,
+    required_permissions = "MANAGE_ROLES",
+    default_member_permissions = "MANAGE_ROLES"
+)]
+pub async fn rolemenu_delete(
+    ctx: Context<'_>,
+    #[description = "ID of the menu"] menu_id: i64,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+    let menu = load_menu(ctx, locale, menu_id).await?;
+
+    if let Some((channel, message)) = published_message(&menu)
+        && let Err(e) = channel.delete_message(ctx.http(), message).await
+    {
+        tracing::info!(menu_id = %menu.id, error = %e, "Failed to delete role menu message");
+    }
+
+    database::delete_role_menu(&ctx.data().database, menu.id, &guild_id.to_string()).await?;
+
+    ctx.say(tr!(locale, "rolemenu-deleted", id = menu.id))
+        .await?;
+
+    Ok(())
+}
+
+async fn load_menu(ctx: Context<'_>, locale: Locale, menu_id: i64) -> Result<RoleMenu> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    database::get_role_menu(&ctx.data().database, menu_id, &guild_id.to_string())
+        .await?
+        .ok_or_else(|| bot_error(tr!(locale, "rolemenu-not-found")))
+}
+
+fn published_message(menu: &RoleMenu) -> Option<(ChannelId, MessageId)> {
+    let channel = menu.channel_id.as_deref().and_then(parse_id)?;
+    let message = menu.message_id.as_deref().and_then(parse_id)?;
+
+    Some((ChannelId::new(channel), MessageId::new(message)))
+}
+
+/// Updates the posted copy of `menu` and describes the result for the moderator.
+async fn refresh_note(ctx: Context<'_>, locale: Locale, menu: &RoleMenu) -> String {
+    if published_message(menu).is_none() {
+        return tr!(locale, "rolemenu-not-published");
+    }
+
+    let guild_locale = match ctx.guild_id() {
+        Some(guild_id) => i18n::guild_locale(ctx.data(), guild_id).await,
+        None => Locale::default(),
+    };
+    match role_menus::refresh(ctx.http(), &ctx.data().database, guild_locale, menu).await {
+        Ok(()) => tr!(locale, "rolemenu-refreshed"),
+        Err(e) => {
+            tracing::warn!(menu_id = %menu.id, error = %e, "Failed to refresh role menu");
+            tr!(locale, "rolemenu-refresh-failed")
+        }
+    }
+}
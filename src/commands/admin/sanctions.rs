@@ -0,0 +1,474 @@
+use crate::audit::{self, AuditAction, AuditOutcome, AuditRecord};
+use crate::database::{self, NewTemporaryBan};
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::moderation::{self, Hierarchy, MAX_TIMEOUT_DAYS, Sanction};
+use crate::settings::Locale;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{Mentionable, User};
+
+const MAX_TEMPBAN_DAYS: i64 = 365;
+
+/// Time out a member
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MODERATE_MEMBERS",
+    required_bot_permissions = "MODERATE_MEMBERS",
+    default_member_permissions = "MODERATE_MEMBERS"
+)]
+pub async fn timeout(
+    ctx: Context<'_>,
+    #[description = "Member to time out"] user: User,
+    #[description = "How long (e.g. 10m, 1h, 7d)"] duration: String,
+    #[description = "Why they are being timed out"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let length = duration::parse(&duration)
+        .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = duration)))?;
+
+    if length < Duration::minutes(1) || length > Duration::days(MAX_TIMEOUT_DAYS) {
+        return Err(bot_error(tr!(
+            locale,
+            "error-timeout-range",
+            max_days = MAX_TIMEOUT_DAYS
+        )));
+    }
+
+    sanction_member(ctx, locale, &user, Sanction::Timeout(length), None, reason).await
+}
+
+/// Kick a member from the server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "KICK_MEMBERS",
+    required_bot_permissions = "KICK_MEMBERS",
+    default_member_permissions = "KICK_MEMBERS"
+)]
+pub async fn kick(
+    ctx: Context<'_>,
+    #[description = "Member to kick"] user: User,
+    #[description = "Why they are being kicked"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    sanction_member(ctx, locale, &user, Sanction::Kick, None, reason).await
+}
+
+/// Ban a user from the server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "BAN_MEMBERS",
+    required_bot_permissions = "BAN_MEMBERS",
+    default_member_permissions = "BAN_MEMBERS"
+)]
+pub async fn ban(
+    ctx: Context<'_>,
+    #[description = "User to ban"] user: User,
+    #[description = "Days of their messages to delete (0-7)"] delete_message_days: Option<u8>,
+    #[description = "Why they are being banned"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let delete_message_days = delete_message_days.unwrap_or(0);
+
+    if delete_message_days > 7 {
+        return Err(bot_error(tr!(locale, "sanction-delete-days-range")));
+    }
+
+    sanction_member(
+        ctx,
+        locale,
+        &user,
+        Sanction::Ban {
+            delete_message_days,
+        },
+        None,
+        reason,
+    )
+    .await
+}
+
+/// Ban a user for a limited time
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "BAN_MEMBERS",
+    required_bot_permissions = "BAN_MEMBERS",
+    default_member_permissions = "BAN_MEMBERS"
+)]
+pub async fn tempban(
+    ctx: Context<'_>,
+    #[description = "User to ban"] user: User,
+    #[description = "How long (e.g. 12h, 7d, 2w)"] duration: String,
+    #[description = "Days of their messages to delete (0-7)"] delete_message_days: Option<u8>,
+    #[description = "Why they are being banned"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let length = duration::parse(&duration)
+        .ok_or_else(|| bot_error(tr!(locale, "error-invalid-duration", input = duration)))?;
+
+    if length < Duration::minutes(1) || length > Duration::days(MAX_TEMPBAN_DAYS) {
+        return Err(bot_error(tr!(
+            locale,
+            "sanction-tempban-range",
+            max_days = MAX_TEMPBAN_DAYS
+        )));
+    }
+
+    let delete_message_days = delete_message_days.unwrap_or(0);
+
+    if delete_message_days > 7 {
+        return Err(bot_error(tr!(locale, "sanction-delete-days-range")));
+    }
+
+    sanction_member(
+        ctx,
+        locale,
+        &user,
+        Sanction::Ban {
+            delete_message_days,
+        },
+        Some(length),
+        reason,
+    )
+    .await
+}
+
+/// Lift the ban of a user
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "BAN_MEMBERS",
+    required_bot_permissions = "BAN_MEMBERS",
+    default_member_permissions = "BAN_MEMBERS"
+)]
+pub async fn unban(
+    ctx: Context<'_>,
+    #[description = "User to unban (mention or id)"] user: User,
+    #[description = "Why the ban is being lifted"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let reason = normalize_reason(locale, reason);
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        target_id = %user.id,
+        "Unban command invoked"
+    );
+
+    let audit_reason =
+        moderation::truncate_reason(&format!("{} (by {})", reason, ctx.author().name));
+    let result = moderation::lift_ban(ctx.http(), guild_id, user.id, &audit_reason).await;
+
+    if matches!(result, Ok(false)) {
+        return Err(bot_error(tr!(
+            locale,
+            "sanction-not-banned",
+            user = user.name
+        )));
+    }
+
+    let error = result.as_ref().err().map(|e| e.to_string());
+
+    let recorded = audit::record(
+        ctx.http(),
+        &ctx.data().database,
+        AuditRecord::new(
+            guild_id,
+            ctx.author().id,
+            &ctx.author().name,
+            AuditAction::Unban,
+        )
+        .target(format!("<@{}>", user.id))
+        .parameters(serde_json::json!({
+            "reason": reason,
+            "error": error,
+        }))
+        .outcome(match error {
+            Some(_) => AuditOutcome::Failure,
+            None => AuditOutcome::Success,
+        })
+        .counts(0, error.is_none() as i64, error.is_some() as i64),
+    )
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(guild_id = %guild_id, target_id = %user.id, error = %e, "Unban failed");
+        return Err(bot_error(tr!(
+            locale,
+            "sanction-unban-failed",
+            user = user.name,
+            error = e
+        )));
+    }
+
+    if let Err(e) = database::cancel_temporary_bans(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        &user.id.to_string(),
+    )
+    .await
+    {
+        tracing::error!(guild_id = %guild_id, target_id = %user.id, error = %e, "Failed to cancel temporary ban");
+    }
+
+    let mut response = tr!(
+        locale,
+        "sanction-unbanned",
+        user = user.name,
+        mention = user.id.mention(),
+        reason = reason
+    );
+    if let Some(case_number) = recorded.and_then(|r| r.case_number) {
+        response.push('\n');
+        response.push_str(&tr!(locale, "sanction-case", number = case_number));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+pub(super) fn normalize_reason(locale: Locale, reason: Option<String>) -> String {
+    reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| tr!(locale, "sanction-no-reason"))
+}
+
+/// Checks the role hierarchy, notifies the target and applies `sanction`, recording the result.
+///
+/// Bans with `expires_in` are stored so the scheduler can lift them, even across restarts.
+async fn sanction_member(
+    ctx: Context<'_>,
+    locale: Locale,
+    user: &User,
+    sanction: Sanction,
+    expires_in: Option<Duration>,
+    reason: Option<String>,
+) -> Result<()> {
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let reason = normalize_reason(locale, reason);
+    let label = sanction.label(locale);
+    let bot_id = ctx.cache().current_user().id;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        target_id = %user.id,
+        sanction = %sanction,
+        "Sanction command invoked"
+    );
+
+    if user.id == ctx.author().id {
+        return Err(bot_error(tr!(locale, "sanction-self", sanction = label)));
+    }
+    if user.id == bot_id {
+        return Err(bot_error(tr!(locale, "sanction-bot", sanction = label)));
+    }
+
+    let (hierarchy, guild_name) = ctx
+        .guild()
+        .map(|guild| (Hierarchy::new(&guild), guild.name.clone()))
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-not-cached")))?;
+
+    // Bans may target users who already left, everything else needs a current member. Any other
+    // failure to load the member must not skip the hierarchy check.
+    let target = match guild_id.member(ctx, user.id).await {
+        Ok(member) => Some(member),
+        Err(e) if moderation::is_unknown_member(&e) => match sanction {
+            Sanction::Ban { .. } => None,
+            _ => return Err(bot_error(tr!(locale, "sanction-not-member"))),
+        },
+        Err(e) => return Err(e.into()),
+    };
+
+    if let Some(target) = &target {
+        let moderator = ctx
+            .author_member()
+            .await
+            .ok_or_else(|| bot_error(tr!(locale, "error-membership-unavailable")))?;
+
+        if !hierarchy.outranks(
+            moderator.user.id,
+            &moderator.roles,
+            target.user.id,
+            &target.roles,
+        ) {
+            return Err(bot_error(tr!(
+                locale,
+                "sanction-outranked-you",
+                sanction = label
+            )));
+        }
+
+        let bot = guild_id.member(ctx, bot_id).await?;
+        if !hierarchy.outranks(bot.user.id, &bot.roles, target.user.id, &target.roles) {
+            return Err(bot_error(tr!(
+                locale,
+                "sanction-outranked-bot",
+                sanction = label
+            )));
+        }
+    }
+
+    // DM first: after a kick or ban the bot no longer shares a server with the target.
+    let notified = match &target {
+        Some(_) => {
+            let target_locale = i18n::user_locale(ctx.data(), user.id, Some(guild_id), None).await;
+            moderation::notify_target(
+                ctx,
+                target_locale,
+                user,
+                &guild_name,
+                sanction,
+                expires_in,
+                &reason,
+            )
+            .await
+        }
+        None => false,
+    };
+
+    let audit_reason = format!("{} (by {})", reason, ctx.author().name);
+    let result =
+        moderation::apply_sanction(ctx.http(), guild_id, user.id, sanction, &audit_reason).await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+
+    let expires_at = expires_in.map(|length| Utc::now() + length);
+    let mut expiry_saved = true;
+
+    if let (None, Some(expires_at)) = (&error, expires_at) {
+        let saved = database::save_temporary_ban(
+            &ctx.data().database,
+            &NewTemporaryBan {
+                guild_id: &guild_id.to_string(),
+                user_id: &user.id.to_string(),
+                user_name: &user.name,
+                moderator_id: &ctx.author().id.to_string(),
+                moderator_name: &ctx.author().name,
+                reason: &reason,
+                expires_at,
+            },
+        )
+        .await;
+
+        if let Err(e) = saved {
+            tracing::error!(guild_id = %guild_id, target_id = %user.id, error = %e, "Failed to save temporary ban");
+            expiry_saved = false;
+        }
+    }
+
+    let recorded = audit::record(
+        ctx.http(),
+        &ctx.data().database,
+        AuditRecord::new(
+            guild_id,
+            ctx.author().id,
+            &ctx.author().name,
+            sanction.audit_action(),
+        )
+        .target(format!("<@{}>", user.id))
+        .parameters(serde_json::json!({
+            "reason": reason,
+            "duration_minutes": match sanction {
+                Sanction::Timeout(duration) => Some(duration.num_minutes()),
+                _ => None,
+            },
+            "delete_message_days": match sanction {
+                Sanction::Ban { delete_message_days } => Some(delete_message_days),
+                _ => None,
+            },
+            "expires_at": expires_at.map(|t| t.to_rfc3339()),
+            "notified": notified,
+            "error": error,
+        }))
+        .outcome(match error {
+            Some(_) => AuditOutcome::Failure,
+            None => AuditOutcome::Success,
+        })
+        .counts(0, error.is_none() as i64, error.is_some() as i64),
+    )
+    .await;
+
+    if let Some(error) = error {
+        tracing::warn!(
+            guild_id = %guild_id,
+            target_id = %user.id,
+            sanction = %sanction,
+            error = %error,
+            "Sanction failed"
+        );
+        return Err(bot_error(tr!(
+            locale,
+            "sanction-failed",
+            sanction = label,
+            error = error
+        )));
+    }
+
+    let mut response = tr!(
+        locale,
+        "sanction-applied",
+        sanction = label,
+        user = user.name,
+        mention = user.id.mention(),
+        reason = reason
+    );
+    if let Some(case_number) = recorded.and_then(|r| r.case_number) {
+        response.push('\n');
+        response.push_str(&tr!(locale, "sanction-case", number = case_number));
+    }
+    if let Some(expires_at) = expires_at {
+        response.push('\n');
+        response.push_str(&tr!(
+            locale,
+            "sanction-expires",
+            time = format!("<t:{}:R>", expires_at.timestamp())
+        ));
+        if !expiry_saved {
+            response.push('\n');
+            response.push_str(&tr!(locale, "sanction-expiry-not-saved"));
+        }
+    }
+    if target.is_some() && !notified {
+        response.push('\n');
+        response.push_str(&tr!(locale, "sanction-not-notified"));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
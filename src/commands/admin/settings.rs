@@ -0,0 +1,280 @@
+use crate::database;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings::{self, Locale, Module};
+use poise::ChoiceParameter;
+use poise::serenity_prelude::GuildChannel;
+
+/// View and change this server's bot settings
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands(
+        "settings_show",
+        "settings_prefix",
+        "settings_locale",
+        "settings_suggestions",
+        "settings_module"
+    ),
+    subcommand_required,
+    // Its own category keeps these commands usable when the admin module is turned off.
+    category = "Settings",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn settings(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Show this server's settings
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "show",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn settings_show(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let settings = ctx
+        .data()
+        .settings
+        .get(&ctx.data().database, guild_id)
+        .await?;
+
+    let channel = |id: &Option<String>| match id {
+        Some(id) => format!("<#{}>", id),
+        None => tr!(locale, "settings-not-set"),
+    };
+    let disabled = settings::parse_modules(&settings.disabled_modules);
+    let modules = Module::ALL
+        .iter()
+        .map(|module| match disabled.contains(module) {
+            true => format!("~~{}~~", module),
+            false => module.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let prefix = match &settings.prefix {
+        Some(prefix) => format!("`{}`", prefix),
+        None => format!(
+            "`{}` ({})",
+            ctx.data().config.get().discord.prefix,
+            tr!(locale, "settings-default")
+        ),
+    };
+
+    let response = tr!(
+        locale,
+        "settings-show",
+        prefix = prefix,
+        language = settings
+            .locale
+            .as_deref()
+            .and_then(Locale::from_code)
+            .map_or_else(|| tr!(locale, "settings-default"), |l| l.name().to_string()),
+        suggestion_channel = channel(&settings.suggestion_channel_id),
+        mod_log_channel = channel(&settings.mod_log_channel_id),
+        message_log_channel = channel(&settings.message_log_channel_id),
+        modules = modules
+    );
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Change the prefix of text commands
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "prefix",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn settings_prefix(
+    ctx: Context<'_>,
+    #[description = "New prefix, leave empty to go back to the default"] prefix: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        prefix = ?prefix,
+        "Settings prefix command invoked"
+    );
+
+    let prefix = prefix.as_deref().map(str::trim);
+    if let Some(prefix) = prefix {
+        settings::validate_prefix(locale, prefix).map_err(bot_error)?;
+    }
+
+    database::set_guild_prefix(&ctx.data().database, &guild_id.to_string(), prefix).await?;
+    ctx.data().settings.invalidate(guild_id);
+
+    let default = ctx.data().config.get();
+    let prefix = prefix.unwrap_or(&default.discord.prefix);
+    ctx.say(tr!(locale, "settings-prefix-set", prefix = prefix))
+        .await?;
+
+    Ok(())
+}
+
+/// Change the language the bot answers in
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "locale",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn settings_locale(
+    ctx: Context<'_>,
+    #[description = "Language, leave empty to go back to the default"] locale: Option<Locale>,
+) -> Result<()> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Err(bot_error(tr!(i18n::locale(ctx).await, "error-guild-only")));
+    };
+
+    database::set_guild_locale(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        locale.map(|l| l.code()),
+    )
+    .await?;
+    ctx.data().settings.invalidate(guild_id);
+
+    // Answer in the new language, unless the member picked their own.
+    let response = match locale {
+        Some(locale) => tr!(
+            i18n::locale(ctx).await,
+            "settings-locale-set",
+            language = locale.name()
+        ),
+        None => tr!(i18n::locale(ctx).await, "settings-locale-reset"),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Set the channel where new suggestions are announced
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "suggestions",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn settings_suggestions(
+    ctx: Context<'_>,
+    #[description = "Channel to announce suggestions in, leave empty to stop"]
+    #[channel_types("Text", "News")]
+    channel: Option<GuildChannel>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    if let Some(channel) = &channel
+        && channel.guild_id != guild_id
+    {
+        return Err(bot_error(tr!(locale, "settings-channel-elsewhere")));
+    }
+
+    database::set_suggestion_channel(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        channel.as_ref().map(|c| c.id.to_string()).as_deref(),
+    )
+    .await?;
+    ctx.data().settings.invalidate(guild_id);
+
+    let response = match channel {
+        Some(channel) => tr!(
+            locale,
+            "settings-suggestions-set",
+            channel = format!("<#{}>", channel.id)
+        ),
+        None => tr!(locale, "settings-suggestions-off"),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Turn a group of commands on or off in this server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "module",
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    default_member_permissions = "MANAGE_GUILD"
+)]
+pub async fn settings_module(
+    ctx: Context<'_>,
+    #[description = "Module to change"] module: Module,
+    #[description = "Whether its commands can be used"] enabled: bool,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        module = %module,
+        enabled = %enabled,
+        "Settings module command invoked"
+    );
+
+    let current = ctx
+        .data()
+        .settings
+        .get(&ctx.data().database, guild_id)
+        .await?;
+    let mut disabled = settings::parse_modules(&current.disabled_modules);
+    match enabled {
+        true => disabled.remove(&module),
+        false => disabled.insert(module),
+    };
+
+    database::set_disabled_modules(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        &settings::format_modules(&disabled),
+    )
+    .await?;
+    ctx.data().settings.invalidate(guild_id);
+
+    let response = match enabled {
+        true => tr!(locale, "settings-module-on", module = module),
+        false => tr!(locale, "settings-module-off", module = module),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
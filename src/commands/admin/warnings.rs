@@ -0,0 +1,390 @@
+use crate::audit::{self, AuditAction, AuditRecord};
+use crate::database;
+use crate::duration;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::moderation::{self, EscalationAction, MAX_TIMEOUT_DAYS, Moderator, Sanction};
+use chrono::Duration;
+use poise::serenity_prelude::{Mentionable, User};
+
+/// Warn a member, applying any configured escalation
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn warn(
+    ctx: Context<'_>,
+    #[description = "Member to warn"] user: User,
+    #[description = "Why they are being warned"]
+    #[rest]
+    reason: String,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = %guild_id,
+        target_id = %user.id,
+        "Warn command invoked"
+    );
+
+    if user.bot {
+        return Err(bot_error(tr!(locale, "warn-bot")));
+    }
+    if user.id == ctx.author().id {
+        return Err(bot_error(tr!(locale, "warn-self")));
+    }
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(bot_error(tr!(locale, "error-reason-empty")));
+    }
+
+    let escalator = {
+        let bot = ctx.cache().current_user();
+        Moderator::new(bot.id, format!("{} (escalation)", bot.name))
+    };
+
+    let outcome = moderation::issue_warning(
+        ctx.http(),
+        &ctx.data().database,
+        guild_id,
+        &user,
+        &Moderator::from(ctx.author()),
+        &escalator,
+        reason,
+    )
+    .await?;
+
+    let mut response = tr!(
+        locale,
+        "warn-issued",
+        id = outcome.warning_id,
+        user = user.id.mention(),
+        reason = reason,
+        active = outcome.active_warnings
+    );
+    if let Some(case_number) = outcome.case_number {
+        response.push('\n');
+        response.push_str(&tr!(locale, "sanction-case", number = case_number));
+    }
+
+    if let Some(escalation) = &outcome.escalation {
+        let sanction = escalation.sanction.label(locale);
+        response.push('\n');
+        response.push_str(&match &escalation.error {
+            None => tr!(
+                locale,
+                "warn-escalated",
+                sanction = sanction,
+                threshold = escalation.threshold
+            ),
+            Some(error) => tr!(
+                locale,
+                "warn-escalation-failed",
+                sanction = sanction,
+                error = error
+            ),
+        });
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// List the warnings of a member
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn warnings(
+    ctx: Context<'_>,
+    #[description = "Member to look up"] user: User,
+    #[description = "Include revoked warnings"] include_revoked: Option<bool>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let warnings = database::get_warnings(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        &user.id.to_string(),
+        include_revoked.unwrap_or(false),
+    )
+    .await?;
+
+    if warnings.is_empty() {
+        ctx.say(tr!(locale, "warnings-none", user = user.id.mention()))
+            .await?;
+        return Ok(());
+    }
+
+    let active = warnings.iter().filter(|w| w.active).count();
+    let mut response = tr!(locale, "warnings-title", user = user.name, active = active) + "\n\n";
+
+    for warning in warnings.iter().take(15) {
+        let id = match warning.active {
+            true => "warnings-entry",
+            false => "warnings-entry-revoked",
+        };
+        response.push_str(&tr!(
+            locale,
+            id,
+            id = warning.id,
+            date = warning.created_at.format("%Y-%m-%d %H:%M UTC"),
+            moderator = warning.moderator_name,
+            reason = warning.reason.chars().take(200).collect::<String>()
+        ));
+        response.push('\n');
+    }
+
+    if warnings.len() > 15 {
+        response.push_str(&tr!(locale, "warnings-more", count = warnings.len() - 15));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Revoke a warning by its ID
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn unwarn(
+    ctx: Context<'_>,
+    #[description = "ID of the warning"] warning_id: i64,
+    #[description = "Why the warning is being revoked"]
+    #[rest]
+    reason: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let revoked = database::revoke_warning(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        warning_id,
+        &ctx.author().id.to_string(),
+    )
+    .await?;
+
+    let Some(warning) = revoked else {
+        ctx.say(tr!(locale, "unwarn-not-found")).await?;
+        return Ok(());
+    };
+
+    audit::record(
+        ctx.http(),
+        &ctx.data().database,
+        AuditRecord::new(
+            guild_id,
+            ctx.author().id,
+            &ctx.author().name,
+            AuditAction::Unwarn,
+        )
+        .target(format!("<@{}>", warning.user_id))
+        .parameters(serde_json::json!({
+            "warning_id": warning.id,
+            "warning_reason": warning.reason,
+            "reason": reason,
+        }))
+        .counts(0, 1, 0),
+    )
+    .await;
+
+    ctx.say(tr!(
+        locale,
+        "unwarn-revoked",
+        id = warning.id,
+        user = format!("<@{}>", warning.user_id)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Configure automatic punishments for warning counts
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands("escalation_set", "escalation_list", "escalation_remove"),
+    subcommand_required,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn escalation(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Set the punishment applied when a member reaches a warning count
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "set",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn escalation_set(
+    ctx: Context<'_>,
+    #[description = "Number of active warnings"] threshold: i64,
+    #[description = "Punishment to apply"] action: EscalationAction,
+    #[description = "Timeout length (e.g. 1h, 1d), timeouts only"] duration: Option<String>,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    if !(1..=100).contains(&threshold) {
+        return Err(bot_error(tr!(
+            locale,
+            "error-option-range",
+            option = "threshold",
+            min = 1,
+            max = 100
+        )));
+    }
+
+    let duration_minutes = match (action, duration.as_deref()) {
+        (EscalationAction::Timeout, input) => {
+            let timeout = match input {
+                Some(input) => duration::parse(input).ok_or_else(|| {
+                    bot_error(tr!(locale, "error-invalid-duration", input = input))
+                })?,
+                None => Duration::hours(1),
+            };
+            if timeout < Duration::minutes(1) || timeout > Duration::days(MAX_TIMEOUT_DAYS) {
+                return Err(bot_error(tr!(
+                    locale,
+                    "error-timeout-range",
+                    max_days = MAX_TIMEOUT_DAYS
+                )));
+            }
+            Some(timeout.num_minutes())
+        }
+        (_, Some(_)) => return Err(bot_error(tr!(locale, "escalation-duration-timeout-only"))),
+        (_, None) => None,
+    };
+
+    database::set_warning_escalation(
+        &ctx.data().database,
+        &guild_id.to_string(),
+        threshold,
+        action.as_str(),
+        duration_minutes,
+        &ctx.author().id.to_string(),
+    )
+    .await?;
+
+    ctx.say(tr!(
+        locale,
+        "escalation-set",
+        threshold = threshold,
+        sanction = action.sanction(duration_minutes).label(locale)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List the warning escalations of this server
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "list",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn escalation_list(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let escalations =
+        database::get_warning_escalations(&ctx.data().database, &guild_id.to_string()).await?;
+
+    if escalations.is_empty() {
+        ctx.say(tr!(locale, "escalation-list-empty")).await?;
+        return Ok(());
+    }
+
+    let mut response = tr!(locale, "escalation-list-title") + "\n\n";
+    for escalation in &escalations {
+        let sanction: Option<Sanction> = EscalationAction::parse(&escalation.action)
+            .map(|action| action.sanction(escalation.duration_minutes));
+
+        response.push_str(&tr!(
+            locale,
+            "escalation-list-entry",
+            threshold = escalation.threshold,
+            sanction = sanction.map_or_else(|| escalation.action.clone(), |s| s.label(locale))
+        ));
+        response.push('\n');
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Remove the escalation for a warning count
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    rename = "remove",
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    default_member_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn escalation_remove(
+    ctx: Context<'_>,
+    #[description = "Number of active warnings"] threshold: i64,
+) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let guild_id = ctx
+        .guild_id()
+        .ok_or_else(|| bot_error(tr!(locale, "error-guild-only")))?;
+
+    let deleted =
+        database::delete_warning_escalation(&ctx.data().database, &guild_id.to_string(), threshold)
+            .await?;
+
+    if deleted {
+        ctx.say(tr!(locale, "escalation-removed", threshold = threshold))
+            .await?;
+    } else {
+        ctx.say(tr!(locale, "escalation-not-found")).await?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::database;
 use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings;
 
 /// Makes a game request entry for me to play later
 #[tracing::instrument]
@@ -22,13 +24,14 @@ pub async fn request_game(
         "Game suggestion command invoked"
     );
 
+    let locale = i18n::locale(ctx).await;
     let game_name = game_name
         .filter(|s| !s.trim().is_empty())
-        .ok_or_else(|| bot_error("Game name cannot be empty"))?;
+        .ok_or_else(|| bot_error(tr!(locale, "game-name-empty")))?;
 
     let developer = developer
         .filter(|s| !s.trim().is_empty())
-        .ok_or_else(|| bot_error("Developer name cannot be empty"))?;
+        .ok_or_else(|| bot_error(tr!(locale, "game-developer-empty")))?;
 
     let suggestion_id = database::save_game_suggestion(
         &ctx.data().database,
@@ -39,12 +42,27 @@ pub async fn request_game(
     )
     .await?;
 
-    let response = format!(
-        "**Game Suggestion #{suggestion_id}** \n**Game:** {game_name}\n**Developer:** {developer}\n**Suggested by:** {}",
-        ctx.author().name
+    let response = tr!(
+        locale,
+        "game-saved",
+        id = suggestion_id,
+        game = game_name,
+        developer = developer,
+        user = ctx.author().name
     );
 
-    ctx.say(response).await?;
+    ctx.say(&response).await?;
+
+    if let Some(guild_id) = ctx.guild_id() {
+        settings::announce_suggestion(
+            ctx.http(),
+            ctx.data(),
+            guild_id,
+            ctx.channel_id(),
+            &response,
+        )
+        .await;
+    }
 
     tracing::info!(
         suggestion_id = %suggestion_id,
@@ -79,29 +97,33 @@ pub async fn list_games(
     let limit = limit.map(|l| l.clamp(1, 50));
     let suggestions = database::get_game_suggestions(&ctx.data().database, limit).await?;
 
+    let locale = i18n::locale(ctx).await;
     if suggestions.is_empty() {
-        ctx.say("No game suggestions found! Be the first to suggest a game with `/suggest game`.")
-            .await?;
+        ctx.say(tr!(locale, "game-list-empty")).await?;
         return Ok(());
     }
 
-    let mut response = format!("**Latest {} Game Suggestions**\n\n", suggestions.len());
+    let mut response = tr!(locale, "game-list-title", count = suggestions.len());
+    response.push_str("\n\n");
 
     for (index, suggestion) in suggestions.iter().enumerate().take(10) {
-        response.push_str(&format!(
-            "**{}. {}** developed by {}\n   *Suggested by {} (ID: {})*\n\n",
-            index + 1,
-            suggestion.game_name,
-            suggestion.developer,
-            suggestion.suggested_by_name,
-            suggestion.id
+        response.push_str(&tr!(
+            locale,
+            "game-list-entry",
+            index = index + 1,
+            game = suggestion.game_name,
+            developer = suggestion.developer,
+            user = suggestion.suggested_by_name,
+            id = suggestion.id
         ));
+        response.push_str("\n\n");
     }
 
     if suggestions.len() > 10 {
-        response.push_str(&format!(
-            "*... and {} more suggestions*",
-            suggestions.len() - 10
+        response.push_str(&tr!(
+            locale,
+            "suggestion-list-more",
+            count = suggestions.len() - 10
         ));
     }
 
@@ -143,22 +165,26 @@ pub async fn my_game_requests(
     )
     .await?;
 
+    let locale = i18n::locale(ctx).await;
     if suggestions.is_empty() {
-        ctx.say("You haven't suggested any games yet! Use `/suggest game` to add your first suggestion.").await?;
+        ctx.say(tr!(locale, "game-mine-empty")).await?;
         return Ok(());
     }
 
-    let mut response = format!("**Your {} Game Suggestions**\n\n", suggestions.len());
+    let mut response = tr!(locale, "game-mine-title", count = suggestions.len());
+    response.push_str("\n\n");
 
     for (index, suggestion) in suggestions.iter().enumerate() {
-        response.push_str(&format!(
-            "**{}. {}** developed by {}\n   *Suggested on {} (ID: {})*\n\n",
-            index + 1,
-            suggestion.game_name,
-            suggestion.developer,
-            suggestion.created_at.format("%Y-%m-%d %H:%M UTC"),
-            suggestion.id
+        response.push_str(&tr!(
+            locale,
+            "game-mine-entry",
+            index = index + 1,
+            game = suggestion.game_name,
+            developer = suggestion.developer,
+            date = suggestion.created_at.format("%Y-%m-%d %H:%M UTC"),
+            id = suggestion.id
         ));
+        response.push_str("\n\n");
     }
 
     ctx.say(response).await?;
@@ -198,20 +224,17 @@ pub async fn delete_game_request(
     )
     .await?;
 
+    let locale = i18n::locale(ctx).await;
     if deleted {
-        ctx.say(format!(
-            "Successfully deleted suggestion #{}",
-            suggestion_id
-        ))
-        .await?;
+        ctx.say(tr!(locale, "suggestion-deleted", id = suggestion_id))
+            .await?;
         tracing::info!(
             user_id = %ctx.author().id,
             suggestion_id = %suggestion_id,
             "Game suggestion deleted successfully"
         );
     } else {
-        ctx.say("Suggestion not found or you don't have permission to delete it.")
-            .await?;
+        ctx.say(tr!(locale, "suggestion-not-deleted")).await?;
         tracing::warn!(
             user_id = %ctx.author().id,
             suggestion_id = %suggestion_id,
@@ -221,3 +244,44 @@ pub async fn delete_game_request(
 
     Ok(())
 }
+
+/// Remove anyone's game request, for curators
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    category = "Games"
+)]
+pub async fn remove_game_request(
+    ctx: Context<'_>,
+    #[description = "ID of the suggestion to remove"] suggestion_id: i64,
+) -> Result<()> {
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        suggestion_id = %suggestion_id,
+        "Remove game suggestion command invoked"
+    );
+
+    let locale = i18n::locale(ctx).await;
+    let removed = database::remove_game_suggestion(&ctx.data().database, suggestion_id).await?;
+    if !removed {
+        return Err(bot_error(tr!(
+            locale,
+            "suggestion-not-found",
+            id = suggestion_id
+        )));
+    }
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        suggestion_id = %suggestion_id,
+        "Game suggestion removed by curator"
+    );
+    ctx.say(tr!(locale, "suggestion-removed", id = suggestion_id))
+        .await?;
+
+    Ok(())
+}
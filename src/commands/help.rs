@@ -0,0 +1,272 @@
+use crate::audit;
+use crate::bot::Data;
+use crate::error::{Context, Error, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings::{self, Locale, Module};
+use poise::CreateReply;
+use poise::serenity_prelude::{Colour, CreateEmbed, CreateEmbedFooter};
+use std::collections::BTreeSet;
+
+type Command = poise::Command<Data, Error>;
+
+/// Show the commands you can use here
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, category = "Misc")]
+pub async fn help(
+    ctx: Context<'_>,
+    #[description = "Command to explain, e.g. `suggest request_song`"]
+    #[rest]
+    command: Option<String>,
+) -> Result<()> {
+    let disabled = match ctx.guild_id() {
+        Some(guild_id) => {
+            let settings = ctx
+                .data()
+                .settings
+                .get(&ctx.data().database, guild_id)
+                .await?;
+            settings::parse_modules(&settings.disabled_modules)
+        }
+        None => BTreeSet::new(),
+    };
+    let commands = &ctx.framework().options().commands;
+    let locale = i18n::locale(ctx).await;
+
+    let embed = match command.as_deref().map(str::trim) {
+        Some(name) => {
+            let mut parents = Vec::new();
+            let command = poise::find_command(commands, name, true, &mut parents)
+                .filter(|(_, _, rest)| rest.is_empty())
+                .map(|(command, _, _)| command)
+                .filter(|command| is_visible(command, &parents, &disabled))
+                .ok_or_else(|| bot_error(tr!(locale, "help-unknown-command", command = name)))?;
+            command_embed(locale, command, &parents, &disabled)
+        }
+        None => overview_embed(locale, commands, &disabled),
+    };
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Whether help lists `command`. A group is listed while any of its commands is, so turning off
+/// admin still leaves `admin settings`.
+fn is_visible(command: &Command, parents: &[&Command], disabled: &BTreeSet<Module>) -> bool {
+    if command.hide_in_help || parents.iter().any(|parent| parent.hide_in_help) {
+        return false;
+    }
+    if command.subcommands.is_empty() {
+        return settings::command_module(command, parents).is_none_or(|m| !disabled.contains(&m));
+    }
+
+    let mut path = parents.to_vec();
+    path.push(command);
+    command
+        .subcommands
+        .iter()
+        .any(|subcommand| is_visible(subcommand, &path, disabled))
+}
+
+/// Leaf commands grouped by section, then by the group they are in.
+type Sections<'a> = Vec<(&'a str, Vec<(String, Vec<&'a str>)>)>;
+
+fn collect<'a>(
+    commands: &'a [Command],
+    parents: &mut Vec<&'a Command>,
+    disabled: &BTreeSet<Module>,
+    sections: &mut Sections<'a>,
+) {
+    for command in commands {
+        if !is_visible(command, parents, disabled) {
+            continue;
+        }
+        if !command.subcommands.is_empty() {
+            parents.push(command);
+            collect(&command.subcommands, parents, disabled, sections);
+            parents.pop();
+            continue;
+        }
+
+        let section = std::iter::once(command)
+            .chain(parents.iter().rev().copied())
+            .find_map(|command| command.category.as_deref())
+            .unwrap_or("Other");
+        let group = parents
+            .iter()
+            .map(|parent| parent.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let index = match sections.iter().position(|(name, _)| *name == section) {
+            Some(index) => index,
+            None => {
+                sections.push((section, Vec::new()));
+                sections.len() - 1
+            }
+        };
+        let groups = &mut sections[index].1;
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, names)) => names.push(&command.name),
+            None => groups.push((group, vec![&command.name])),
+        }
+    }
+}
+
+/// The heading of a help section in `locale`, categories without a translation keep their name.
+fn section_name(locale: Locale, section: &str) -> String {
+    i18n::try_translate(locale, &format!("category-{}", section.to_lowercase()))
+        .unwrap_or_else(|| section.to_string())
+}
+
+/// `command`'s description in `locale`, as registered with Discord.
+fn description(locale: Locale, command: &Command) -> Option<&str> {
+    command
+        .description_localizations
+        .get(locale.code())
+        .or(command.description.as_ref())
+        .map(String::as_str)
+}
+
+fn overview_embed(
+    locale: Locale,
+    commands: &[Command],
+    disabled: &BTreeSet<Module>,
+) -> CreateEmbed {
+    let mut sections = Sections::new();
+    collect(commands, &mut Vec::new(), disabled, &mut sections);
+
+    let mut embed = CreateEmbed::new()
+        .title(tr!(locale, "help-title"))
+        .colour(Colour::BLUE)
+        .footer(CreateEmbedFooter::new(tr!(locale, "help-footer")));
+
+    for (section, groups) in sections {
+        let lines = groups
+            .iter()
+            .map(|(group, names)| match group.is_empty() {
+                true => names
+                    .iter()
+                    .map(|name| format!("`/{}`", name))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                false => format!("`/{}` {}", group, names.join(", ")),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field(
+            section_name(locale, section),
+            audit::truncate_field(&lines),
+            false,
+        );
+    }
+
+    if !disabled.is_empty() {
+        let names = disabled
+            .iter()
+            .map(Module::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        embed = embed.description(tr!(locale, "help-disabled", modules = names));
+    }
+
+    embed
+}
+
+fn command_embed(
+    locale: Locale,
+    command: &Command,
+    parents: &[&Command],
+    disabled: &BTreeSet<Module>,
+) -> CreateEmbed {
+    let mut description = description(locale, command)
+        .map(str::to_string)
+        .unwrap_or_else(|| tr!(locale, "help-no-description"));
+    if let Some(help_text) = &command.help_text {
+        description.push_str("\n\n");
+        description.push_str(help_text);
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("/{}", command.qualified_name))
+        .colour(Colour::BLUE)
+        .description(description);
+
+    if !command.parameters.is_empty() {
+        let parameters = command
+            .parameters
+            .iter()
+            .map(|parameter| {
+                format!(
+                    "`{}`{} {}",
+                    parameter.name,
+                    if parameter.required {
+                        String::new()
+                    } else {
+                        format!(" {}", tr!(locale, "help-optional"))
+                    },
+                    parameter
+                        .description_localizations
+                        .get(locale.code())
+                        .or(parameter.description.as_ref())
+                        .map_or("", String::as_str)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        embed = embed.field(
+            tr!(locale, "help-options"),
+            audit::truncate_field(&parameters),
+            false,
+        );
+    }
+
+    let mut path = parents.to_vec();
+    path.push(command);
+    let subcommands = command
+        .subcommands
+        .iter()
+        .filter(|subcommand| is_visible(subcommand, &path, disabled))
+        .map(|subcommand| format!("`{}`", subcommand.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !subcommands.is_empty() {
+        embed = embed.field(
+            tr!(locale, "help-subcommands"),
+            audit::truncate_field(&subcommands),
+            false,
+        );
+    }
+
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::get_commands;
+
+    #[test]
+    fn test_disabled_modules_are_hidden() {
+        let commands = get_commands();
+
+        let mut sections = Sections::new();
+        collect(&commands, &mut Vec::new(), &BTreeSet::new(), &mut sections);
+        let names: Vec<_> = sections.iter().map(|(name, _)| *name).collect();
+        assert!(names.contains(&"Music"));
+        assert!(names.contains(&"Admin"));
+        assert!(names.contains(&"Settings"));
+        assert!(!names.contains(&"Owner"));
+
+        let disabled = BTreeSet::from([Module::Music, Module::Admin]);
+        let mut sections = Sections::new();
+        collect(&commands, &mut Vec::new(), &disabled, &mut sections);
+        let names: Vec<_> = sections.iter().map(|(name, _)| *name).collect();
+        assert!(!names.contains(&"Music"));
+        assert!(!names.contains(&"Admin"));
+        // Settings stay reachable so the modules can be turned back on.
+        assert!(names.contains(&"Settings"));
+        assert!(names.contains(&"Games"));
+    }
+}
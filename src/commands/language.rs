@@ -0,0 +1,41 @@
+use crate::database;
+use crate::error::{Context, Result};
+use crate::i18n::{self, tr};
+use crate::settings::Locale;
+use poise::ChoiceParameter;
+
+/// Choose the language the bot answers you in
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    category = "Misc",
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Language, leave empty to use the server's"] locale: Option<Locale>,
+) -> Result<()> {
+    tracing::info!(
+        user_id = %ctx.author().id,
+        locale = ?locale,
+        "Language command invoked"
+    );
+
+    database::set_user_locale(
+        &ctx.data().database,
+        &ctx.author().id.to_string(),
+        locale.map(|l| l.code()),
+    )
+    .await?;
+    ctx.data().locales.invalidate(ctx.author().id);
+
+    let response = match locale {
+        Some(locale) => tr!(locale, "language-set", language = locale.name()),
+        None => tr!(i18n::locale(ctx).await, "language-reset"),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
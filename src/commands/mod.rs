@@ -1,13 +1,20 @@
 mod admin;
 mod games;
+mod help;
+mod language;
 mod music;
+mod owner;
 
 use crate::error::{Context, Error, Result};
 
 pub use admin::*;
 pub use games::*;
+pub use help::*;
+pub use language::*;
 pub use music::*;
+pub use owner::*;
 
+/// Suggest songs and games
 #[poise::command(
     prefix_command,
     slash_command,
@@ -16,10 +23,12 @@ pub use music::*;
         "list_songs",
         "my_song_requests",
         "delete_song_request",
+        "remove_song_request",
         "request_game",
         "list_games",
         "my_game_requests",
-        "delete_game_request"
+        "delete_game_request",
+        "remove_game_request"
     ),
     subcommand_required,
     category = "Misc",
@@ -31,5 +40,5 @@ pub async fn suggest(_: Context<'_>) -> Result<()> {
 }
 
 pub fn get_commands() -> Vec<poise::Command<crate::bot::Data, Error>> {
-    vec![suggest(), admin()]
+    vec![help(), language(), suggest(), admin(), owner()]
 }
@@ -1,5 +1,7 @@
 use crate::database;
 use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings;
 
 /// Makes a song request entry for me to listen to later
 #[tracing::instrument]
@@ -22,13 +24,14 @@ pub async fn request_song(
         "Song suggestion command invoked"
     );
 
+    let locale = i18n::locale(ctx).await;
     let song_name = song_name
         .filter(|s| !s.trim().is_empty())
-        .ok_or_else(|| bot_error("Song name cannot be empty"))?;
+        .ok_or_else(|| bot_error(tr!(locale, "song-name-empty")))?;
 
     let artist = artist
         .filter(|s| !s.trim().is_empty())
-        .ok_or_else(|| bot_error("Artist name cannot be empty"))?;
+        .ok_or_else(|| bot_error(tr!(locale, "song-artist-empty")))?;
 
     let suggestion_id = database::save_song_suggestion(
         &ctx.data().database,
@@ -39,12 +42,27 @@ pub async fn request_song(
     )
     .await?;
 
-    let response = format!(
-        "**Song Suggestion #{suggestion_id}** \n**Song:** {song_name}\n**Artist:** {artist}\n**Suggested by:** {}",
-        ctx.author().name
+    let response = tr!(
+        locale,
+        "song-saved",
+        id = suggestion_id,
+        song = song_name,
+        artist = artist,
+        user = ctx.author().name
     );
 
-    ctx.say(response).await?;
+    ctx.say(&response).await?;
+
+    if let Some(guild_id) = ctx.guild_id() {
+        settings::announce_suggestion(
+            ctx.http(),
+            ctx.data(),
+            guild_id,
+            ctx.channel_id(),
+            &response,
+        )
+        .await;
+    }
 
     tracing::info!(
         suggestion_id = %suggestion_id,
@@ -79,29 +97,33 @@ pub async fn list_songs(
     let limit = limit.map(|l| l.clamp(1, 50));
     let suggestions = database::get_song_suggestions(&ctx.data().database, limit).await?;
 
+    let locale = i18n::locale(ctx).await;
     if suggestions.is_empty() {
-        ctx.say("No song suggestions found! Be the first to suggest a song with `/suggest song`.")
-            .await?;
+        ctx.say(tr!(locale, "song-list-empty")).await?;
         return Ok(());
     }
 
-    let mut response = format!("**Latest {} Song Suggestions**\n\n", suggestions.len());
+    let mut response = tr!(locale, "song-list-title", count = suggestions.len());
+    response.push_str("\n\n");
 
     for (index, suggestion) in suggestions.iter().enumerate().take(10) {
-        response.push_str(&format!(
-            "**{}. {}** by {}\n   *Suggested by {} (ID: {})*\n\n",
-            index + 1,
-            suggestion.song_name,
-            suggestion.artist,
-            suggestion.suggested_by_name,
-            suggestion.id
+        response.push_str(&tr!(
+            locale,
+            "song-list-entry",
+            index = index + 1,
+            song = suggestion.song_name,
+            artist = suggestion.artist,
+            user = suggestion.suggested_by_name,
+            id = suggestion.id
         ));
+        response.push_str("\n\n");
     }
 
     if suggestions.len() > 10 {
-        response.push_str(&format!(
-            "*... and {} more suggestions*",
-            suggestions.len() - 10
+        response.push_str(&tr!(
+            locale,
+            "suggestion-list-more",
+            count = suggestions.len() - 10
         ));
     }
 
@@ -143,22 +165,26 @@ pub async fn my_song_requests(
     )
     .await?;
 
+    let locale = i18n::locale(ctx).await;
     if suggestions.is_empty() {
-        ctx.say("You haven't suggested any songs yet! Use `/suggest song` to add your first suggestion.").await?;
+        ctx.say(tr!(locale, "song-mine-empty")).await?;
         return Ok(());
     }
 
-    let mut response = format!("**Your {} Song Suggestions**\n\n", suggestions.len());
+    let mut response = tr!(locale, "song-mine-title", count = suggestions.len());
+    response.push_str("\n\n");
 
     for (index, suggestion) in suggestions.iter().enumerate() {
-        response.push_str(&format!(
-            "**{}. {}** by {}\n   *Suggested on {} (ID: {})*\n\n",
-            index + 1,
-            suggestion.song_name,
-            suggestion.artist,
-            suggestion.created_at.format("%Y-%m-%d %H:%M UTC"),
-            suggestion.id
+        response.push_str(&tr!(
+            locale,
+            "song-mine-entry",
+            index = index + 1,
+            song = suggestion.song_name,
+            artist = suggestion.artist,
+            date = suggestion.created_at.format("%Y-%m-%d %H:%M UTC"),
+            id = suggestion.id
         ));
+        response.push_str("\n\n");
     }
 
     ctx.say(response).await?;
@@ -198,20 +224,17 @@ pub async fn delete_song_request(
     )
     .await?;
 
+    let locale = i18n::locale(ctx).await;
     if deleted {
-        ctx.say(format!(
-            "Successfully deleted suggestion #{}",
-            suggestion_id
-        ))
-        .await?;
+        ctx.say(tr!(locale, "suggestion-deleted", id = suggestion_id))
+            .await?;
         tracing::info!(
             user_id = %ctx.author().id,
             suggestion_id = %suggestion_id,
             "Song suggestion deleted successfully"
         );
     } else {
-        ctx.say("Suggestion not found or you don't have permission to delete it.")
-            .await?;
+        ctx.say(tr!(locale, "suggestion-not-deleted")).await?;
         tracing::warn!(
             user_id = %ctx.author().id,
             suggestion_id = %suggestion_id,
@@ -221,3 +244,44 @@ pub async fn delete_song_request(
 
     Ok(())
 }
+
+/// Remove anyone's song request, for curators
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES",
+    category = "Music"
+)]
+pub async fn remove_song_request(
+    ctx: Context<'_>,
+    #[description = "ID of the suggestion to remove"] suggestion_id: i64,
+) -> Result<()> {
+    tracing::info!(
+        user_id = %ctx.author().id,
+        guild_id = ?ctx.guild_id(),
+        suggestion_id = %suggestion_id,
+        "Remove song suggestion command invoked"
+    );
+
+    let locale = i18n::locale(ctx).await;
+    let removed = database::remove_song_suggestion(&ctx.data().database, suggestion_id).await?;
+    if !removed {
+        return Err(bot_error(tr!(
+            locale,
+            "suggestion-not-found",
+            id = suggestion_id
+        )));
+    }
+
+    tracing::info!(
+        user_id = %ctx.author().id,
+        suggestion_id = %suggestion_id,
+        "Song suggestion removed by curator"
+    );
+    ctx.say(tr!(locale, "suggestion-removed", id = suggestion_id))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,254 @@
+use crate::config::Approval;
+use crate::database;
+use crate::error::{Context, Result, bot_error};
+use crate::i18n::{self, tr};
+use crate::registration::{self, Scope};
+use crate::scheduler::parse_id;
+use crate::settings::Locale;
+use poise::serenity_prelude::GuildId;
+use std::collections::BTreeMap;
+
+/// Commands for the people running the bot
+#[tracing::instrument]
+#[poise::command(
+    prefix_command,
+    slash_command,
+    subcommands("reload", "sync", "clear", "approve", "revoke", "guilds"),
+    subcommand_required,
+    owners_only,
+    hide_in_help,
+    category = "Owner"
+)]
+pub async fn owner(_: Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Re-read the config file and apply what can change without a restart
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, owners_only, hide_in_help)]
+pub async fn reload(ctx: Context<'_>) -> Result<()> {
+    tracing::info!(user_id = %ctx.author().id, "Reload command invoked");
+
+    let locale = i18n::locale(ctx).await;
+    let report = ctx.data().config.reload().map_err(|e| {
+        tracing::warn!(error = %format!("{:#}", e), "Configuration reload failed");
+        bot_error(tr!(
+            locale,
+            "owner-reload-failed",
+            error = format!("{:#}", e)
+        ))
+    })?;
+    ctx.data().apply_config();
+
+    tracing::warn!(
+        applied = ?report.applied,
+        needs_restart = ?report.needs_restart,
+        "Configuration reloaded"
+    );
+
+    ctx.say(tr!(
+        locale,
+        "owner-reloaded",
+        report = report.describe(locale)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Register the slash commands again as configured in `commands.registration`
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, owners_only, hide_in_help)]
+pub async fn sync(ctx: Context<'_>) -> Result<()> {
+    tracing::info!(user_id = %ctx.author().id, "Sync command invoked");
+
+    let locale = i18n::locale(ctx).await;
+    let config = ctx.data().config.get();
+    let scopes = registration::scopes(&config.commands);
+    if scopes.is_empty() {
+        ctx.say(tr!(locale, "owner-sync-off")).await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    let commands = &ctx.framework().options().commands;
+    registration::register(ctx.http(), commands, &scopes).await?;
+
+    ctx.say(tr!(
+        locale,
+        "owner-synced",
+        count = commands.len(),
+        scopes = describe_scopes(locale, &scopes)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Remove the registered slash commands
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, owners_only, hide_in_help)]
+pub async fn clear(
+    ctx: Context<'_>,
+    #[description = "Only this guild, instead of everywhere"] guild: Option<GuildId>,
+) -> Result<()> {
+    tracing::info!(user_id = %ctx.author().id, guild = ?guild, "Clear command invoked");
+
+    let locale = i18n::locale(ctx).await;
+    let scopes = match guild {
+        Some(guild_id) => vec![Scope::Guild(guild_id)],
+        None => registration::all_scopes(&ctx.data().config.get().commands),
+    };
+
+    ctx.defer().await?;
+    registration::clear(ctx.http(), &scopes).await?;
+
+    ctx.say(tr!(
+        locale,
+        "owner-cleared",
+        scopes = describe_scopes(locale, &scopes)
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Let the bot work in a server, also before it is added there
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, owners_only, hide_in_help)]
+pub async fn approve(
+    ctx: Context<'_>,
+    #[description = "ID of the server"] guild: GuildId,
+) -> Result<()> {
+    tracing::info!(user_id = %ctx.author().id, guild_id = %guild, "Approve command invoked");
+
+    let locale = i18n::locale(ctx).await;
+    let approved = database::approve_guild(
+        &ctx.data().database,
+        &guild.to_string(),
+        &ctx.author().id.to_string(),
+    )
+    .await?;
+    ctx.data().approvals.invalidate(guild);
+
+    let label = guild_label(ctx, guild);
+    let response = match approved {
+        true => tr!(locale, "owner-approved", guild = label),
+        false => tr!(locale, "owner-already-approved", guild = label),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Withdraw a server's approval
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, owners_only, hide_in_help)]
+pub async fn revoke(
+    ctx: Context<'_>,
+    #[description = "ID of the server"] guild: GuildId,
+) -> Result<()> {
+    tracing::info!(user_id = %ctx.author().id, guild_id = %guild, "Revoke command invoked");
+
+    let locale = i18n::locale(ctx).await;
+    let label = guild_label(ctx, guild);
+    let revoked = database::revoke_guild(&ctx.data().database, &guild.to_string()).await?;
+    ctx.data().approvals.invalidate(guild);
+    if !revoked {
+        return Err(bot_error(tr!(locale, "owner-not-approved", guild = label)));
+    }
+
+    let joined = ctx.cache().guild(guild).is_some();
+    let response = match ctx.data().config.get().guilds.approval {
+        Approval::Open => tr!(locale, "owner-revoked-open", guild = label),
+        Approval::Leave if joined => {
+            // Answer first, the reply can't be sent once the bot left the server it was asked in.
+            ctx.say(tr!(locale, "owner-revoked-leaving", guild = label))
+                .await?;
+            guild.leave(ctx.http()).await?;
+            tracing::info!(guild_id = %guild, "Left guild after revoking its approval");
+            return Ok(());
+        }
+        _ if joined => tr!(locale, "owner-revoked-ignored", guild = label),
+        _ => tr!(locale, "owner-revoked", guild = label),
+    };
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// List the servers the bot is in and which are approved
+#[tracing::instrument]
+#[poise::command(prefix_command, slash_command, owners_only, hide_in_help)]
+pub async fn guilds(ctx: Context<'_>) -> Result<()> {
+    let locale = i18n::locale(ctx).await;
+    let approved = database::get_approved_guilds(&ctx.data().database).await?;
+
+    // Every joined guild, then the approved ones the bot isn't in yet.
+    let mut guilds: BTreeMap<GuildId, bool> = ctx
+        .cache()
+        .guilds()
+        .into_iter()
+        .map(|guild_id| (guild_id, false))
+        .collect();
+    for guild in &approved {
+        if let Some(guild_id) = parse_id(&guild.guild_id).map(GuildId::new) {
+            guilds.insert(guild_id, true);
+        }
+    }
+
+    let mut response = tr!(
+        locale,
+        "owner-guilds-title",
+        count = guilds.len(),
+        approval = format!("{:?}", ctx.data().config.get().guilds.approval)
+    ) + "\n";
+    for (guild_id, approved) in guilds {
+        let name = ctx.cache().guild(guild_id).map(|guild| guild.name.clone());
+        let status = match (&name, approved) {
+            (Some(_), true) => tr!(locale, "owner-guild-approved"),
+            (Some(_), false) => tr!(locale, "owner-guild-not-approved"),
+            (None, _) => tr!(locale, "owner-guild-not-joined"),
+        };
+        response.push_str(&format!(
+            "\n`{}` {} — {}",
+            guild_id,
+            name.as_deref().unwrap_or("?"),
+            status
+        ));
+    }
+
+    if response.len() > 1900 {
+        response = response.chars().take(1900).collect();
+        response.push('\n');
+        response.push_str(&tr!(locale, "response-truncated"));
+    }
+
+    ctx.say(response).await?;
+
+    Ok(())
+}
+
+/// Where commands were registered, like [`registration::describe`] but for replies.
+fn describe_scopes(locale: Locale, scopes: &[Scope]) -> String {
+    if scopes.is_empty() {
+        return tr!(locale, "owner-scope-none");
+    }
+
+    scopes
+        .iter()
+        .map(|scope| match scope {
+            Scope::Global => tr!(locale, "owner-scope-global"),
+            Scope::Guild(guild_id) => tr!(locale, "owner-scope-guild", id = guild_id),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `guild`'s name and ID, or only the ID when the bot is not in it.
+fn guild_label(ctx: Context<'_>, guild_id: GuildId) -> String {
+    match ctx.cache().guild(guild_id) {
+        Some(guild) => format!("**{}** (`{}`)", guild.name, guild_id),
+        None => format!("`{}`", guild_id),
+    }
+}
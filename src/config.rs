@@ -1,33 +1,602 @@
-use anyhow::{Context, Result};
-use serenity::all::GatewayIntents;
-use std::path::PathBuf;
+use crate::message_log::DEFAULT_MESSAGE_CACHE_CAPACITY;
+use anyhow::{Context, Result, anyhow, bail};
+use serde::{Deserialize, Deserializer};
+use serenity::all::{GatewayIntents, GuildId, UserId};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
 
-#[derive(Debug, Clone)]
+/// Swaps the log filter of the running subscriber.
+pub type LogFilter = reload::Handle<EnvFilter, Registry>;
+
+/// Config file read when no path is given, if it exists.
+const DEFAULT_CONFIG_PATH: &str = "./bearobot.toml";
+
+/// Bot configuration, read from an optional TOML file with environment variables on top.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
-    pub discord_token: String,
+    pub discord: DiscordConfig,
+    pub logging: LoggingConfig,
+    pub database: DatabaseConfig,
+    pub message_log: MessageLogConfig,
+    pub commands: CommandsConfig,
+    pub guilds: GuildsConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscordConfig {
+    pub token: Secret,
+    pub prefix: String,
+    #[serde(deserialize_with = "deserialize_intents")]
     pub intents: GatewayIntents,
-    pub command_prefix: String,
-    pub database_path: PathBuf,
+    /// Users allowed to run owner commands, on top of the application owner.
+    pub owners: Vec<u64>,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        Self {
+            token: Secret::default(),
+            prefix: ")".to_string(),
+            intents: GatewayIntents::non_privileged() | GatewayIntents::MESSAGE_CONTENT,
+            owners: Vec::new(),
+        }
+    }
+}
+
+/// A value shown as `[redacted]` by `Debug`, so instrumented functions and logged configs never
+/// print it.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+impl DiscordConfig {
+    pub fn owner_ids(&self) -> HashSet<UserId> {
+        self.owners.iter().copied().map(UserId::new).collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// A `tracing` filter, either a level such as `info` or directives such as
+    /// `warn,bearobot=debug`.
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "warn".to_string(),
+            format: LogFormat::Pretty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            other => bail!(
+                "unknown log format `{}`, expected `pretty` or `json`",
+                other
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub path: PathBuf,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            path: "./db/bearobot.sqlite".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageLogConfig {
+    /// Messages kept in memory so edits and deletions can show the original.
+    pub cache_capacity: usize,
+}
+
+impl Default for MessageLogConfig {
+    fn default() -> Self {
+        Self {
+            cache_capacity: DEFAULT_MESSAGE_CACHE_CAPACITY,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommandsConfig {
+    pub registration: Registration,
+    /// Guilds to register in when `registration` is `guilds`.
+    pub guilds: Vec<u64>,
+}
+
+/// Where slash commands are registered when the bot starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Registration {
+    /// Everywhere. Discord can take up to an hour to show changes.
+    #[default]
+    Global,
+    /// Only in `commands.guilds`, where changes show immediately.
+    Guilds,
+    /// Leave the registered commands as they are.
+    None,
+}
+
+impl Registration {
+    fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "global" => Ok(Self::Global),
+            "guilds" => Ok(Self::Guilds),
+            "none" => Ok(Self::None),
+            other => bail!(
+                "unknown registration `{}`, expected `global`, `guilds` or `none`",
+                other
+            ),
+        }
+    }
+}
+
+impl CommandsConfig {
+    pub fn guild_ids(&self) -> Vec<GuildId> {
+        self.guilds.iter().copied().map(GuildId::new).collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GuildsConfig {
+    pub approval: Approval,
+}
+
+/// Which servers the bot works in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Approval {
+    /// Any server that adds the bot.
+    #[default]
+    Open,
+    /// Only servers an owner approved. The bot leaves the others when it joins them.
+    Leave,
+    /// Only servers an owner approved. The bot stays in the others but ignores them.
+    Dormant,
+}
+
+impl Approval {
+    fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "leave" => Ok(Self::Leave),
+            "dormant" => Ok(Self::Dormant),
+            other => bail!(
+                "unknown approval `{}`, expected `open`, `leave` or `dormant`",
+                other
+            ),
+        }
+    }
 }
 
 impl Config {
-    #[tracing::instrument]
-    pub fn from_env() -> Result<Self> {
-        tracing::info!("Loading .env variables...");
+    /// Loads the config file at `path`, or `./bearobot.toml` when it exists, then applies
+    /// environment overrides and validates the result.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
         dotenv::dotenv().ok();
 
-        let discord_token = std::env::var("DISCORD_TOKEN")
-            .context("DISCORD_TOKEN environment variable is required")?;
+        let path = match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => Some(PathBuf::from(DEFAULT_CONFIG_PATH)).filter(|path| path.exists()),
+        };
 
-        let database_path = std::env::var("DATABASE_PATH")
-            .unwrap_or_else(|_| "./db/bearobot.sqlite".to_string())
-            .into();
+        let mut config = match &path {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read config file {}", path.display()))?;
+                Self::from_toml(&contents)
+                    .with_context(|| format!("Invalid config file {}", path.display()))?
+            }
+            None => Self::default(),
+        };
 
-        Ok(Self {
-            discord_token,
-            intents: GatewayIntents::non_privileged() | GatewayIntents::MESSAGE_CONTENT,
-            command_prefix: ")".to_string(),
-            database_path,
-        })
+        config.apply_env(|name| std::env::var(name).ok())?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Overrides file values with the environment variables returned by `var`.
+    ///
+    /// The token can also be read from the file named by `DISCORD_TOKEN_FILE`, such as
+    /// `/run/secrets/discord_token`.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<()> {
+        if let Some(token) = token_var(&var)? {
+            self.discord.token = token;
+        }
+        if let Some(prefix) = var("BEAROBOT_PREFIX") {
+            self.discord.prefix = prefix;
+        }
+        if let Some(intents) = var("BEAROBOT_INTENTS") {
+            self.discord.intents =
+                parse_intents(intents.split(',')).context("Invalid BEAROBOT_INTENTS")?;
+        }
+        if let Some(owners) = var("BEAROBOT_OWNERS") {
+            self.discord.owners = parse_ids(&owners).context("Invalid BEAROBOT_OWNERS")?;
+        }
+        if let Some(level) = var("BEAROBOT_LOG_LEVEL") {
+            self.logging.level = level;
+        }
+        if let Some(format) = var("BEAROBOT_LOG_FORMAT") {
+            self.logging.format =
+                LogFormat::parse(&format).context("Invalid BEAROBOT_LOG_FORMAT")?;
+        }
+        if let Some(path) = var("DATABASE_PATH") {
+            self.database.path = path.into();
+        }
+        if let Some(capacity) = var("BEAROBOT_MESSAGE_CACHE_CAPACITY") {
+            self.message_log.cache_capacity = capacity
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("`{}` is not a number", capacity))
+                .context("Invalid BEAROBOT_MESSAGE_CACHE_CAPACITY")?;
+        }
+        if let Some(registration) = var("BEAROBOT_REGISTRATION") {
+            self.commands.registration =
+                Registration::parse(&registration).context("Invalid BEAROBOT_REGISTRATION")?;
+        }
+        if let Some(guilds) = var("BEAROBOT_REGISTRATION_GUILDS") {
+            self.commands.guilds =
+                parse_ids(&guilds).context("Invalid BEAROBOT_REGISTRATION_GUILDS")?;
+        }
+        if let Some(approval) = var("BEAROBOT_GUILD_APPROVAL") {
+            self.guilds.approval =
+                Approval::parse(&approval).context("Invalid BEAROBOT_GUILD_APPROVAL")?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks every value. The token is only checked by [`Config::token`], so commands that
+    /// never talk to Discord work without one.
+    pub fn validate(&self) -> Result<()> {
+        let prefix = &self.discord.prefix;
+        if prefix.is_empty() || prefix.chars().any(char::is_whitespace) {
+            bail!(
+                "discord.prefix `{}` must be non-empty and contain no whitespace",
+                prefix
+            );
+        }
+        if self.discord.intents.is_empty() {
+            bail!("discord.intents must enable at least one intent");
+        }
+        if self.discord.owners.contains(&0) {
+            bail!("discord.owners contains `0`, which is not a user id");
+        }
+
+        EnvFilter::try_new(&self.logging.level).map_err(|e| {
+            anyhow!(
+                "logging.level `{}` is not a valid filter: {}",
+                self.logging.level,
+                e
+            )
+        })?;
+
+        if self.database.path.to_str().is_none_or(str::is_empty) {
+            bail!("database.path must be a non-empty UTF-8 path");
+        }
+        if self.message_log.cache_capacity == 0 {
+            bail!("message_log.cache_capacity must be at least 1");
+        }
+        if self.commands.guilds.contains(&0) {
+            bail!("commands.guilds contains `0`, which is not a guild id");
+        }
+        if self.commands.registration == Registration::Guilds && self.commands.guilds.is_empty() {
+            bail!("commands.registration is `guilds` but commands.guilds is empty");
+        }
+
+        Ok(())
+    }
+
+    /// The bot token, or an error saying how to set it.
+    pub fn token(&self) -> Result<&str> {
+        match self.discord.token.expose().trim() {
+            "" => bail!(
+                "discord.token is not set, set it in the config file, with DISCORD_TOKEN or with \
+                 DISCORD_TOKEN_FILE"
+            ),
+            token => Ok(token),
+        }
+    }
+
+    /// Starts logging as configured. Called once, after the config is loaded.
+    ///
+    /// The returned handle lets a config reload change the level without a restart.
+    pub fn init_tracing(&self) -> LogFilter {
+        let (filter, handle) = reload::Layer::new(EnvFilter::new(&self.logging.level));
+        let registry = tracing_subscriber::registry().with(filter);
+
+        match self.logging.format {
+            LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer()).init(),
+            LogFormat::Json => registry
+                .with(tracing_subscriber::fmt::layer().json())
+                .init(),
+        }
+
+        handle
+    }
+}
+
+/// Reads the token from `DISCORD_TOKEN`, or from the file named by `DISCORD_TOKEN_FILE`.
+fn token_var(var: impl Fn(&str) -> Option<String>) -> Result<Option<Secret>> {
+    match (var("DISCORD_TOKEN"), var("DISCORD_TOKEN_FILE")) {
+        // Deployments moving to the Docker secret usually still have the token in `.env`.
+        (Some(_), Some(_)) => bail!(
+            "DISCORD_TOKEN and DISCORD_TOKEN_FILE are both set. The token is read from \
+             DISCORD_TOKEN_FILE, remove DISCORD_TOKEN from .env or the environment"
+        ),
+        (Some(value), None) => Ok(Some(Secret::new(value))),
+        (None, Some(path)) => read_secret_file(Path::new(&path))
+            .context("Invalid DISCORD_TOKEN_FILE")
+            .map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Reads a secret file such as a Docker secret, without the surrounding whitespace.
+///
+/// Files others can write to are refused, since anyone able to change them controls the secret.
+fn read_secret_file(path: &Path) -> Result<Secret> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read secret file {}", path.display()))?;
+    if !metadata.is_file() {
+        bail!("{} is not a file", path.display());
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = metadata.permissions().mode();
+        if mode & 0o022 != 0 {
+            bail!(
+                "{} can be written by other users (mode {:o}), restrict it with chmod go-w",
+                path.display(),
+                mode & 0o777
+            );
+        }
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read secret file {}", path.display()))?;
+    match contents.trim() {
+        "" => bail!("{} is empty", path.display()),
+        value => Ok(Secret::new(value)),
+    }
+}
+
+/// Parses a comma separated list of Discord ids.
+fn parse_ids(value: &str) -> Result<Vec<u64>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse().map_err(|_| anyhow!("`{}` is not an id", id)))
+        .collect()
+}
+
+/// Parses intent names such as `guild_members`, plus `non_privileged` and `all`.
+fn parse_intents<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<GatewayIntents> {
+    let mut intents = GatewayIntents::empty();
+
+    for name in names.into_iter().map(str::trim).filter(|n| !n.is_empty()) {
+        let upper = name.to_ascii_uppercase();
+        intents |= match upper.as_str() {
+            "NON_PRIVILEGED" => GatewayIntents::non_privileged(),
+            "ALL" => GatewayIntents::all(),
+            _ => GatewayIntents::from_name(&upper)
+                .ok_or_else(|| anyhow!("unknown intent `{}`", name))?,
+        };
+    }
+
+    Ok(intents)
+}
+
+fn deserialize_intents<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<GatewayIntents, D::Error> {
+    let names = Vec::<String>::deserialize(deserializer)?;
+    parse_intents(names.iter().map(String::as_str)).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_file_values() {
+        let config = Config::from_toml(
+            r#"
+            [discord]
+            token = "file-token"
+            prefix = "!"
+            intents = ["guilds", "guild_messages", "message_content"]
+            owners = [123]
+
+            [logging]
+            level = "info,bearobot=debug"
+            format = "json"
+
+            [message_log]
+            cache_capacity = 50
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.discord.prefix, "!");
+        assert_eq!(
+            config.discord.intents,
+            GatewayIntents::GUILDS
+                | GatewayIntents::GUILD_MESSAGES
+                | GatewayIntents::MESSAGE_CONTENT
+        );
+        assert_eq!(
+            config.discord.owner_ids(),
+            HashSet::from([UserId::new(123)])
+        );
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.message_log.cache_capacity, 50);
+        // Sections left out keep their defaults.
+        assert_eq!(config.database.path, PathBuf::from("./db/bearobot.sqlite"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let mut config = Config::from_toml("[discord]\ntoken = \"file\"\nprefix = \"!\"").unwrap();
+        config
+            .apply_env(env(&[
+                ("DISCORD_TOKEN", "env"),
+                ("BEAROBOT_INTENTS", "non_privileged, guild_members"),
+                ("BEAROBOT_OWNERS", "1,2"),
+                ("DATABASE_PATH", "/data/bot.sqlite"),
+                ("BEAROBOT_REGISTRATION", "Guilds"),
+                ("BEAROBOT_REGISTRATION_GUILDS", "10, 20"),
+                ("BEAROBOT_GUILD_APPROVAL", "Dormant"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.discord.token.expose(), "env");
+        assert_eq!(config.discord.prefix, "!");
+        assert_eq!(
+            config.discord.intents,
+            GatewayIntents::non_privileged() | GatewayIntents::GUILD_MEMBERS
+        );
+        assert_eq!(config.discord.owners, vec![1, 2]);
+        assert_eq!(config.database.path, PathBuf::from("/data/bot.sqlite"));
+        assert_eq!(config.commands.registration, Registration::Guilds);
+        assert_eq!(config.commands.guilds, vec![10, 20]);
+        assert_eq!(config.guilds.approval, Approval::Dormant);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let unknown_intent = Config::from_toml("[discord]\nintents = [\"guilds\", \"typo\"]");
+        assert!(format!("{:#}", unknown_intent.unwrap_err()).contains("unknown intent `typo`"));
+        assert!(Config::from_toml("[discord]\nprefx = \"!\"").is_err());
+        assert!(Config::from_toml("[logging]\nformat = \"xml\"").is_err());
+        assert!(Config::from_toml("[guilds]\napproval = \"closed\"").is_err());
+
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+        assert!(config.token().is_err());
+        config.discord.token = Secret::new(" token\n");
+        assert_eq!(config.token().unwrap(), "token");
+
+        config.discord.prefix = "a b".to_string();
+        assert!(config.validate().is_err());
+        config.discord.prefix = "!".to_string();
+
+        config.logging.level = "bearobot=loud".to_string();
+        assert!(config.validate().is_err());
+        config.logging.level = "info".to_string();
+
+        config.commands.registration = Registration::Guilds;
+        assert!(config.validate().is_err());
+        config.commands.registration = Registration::None;
+
+        let error = config
+            .apply_env(env(&[("BEAROBOT_MESSAGE_CACHE_CAPACITY", "lots")]))
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("BEAROBOT_MESSAGE_CACHE_CAPACITY"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_token_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("bearobot-token-{}", std::process::id()));
+        let write = |contents: &str, mode: u32| {
+            std::fs::write(&path, contents).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        };
+        let vars = [("DISCORD_TOKEN_FILE", path.to_str().unwrap())];
+
+        write("file-token\n", 0o600);
+        let mut config = Config::default();
+        config.apply_env(env(&vars)).unwrap();
+        assert_eq!(config.token().unwrap(), "file-token");
+        // The token never shows up in debug output, which `tracing::instrument` relies on.
+        assert!(!format!("{:?}", config).contains("file-token"));
+
+        let both = Config::default()
+            .apply_env(env(&[vars[0], ("DISCORD_TOKEN", "env")]))
+            .unwrap_err();
+        assert!(format!("{:#}", both).contains("remove DISCORD_TOKEN from .env"));
+
+        write("file-token", 0o666);
+        let error = Config::default().apply_env(env(&vars)).unwrap_err();
+        assert!(format!("{:#}", error).contains("written by other users"));
+
+        write(" \n", 0o600);
+        let error = Config::default().apply_env(env(&vars)).unwrap_err();
+        assert!(format!("{:#}", error).contains("is empty"));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(Config::default().apply_env(env(&vars)).is_err());
     }
 }
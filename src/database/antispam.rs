@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Per-guild anti-spam thresholds. A threshold of 0 turns that check off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AntispamSettings {
+    pub guild_id: String,
+    pub enabled: bool,
+    pub flood_messages: i64,
+    pub flood_seconds: i64,
+    pub duplicate_messages: i64,
+    pub duplicate_seconds: i64,
+    pub mention_limit: i64,
+    pub action: String,
+    pub timeout_minutes: i64,
+    pub lockdown_minutes: i64,
+    pub raid_joins: i64,
+    pub raid_seconds: i64,
+    pub raid_account_age_days: i64,
+    pub raid_action: String,
+}
+
+#[tracing::instrument]
+pub async fn get_antispam_settings(
+    pool: &SqlitePool,
+    guild_id: &str,
+) -> Result<Option<AntispamSettings>> {
+    let settings = sqlx::query_as!(
+        AntispamSettings,
+        r#"SELECT guild_id as "guild_id!", enabled, flood_messages, flood_seconds,
+                  duplicate_messages, duplicate_seconds, mention_limit, action, timeout_minutes,
+                  lockdown_minutes, raid_joins, raid_seconds, raid_account_age_days, raid_action
+           FROM antispam_settings
+           WHERE guild_id = ?"#,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch anti-spam settings")?;
+
+    Ok(settings)
+}
+
+#[tracing::instrument]
+pub async fn save_antispam_settings(
+    pool: &SqlitePool,
+    settings: &AntispamSettings,
+    updated_by_id: &str,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO antispam_settings
+            (guild_id, enabled, flood_messages, flood_seconds, duplicate_messages,
+             duplicate_seconds, mention_limit, action, timeout_minutes, lockdown_minutes,
+             raid_joins, raid_seconds, raid_account_age_days, raid_action, updated_by_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            enabled = excluded.enabled,
+            flood_messages = excluded.flood_messages,
+            flood_seconds = excluded.flood_seconds,
+            duplicate_messages = excluded.duplicate_messages,
+            duplicate_seconds = excluded.duplicate_seconds,
+            mention_limit = excluded.mention_limit,
+            action = excluded.action,
+            timeout_minutes = excluded.timeout_minutes,
+            lockdown_minutes = excluded.lockdown_minutes,
+            raid_joins = excluded.raid_joins,
+            raid_seconds = excluded.raid_seconds,
+            raid_account_age_days = excluded.raid_account_age_days,
+            raid_action = excluded.raid_action,
+            updated_by_id = excluded.updated_by_id,
+            updated_at = CURRENT_TIMESTAMP",
+        settings.guild_id,
+        settings.enabled,
+        settings.flood_messages,
+        settings.flood_seconds,
+        settings.duplicate_messages,
+        settings.duplicate_seconds,
+        settings.mention_limit,
+        settings.action,
+        settings.timeout_minutes,
+        settings.lockdown_minutes,
+        settings.raid_joins,
+        settings.raid_seconds,
+        settings.raid_account_age_days,
+        settings.raid_action,
+        updated_by_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save anti-spam settings")?;
+
+    tracing::info!(guild_id = %settings.guild_id, "Anti-spam settings saved");
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovedGuild {
+    pub guild_id: String,
+    pub approved_by_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+struct ApprovedGuildRow {
+    guild_id: String,
+    approved_by_id: String,
+    created_at: NaiveDateTime,
+}
+
+impl From<ApprovedGuildRow> for ApprovedGuild {
+    fn from(row: ApprovedGuildRow) -> Self {
+        Self {
+            guild_id: row.guild_id,
+            approved_by_id: row.approved_by_id,
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+/// Approves a guild, returning `false` when it already was.
+#[tracing::instrument]
+pub async fn approve_guild(
+    pool: &SqlitePool,
+    guild_id: &str,
+    approved_by_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "INSERT INTO approved_guilds (guild_id, approved_by_id)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO NOTHING",
+        guild_id,
+        approved_by_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to approve guild")?;
+
+    let approved = result.rows_affected() > 0;
+    if approved {
+        tracing::info!(guild_id = %guild_id, approved_by_id = %approved_by_id, "Guild approved");
+    }
+
+    Ok(approved)
+}
+
+/// Withdraws a guild's approval, returning `false` when it had none.
+#[tracing::instrument]
+pub async fn revoke_guild(pool: &SqlitePool, guild_id: &str) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM approved_guilds WHERE guild_id = ?", guild_id)
+        .execute(pool)
+        .await
+        .context("Failed to revoke guild approval")?;
+
+    let revoked = result.rows_affected() > 0;
+    if revoked {
+        tracing::info!(guild_id = %guild_id, "Guild approval revoked");
+    }
+
+    Ok(revoked)
+}
+
+#[tracing::instrument]
+pub async fn is_guild_approved(pool: &SqlitePool, guild_id: &str) -> Result<bool> {
+    let approved = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM approved_guilds WHERE guild_id = ?) as "approved!: bool""#,
+        guild_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to check guild approval")?;
+
+    Ok(approved)
+}
+
+#[tracing::instrument]
+pub async fn get_approved_guilds(pool: &SqlitePool) -> Result<Vec<ApprovedGuild>> {
+    let rows = sqlx::query_as!(
+        ApprovedGuildRow,
+        r#"SELECT guild_id as "guild_id!", approved_by_id, created_at
+           FROM approved_guilds
+           ORDER BY created_at, guild_id"#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch approved guilds")?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
@@ -0,0 +1,160 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub guild_id: String,
+    pub actor_id: String,
+    pub actor_name: String,
+    pub action: String,
+    pub target: Option<String>,
+    pub parameters: String,
+    pub outcome: String,
+    pub checked_count: i64,
+    pub affected_count: i64,
+    pub failed_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewAuditEntry<'a> {
+    pub guild_id: &'a str,
+    pub actor_id: &'a str,
+    pub actor_name: &'a str,
+    pub action: &'a str,
+    pub target: Option<&'a str>,
+    pub parameters: &'a str,
+    pub outcome: &'a str,
+    pub checked_count: i64,
+    pub affected_count: i64,
+    pub failed_count: i64,
+}
+
+/// Filters for [`get_audit_entries`]. Unset fields match everything.
+#[derive(Debug, Default)]
+pub struct AuditQuery<'a> {
+    pub actor_id: Option<&'a str>,
+    pub action: Option<&'a str>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<i32>,
+}
+
+struct AuditEntryRow {
+    id: i64,
+    guild_id: String,
+    actor_id: String,
+    actor_name: String,
+    action: String,
+    target: Option<String>,
+    parameters: String,
+    outcome: String,
+    checked_count: i64,
+    affected_count: i64,
+    failed_count: i64,
+    created_at: NaiveDateTime,
+}
+
+impl From<AuditEntryRow> for AuditEntry {
+    fn from(row: AuditEntryRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            actor_id: row.actor_id,
+            actor_name: row.actor_name,
+            action: row.action,
+            target: row.target,
+            parameters: row.parameters,
+            outcome: row.outcome,
+            checked_count: row.checked_count,
+            affected_count: row.affected_count,
+            failed_count: row.failed_count,
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+#[tracing::instrument]
+pub async fn save_audit_entry(pool: &SqlitePool, entry: &NewAuditEntry<'_>) -> Result<i64> {
+    tracing::debug!(
+        guild_id = %entry.guild_id,
+        actor_id = %entry.actor_id,
+        action = %entry.action,
+        "Saving audit entry to database"
+    );
+
+    let result = sqlx::query!(
+        "INSERT INTO moderation_audit
+            (guild_id, actor_id, actor_name, action, target, parameters, outcome,
+             checked_count, affected_count, failed_count)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        entry.guild_id,
+        entry.actor_id,
+        entry.actor_name,
+        entry.action,
+        entry.target,
+        entry.parameters,
+        entry.outcome,
+        entry.checked_count,
+        entry.affected_count,
+        entry.failed_count
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save audit entry")?;
+
+    tracing::info!(
+        audit_id = %result.last_insert_rowid(),
+        guild_id = %entry.guild_id,
+        action = %entry.action,
+        "Audit entry saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_audit_entries(
+    pool: &SqlitePool,
+    guild_id: &str,
+    query: &AuditQuery<'_>,
+) -> Result<Vec<AuditEntry>> {
+    let limit = query.limit.unwrap_or(20);
+    let since = query.since.map(|t| t.naive_utc());
+    let until = query.until.map(|t| t.naive_utc());
+
+    tracing::debug!(guild_id = %guild_id, limit = %limit, "Fetching audit entries");
+
+    let rows = sqlx::query_as!(
+        AuditEntryRow,
+        r#"SELECT id as "id!", guild_id, actor_id, actor_name, action, target, parameters, outcome,
+                  checked_count, affected_count, failed_count, created_at
+           FROM moderation_audit
+           WHERE guild_id = ?
+             AND (? IS NULL OR actor_id = ?)
+             AND (? IS NULL OR action = ?)
+             AND (? IS NULL OR created_at >= ?)
+             AND (? IS NULL OR created_at < ?)
+           ORDER BY created_at DESC, id DESC
+           LIMIT ?"#,
+        guild_id,
+        query.actor_id,
+        query.actor_id,
+        query.action,
+        query.action,
+        since,
+        since,
+        until,
+        until,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch audit entries")?;
+
+    tracing::debug!(count = %rows.len(), "Fetched audit entries successfully");
+    Ok(rows.into_iter().map(AuditEntry::from).collect())
+}
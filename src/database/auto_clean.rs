@@ -0,0 +1,240 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoCleanRule {
+    pub id: i64,
+    pub guild_id: String,
+    pub channel_id: String,
+    pub interval_minutes: i64,
+    pub max_age_minutes: Option<i64>,
+    pub pattern: Option<String>,
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub literal: bool,
+    pub enabled: bool,
+    pub created_by_id: String,
+    pub created_by_name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_checked: Option<i64>,
+    pub last_deleted: Option<i64>,
+    pub last_failed: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct NewAutoCleanRule<'a> {
+    pub guild_id: &'a str,
+    pub channel_id: &'a str,
+    pub interval_minutes: i64,
+    pub max_age_minutes: Option<i64>,
+    pub pattern: Option<&'a str>,
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub literal: bool,
+    pub created_by_id: &'a str,
+    pub created_by_name: &'a str,
+}
+
+struct AutoCleanRuleRow {
+    id: i64,
+    guild_id: String,
+    channel_id: String,
+    interval_minutes: i64,
+    max_age_minutes: Option<i64>,
+    pattern: Option<String>,
+    case_insensitive: bool,
+    multi_line: bool,
+    literal: bool,
+    enabled: bool,
+    created_by_id: String,
+    created_by_name: String,
+    created_at: NaiveDateTime,
+    last_run_at: Option<NaiveDateTime>,
+    last_checked: Option<i64>,
+    last_deleted: Option<i64>,
+    last_failed: Option<i64>,
+    last_error: Option<String>,
+}
+
+impl From<AutoCleanRuleRow> for AutoCleanRule {
+    fn from(row: AutoCleanRuleRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            interval_minutes: row.interval_minutes,
+            max_age_minutes: row.max_age_minutes,
+            pattern: row.pattern,
+            case_insensitive: row.case_insensitive,
+            multi_line: row.multi_line,
+            literal: row.literal,
+            enabled: row.enabled,
+            created_by_id: row.created_by_id,
+            created_by_name: row.created_by_name,
+            created_at: row.created_at.and_utc(),
+            last_run_at: row.last_run_at.map(|t| t.and_utc()),
+            last_checked: row.last_checked,
+            last_deleted: row.last_deleted,
+            last_failed: row.last_failed,
+            last_error: row.last_error,
+        }
+    }
+}
+
+#[tracing::instrument]
+pub async fn save_auto_clean_rule(pool: &SqlitePool, rule: &NewAutoCleanRule<'_>) -> Result<i64> {
+    tracing::debug!(
+        guild_id = %rule.guild_id,
+        channel_id = %rule.channel_id,
+        "Saving auto-clean rule to database"
+    );
+
+    let result = sqlx::query!(
+        "INSERT INTO auto_clean_rules
+            (guild_id, channel_id, interval_minutes, max_age_minutes, pattern,
+             case_insensitive, multi_line, literal, created_by_id, created_by_name)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rule.guild_id,
+        rule.channel_id,
+        rule.interval_minutes,
+        rule.max_age_minutes,
+        rule.pattern,
+        rule.case_insensitive,
+        rule.multi_line,
+        rule.literal,
+        rule.created_by_id,
+        rule.created_by_name
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save auto-clean rule")?;
+
+    tracing::info!(
+        rule_id = %result.last_insert_rowid(),
+        guild_id = %rule.guild_id,
+        channel_id = %rule.channel_id,
+        "Auto-clean rule saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_auto_clean_rules(pool: &SqlitePool, guild_id: &str) -> Result<Vec<AutoCleanRule>> {
+    tracing::debug!(guild_id = %guild_id, "Fetching auto-clean rules");
+
+    let rows = sqlx::query_as!(
+        AutoCleanRuleRow,
+        r#"SELECT id as "id!", guild_id, channel_id, interval_minutes, max_age_minutes, pattern,
+                  case_insensitive, multi_line, literal, enabled, created_by_id, created_by_name,
+                  created_at, last_run_at, last_checked, last_deleted, last_failed, last_error
+           FROM auto_clean_rules
+           WHERE guild_id = ?
+           ORDER BY id"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch auto-clean rules")?;
+
+    Ok(rows.into_iter().map(AutoCleanRule::from).collect())
+}
+
+/// Enabled rules whose interval has elapsed since their last run.
+#[tracing::instrument]
+pub async fn get_due_auto_clean_rules(pool: &SqlitePool) -> Result<Vec<AutoCleanRule>> {
+    let rows = sqlx::query_as!(
+        AutoCleanRuleRow,
+        r#"SELECT id as "id!", guild_id, channel_id, interval_minutes, max_age_minutes, pattern,
+                  case_insensitive, multi_line, literal, enabled, created_by_id, created_by_name,
+                  created_at, last_run_at, last_checked, last_deleted, last_failed, last_error
+           FROM auto_clean_rules
+           WHERE enabled = 1
+             AND (last_run_at IS NULL
+                  OR datetime(last_run_at, '+' || interval_minutes || ' minutes') <= datetime('now'))
+           ORDER BY last_run_at IS NOT NULL, last_run_at"#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch due auto-clean rules")?;
+
+    tracing::debug!(count = %rows.len(), "Fetched due auto-clean rules");
+    Ok(rows.into_iter().map(AutoCleanRule::from).collect())
+}
+
+#[tracing::instrument]
+pub async fn record_auto_clean_run(
+    pool: &SqlitePool,
+    rule_id: i64,
+    checked: i64,
+    deleted: i64,
+    failed: i64,
+    error: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE auto_clean_rules
+         SET last_run_at = CURRENT_TIMESTAMP, last_checked = ?, last_deleted = ?,
+             last_failed = ?, last_error = ?
+         WHERE id = ?",
+        checked,
+        deleted,
+        failed,
+        error,
+        rule_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record auto-clean run")?;
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn set_auto_clean_rule_enabled(
+    pool: &SqlitePool,
+    rule_id: i64,
+    guild_id: &str,
+    enabled: bool,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "UPDATE auto_clean_rules SET enabled = ? WHERE id = ? AND guild_id = ?",
+        enabled,
+        rule_id,
+        guild_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to update auto-clean rule")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[tracing::instrument]
+pub async fn delete_auto_clean_rule(
+    pool: &SqlitePool,
+    rule_id: i64,
+    guild_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM auto_clean_rules WHERE id = ? AND guild_id = ?",
+        rule_id,
+        guild_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete auto-clean rule")?;
+
+    let deleted = result.rows_affected() > 0;
+
+    if deleted {
+        tracing::info!(rule_id = %rule_id, guild_id = %guild_id, "Auto-clean rule deleted");
+    } else {
+        tracing::warn!(rule_id = %rule_id, guild_id = %guild_id, "Auto-clean rule not found");
+    }
+
+    Ok(deleted)
+}
@@ -0,0 +1,192 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomodRule {
+    pub id: i64,
+    pub guild_id: String,
+    pub name: String,
+    pub kind: String,
+    pub pattern: Option<String>,
+    pub threshold: Option<i64>,
+    pub case_insensitive: bool,
+    pub actions: String,
+    pub timeout_minutes: Option<i64>,
+    pub enabled: bool,
+    pub created_by_id: String,
+    pub created_by_name: String,
+    pub created_at: DateTime<Utc>,
+    pub hit_count: i64,
+    pub last_hit_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub struct NewAutomodRule<'a> {
+    pub guild_id: &'a str,
+    pub name: &'a str,
+    pub kind: &'a str,
+    pub pattern: Option<&'a str>,
+    pub threshold: Option<i64>,
+    pub case_insensitive: bool,
+    pub actions: &'a str,
+    pub timeout_minutes: Option<i64>,
+    pub created_by_id: &'a str,
+    pub created_by_name: &'a str,
+}
+
+struct AutomodRuleRow {
+    id: i64,
+    guild_id: String,
+    name: String,
+    kind: String,
+    pattern: Option<String>,
+    threshold: Option<i64>,
+    case_insensitive: bool,
+    actions: String,
+    timeout_minutes: Option<i64>,
+    enabled: bool,
+    created_by_id: String,
+    created_by_name: String,
+    created_at: NaiveDateTime,
+    hit_count: i64,
+    last_hit_at: Option<NaiveDateTime>,
+}
+
+impl From<AutomodRuleRow> for AutomodRule {
+    fn from(row: AutomodRuleRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            name: row.name,
+            kind: row.kind,
+            pattern: row.pattern,
+            threshold: row.threshold,
+            case_insensitive: row.case_insensitive,
+            actions: row.actions,
+            timeout_minutes: row.timeout_minutes,
+            enabled: row.enabled,
+            created_by_id: row.created_by_id,
+            created_by_name: row.created_by_name,
+            created_at: row.created_at.and_utc(),
+            hit_count: row.hit_count,
+            last_hit_at: row.last_hit_at.map(|t| t.and_utc()),
+        }
+    }
+}
+
+#[tracing::instrument]
+pub async fn save_automod_rule(pool: &SqlitePool, rule: &NewAutomodRule<'_>) -> Result<i64> {
+    tracing::debug!(
+        guild_id = %rule.guild_id,
+        name = %rule.name,
+        kind = %rule.kind,
+        "Saving automod rule to database"
+    );
+
+    let result = sqlx::query!(
+        "INSERT INTO automod_rules
+            (guild_id, name, kind, pattern, threshold, case_insensitive, actions,
+             timeout_minutes, created_by_id, created_by_name)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rule.guild_id,
+        rule.name,
+        rule.kind,
+        rule.pattern,
+        rule.threshold,
+        rule.case_insensitive,
+        rule.actions,
+        rule.timeout_minutes,
+        rule.created_by_id,
+        rule.created_by_name
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save automod rule")?;
+
+    tracing::info!(
+        rule_id = %result.last_insert_rowid(),
+        guild_id = %rule.guild_id,
+        "Automod rule saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_automod_rules(pool: &SqlitePool, guild_id: &str) -> Result<Vec<AutomodRule>> {
+    tracing::debug!(guild_id = %guild_id, "Fetching automod rules");
+
+    let rows = sqlx::query_as!(
+        AutomodRuleRow,
+        r#"SELECT id as "id!", guild_id, name, kind, pattern, threshold, case_insensitive, actions,
+                  timeout_minutes, enabled, created_by_id, created_by_name, created_at,
+                  hit_count, last_hit_at
+           FROM automod_rules
+           WHERE guild_id = ?
+           ORDER BY id"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch automod rules")?;
+
+    Ok(rows.into_iter().map(AutomodRule::from).collect())
+}
+
+#[tracing::instrument]
+pub async fn record_automod_hit(pool: &SqlitePool, rule_id: i64) -> Result<()> {
+    sqlx::query!(
+        "UPDATE automod_rules SET hit_count = hit_count + 1, last_hit_at = CURRENT_TIMESTAMP
+         WHERE id = ?",
+        rule_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record automod hit")?;
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn set_automod_rule_enabled(
+    pool: &SqlitePool,
+    rule_id: i64,
+    guild_id: &str,
+    enabled: bool,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "UPDATE automod_rules SET enabled = ? WHERE id = ? AND guild_id = ?",
+        enabled,
+        rule_id,
+        guild_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to update automod rule")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[tracing::instrument]
+pub async fn delete_automod_rule(pool: &SqlitePool, rule_id: i64, guild_id: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM automod_rules WHERE id = ? AND guild_id = ?",
+        rule_id,
+        guild_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete automod rule")?;
+
+    let deleted = result.rows_affected() > 0;
+
+    if deleted {
+        tracing::info!(rule_id = %rule_id, guild_id = %guild_id, "Automod rule deleted");
+    } else {
+        tracing::warn!(rule_id = %rule_id, guild_id = %guild_id, "Automod rule not found");
+    }
+
+    Ok(deleted)
+}
@@ -0,0 +1,211 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A numbered moderation case together with the audit entry it was opened for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationCase {
+    pub id: i64,
+    pub guild_id: String,
+    pub case_number: i64,
+    pub audit_id: i64,
+    pub action: String,
+    pub actor_id: String,
+    pub actor_name: String,
+    pub target: Option<String>,
+    pub parameters: String,
+    pub outcome: String,
+    pub reason: Option<String>,
+    pub reason_updated_by_id: Option<String>,
+    pub reason_updated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseEvidence {
+    pub id: i64,
+    pub case_id: i64,
+    pub url: String,
+    pub added_by_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+struct ModerationCaseRow {
+    id: i64,
+    guild_id: String,
+    case_number: i64,
+    audit_id: i64,
+    action: String,
+    actor_id: String,
+    actor_name: String,
+    target: Option<String>,
+    parameters: String,
+    outcome: String,
+    reason: Option<String>,
+    reason_updated_by_id: Option<String>,
+    reason_updated_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+impl From<ModerationCaseRow> for ModerationCase {
+    fn from(row: ModerationCaseRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            case_number: row.case_number,
+            audit_id: row.audit_id,
+            action: row.action,
+            actor_id: row.actor_id,
+            actor_name: row.actor_name,
+            target: row.target,
+            parameters: row.parameters,
+            outcome: row.outcome,
+            reason: row.reason,
+            reason_updated_by_id: row.reason_updated_by_id,
+            reason_updated_at: row.reason_updated_at.map(|t| t.and_utc()),
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+struct CaseEvidenceRow {
+    id: i64,
+    case_id: i64,
+    url: String,
+    added_by_id: String,
+    created_at: NaiveDateTime,
+}
+
+impl From<CaseEvidenceRow> for CaseEvidence {
+    fn from(row: CaseEvidenceRow) -> Self {
+        Self {
+            id: row.id,
+            case_id: row.case_id,
+            url: row.url,
+            added_by_id: row.added_by_id,
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+/// Opens the next case of `guild_id` for an audit entry and returns its number.
+///
+/// The number is picked inside the insert, so concurrent actions can't share one.
+#[tracing::instrument]
+pub async fn open_case(
+    pool: &SqlitePool,
+    guild_id: &str,
+    audit_id: i64,
+    reason: Option<&str>,
+) -> Result<i64> {
+    let case_number = sqlx::query_scalar!(
+        r#"INSERT INTO moderation_cases (guild_id, case_number, audit_id, reason)
+           VALUES (
+               ?,
+               (SELECT COALESCE(MAX(case_number), 0) + 1 FROM moderation_cases WHERE guild_id = ?),
+               ?,
+               ?
+           )
+           RETURNING case_number as "case_number!""#,
+        guild_id,
+        guild_id,
+        audit_id,
+        reason
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to open moderation case")?;
+
+    tracing::info!(guild_id = %guild_id, case_number = %case_number, audit_id = %audit_id, "Moderation case opened");
+
+    Ok(case_number)
+}
+
+#[tracing::instrument]
+pub async fn get_case(
+    pool: &SqlitePool,
+    guild_id: &str,
+    case_number: i64,
+) -> Result<Option<ModerationCase>> {
+    let row = sqlx::query_as!(
+        ModerationCaseRow,
+        r#"SELECT c.id as "id!", c.guild_id, c.case_number, c.audit_id,
+                  a.action, a.actor_id, a.actor_name, a.target, a.parameters, a.outcome,
+                  c.reason, c.reason_updated_by_id, c.reason_updated_at, c.created_at
+           FROM moderation_cases c
+           JOIN moderation_audit a ON a.id = c.audit_id
+           WHERE c.guild_id = ? AND c.case_number = ?"#,
+        guild_id,
+        case_number
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch moderation case")?;
+
+    Ok(row.map(ModerationCase::from))
+}
+
+/// Replaces the reason of a case, returning `false` when it doesn't exist.
+#[tracing::instrument]
+pub async fn update_case_reason(
+    pool: &SqlitePool,
+    guild_id: &str,
+    case_number: i64,
+    reason: &str,
+    updated_by_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "UPDATE moderation_cases
+         SET reason = ?, reason_updated_by_id = ?, reason_updated_at = CURRENT_TIMESTAMP
+         WHERE guild_id = ? AND case_number = ?",
+        reason,
+        updated_by_id,
+        guild_id,
+        case_number
+    )
+    .execute(pool)
+    .await
+    .context("Failed to update case reason")?;
+
+    tracing::info!(guild_id = %guild_id, case_number = %case_number, "Case reason updated");
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[tracing::instrument]
+pub async fn add_case_evidence(
+    pool: &SqlitePool,
+    case_id: i64,
+    url: &str,
+    added_by_id: &str,
+) -> Result<i64> {
+    let result = sqlx::query!(
+        "INSERT INTO case_evidence (case_id, url, added_by_id) VALUES (?, ?, ?)",
+        case_id,
+        url,
+        added_by_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save case evidence")?;
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_case_evidence(pool: &SqlitePool, case_id: i64) -> Result<Vec<CaseEvidence>> {
+    let rows = sqlx::query_as!(
+        CaseEvidenceRow,
+        r#"SELECT id as "id!", case_id, url, added_by_id, created_at
+           FROM case_evidence
+           WHERE case_id = ?
+           ORDER BY id"#,
+        case_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch case evidence")?;
+
+    Ok(rows.into_iter().map(CaseEvidence::from).collect())
+}
@@ -0,0 +1,195 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelLock {
+    pub id: i64,
+    pub guild_id: String,
+    pub channel_id: String,
+    /// The @everyone overwrite of locks taken before `previous_overwrites` existed.
+    pub previous_allow: Option<String>,
+    pub previous_deny: Option<String>,
+    /// JSON snapshot of every overwrite on the channel before the lock.
+    pub previous_overwrites: Option<String>,
+    pub moderator_id: String,
+    pub moderator_name: String,
+    pub reason: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i64,
+}
+
+#[derive(Debug)]
+pub struct NewChannelLock<'a> {
+    pub guild_id: &'a str,
+    pub channel_id: &'a str,
+    pub previous_overwrites: &'a str,
+    pub moderator_id: &'a str,
+    pub moderator_name: &'a str,
+    pub reason: &'a str,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+struct ChannelLockRow {
+    id: i64,
+    guild_id: String,
+    channel_id: String,
+    previous_allow: Option<String>,
+    previous_deny: Option<String>,
+    previous_overwrites: Option<String>,
+    moderator_id: String,
+    moderator_name: String,
+    reason: String,
+    expires_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    attempts: i64,
+}
+
+impl From<ChannelLockRow> for ChannelLock {
+    fn from(row: ChannelLockRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            previous_allow: row.previous_allow,
+            previous_deny: row.previous_deny,
+            previous_overwrites: row.previous_overwrites,
+            moderator_id: row.moderator_id,
+            moderator_name: row.moderator_name,
+            reason: row.reason,
+            expires_at: row.expires_at.map(|t| t.and_utc()),
+            created_at: row.created_at.and_utc(),
+            attempts: row.attempts,
+        }
+    }
+}
+
+/// Stores the snapshot taken before locking a channel.
+///
+/// Returns `None` when the channel is already locked, so the original snapshot is never
+/// overwritten by one taken while the lock was in place.
+#[tracing::instrument]
+pub async fn save_channel_lock(
+    pool: &SqlitePool,
+    lock: &NewChannelLock<'_>,
+) -> Result<Option<i64>> {
+    let expires_at = lock.expires_at.map(|t| t.naive_utc());
+
+    let result = sqlx::query!(
+        "INSERT OR IGNORE INTO channel_locks
+            (guild_id, channel_id, previous_overwrites, moderator_id, moderator_name, reason,
+             expires_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        lock.guild_id,
+        lock.channel_id,
+        lock.previous_overwrites,
+        lock.moderator_id,
+        lock.moderator_name,
+        lock.reason,
+        expires_at
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save channel lock")?;
+
+    if result.rows_affected() == 0 {
+        tracing::debug!(channel_id = %lock.channel_id, "Channel is already locked");
+        return Ok(None);
+    }
+
+    tracing::info!(
+        lock_id = %result.last_insert_rowid(),
+        guild_id = %lock.guild_id,
+        channel_id = %lock.channel_id,
+        expires_at = ?lock.expires_at,
+        "Channel lock saved successfully"
+    );
+
+    Ok(Some(result.last_insert_rowid()))
+}
+
+/// Channels of `guild_id` that are currently locked, oldest first.
+#[tracing::instrument]
+pub async fn get_active_channel_locks(
+    pool: &SqlitePool,
+    guild_id: &str,
+) -> Result<Vec<ChannelLock>> {
+    let rows = sqlx::query_as!(
+        ChannelLockRow,
+        r#"SELECT id as "id!", guild_id, channel_id, previous_allow, previous_deny,
+                  previous_overwrites, moderator_id, moderator_name, reason, expires_at,
+                  created_at, attempts
+           FROM channel_locks
+           WHERE guild_id = ? AND unlocked_at IS NULL
+           ORDER BY id"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch active channel locks")?;
+
+    Ok(rows.into_iter().map(ChannelLock::from).collect())
+}
+
+/// Active locks whose expiry has passed, oldest first.
+#[tracing::instrument]
+pub async fn get_due_channel_locks(pool: &SqlitePool) -> Result<Vec<ChannelLock>> {
+    let rows = sqlx::query_as!(
+        ChannelLockRow,
+        r#"SELECT id as "id!", guild_id, channel_id, previous_allow, previous_deny,
+                  previous_overwrites, moderator_id, moderator_name, reason, expires_at,
+                  created_at, attempts
+           FROM channel_locks
+           WHERE unlocked_at IS NULL AND expires_at IS NOT NULL
+             AND datetime(expires_at) <= datetime('now')
+           ORDER BY expires_at"#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch due channel locks")?;
+
+    tracing::debug!(count = %rows.len(), "Fetched due channel locks");
+    Ok(rows.into_iter().map(ChannelLock::from).collect())
+}
+
+/// Marks a lock as finished. `error` is kept when its snapshot could not be restored.
+#[tracing::instrument]
+pub async fn mark_channel_unlocked(
+    pool: &SqlitePool,
+    lock_id: i64,
+    error: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE channel_locks SET unlocked_at = CURRENT_TIMESTAMP, last_error = ? WHERE id = ?",
+        error,
+        lock_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to mark channel as unlocked")?;
+
+    Ok(())
+}
+
+/// Records a failed attempt to restore a snapshot and returns the attempt count.
+#[tracing::instrument]
+pub async fn record_channel_unlock_failure(
+    pool: &SqlitePool,
+    lock_id: i64,
+    error: &str,
+) -> Result<i64> {
+    let attempts = sqlx::query_scalar!(
+        "UPDATE channel_locks SET attempts = attempts + 1, last_error = ?
+         WHERE id = ?
+         RETURNING attempts",
+        error,
+        lock_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to record channel unlock failure")?;
+
+    Ok(attempts)
+}
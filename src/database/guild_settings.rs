@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildSettings {
+    pub guild_id: String,
+    pub mod_log_channel_id: Option<String>,
+    pub message_log_channel_id: Option<String>,
+    /// Overrides the configured command prefix.
+    pub prefix: Option<String>,
+    pub locale: Option<String>,
+    /// Where new suggestions are announced.
+    pub suggestion_channel_id: Option<String>,
+    /// Comma separated names of the command modules turned off in the guild.
+    pub disabled_modules: String,
+}
+
+#[tracing::instrument]
+pub async fn get_guild_settings(
+    pool: &SqlitePool,
+    guild_id: &str,
+) -> Result<Option<GuildSettings>> {
+    let settings = sqlx::query_as!(
+        GuildSettings,
+        r#"SELECT guild_id as "guild_id!", mod_log_channel_id, message_log_channel_id,
+                  prefix, locale, suggestion_channel_id, disabled_modules
+           FROM guild_settings
+           WHERE guild_id = ?"#,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch guild settings")?;
+
+    Ok(settings)
+}
+
+#[tracing::instrument]
+pub async fn set_mod_log_channel(
+    pool: &SqlitePool,
+    guild_id: &str,
+    channel_id: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO guild_settings (guild_id, mod_log_channel_id)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            mod_log_channel_id = excluded.mod_log_channel_id,
+            updated_at = CURRENT_TIMESTAMP",
+        guild_id,
+        channel_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save mod-log channel")?;
+
+    tracing::info!(guild_id = %guild_id, channel_id = ?channel_id, "Mod-log channel updated");
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn set_message_log_channel(
+    pool: &SqlitePool,
+    guild_id: &str,
+    channel_id: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO guild_settings (guild_id, message_log_channel_id)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            message_log_channel_id = excluded.message_log_channel_id,
+            updated_at = CURRENT_TIMESTAMP",
+        guild_id,
+        channel_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save message log channel")?;
+
+    tracing::info!(guild_id = %guild_id, channel_id = ?channel_id, "Message log channel updated");
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn set_guild_prefix(
+    pool: &SqlitePool,
+    guild_id: &str,
+    prefix: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO guild_settings (guild_id, prefix)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            prefix = excluded.prefix,
+            updated_at = CURRENT_TIMESTAMP",
+        guild_id,
+        prefix
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save command prefix")?;
+
+    tracing::info!(guild_id = %guild_id, prefix = ?prefix, "Command prefix updated");
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn set_guild_locale(
+    pool: &SqlitePool,
+    guild_id: &str,
+    locale: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO guild_settings (guild_id, locale)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            locale = excluded.locale,
+            updated_at = CURRENT_TIMESTAMP",
+        guild_id,
+        locale
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save locale")?;
+
+    tracing::info!(guild_id = %guild_id, locale = ?locale, "Locale updated");
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn set_suggestion_channel(
+    pool: &SqlitePool,
+    guild_id: &str,
+    channel_id: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO guild_settings (guild_id, suggestion_channel_id)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            suggestion_channel_id = excluded.suggestion_channel_id,
+            updated_at = CURRENT_TIMESTAMP",
+        guild_id,
+        channel_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save suggestion channel")?;
+
+    tracing::info!(guild_id = %guild_id, channel_id = ?channel_id, "Suggestion channel updated");
+
+    Ok(())
+}
+
+/// Stores the comma separated names of the modules turned off in a guild.
+#[tracing::instrument]
+pub async fn set_disabled_modules(
+    pool: &SqlitePool,
+    guild_id: &str,
+    disabled_modules: &str,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO guild_settings (guild_id, disabled_modules)
+         VALUES (?, ?)
+         ON CONFLICT (guild_id) DO UPDATE SET
+            disabled_modules = excluded.disabled_modules,
+            updated_at = CURRENT_TIMESTAMP",
+        guild_id,
+        disabled_modules
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save disabled modules")?;
+
+    tracing::info!(guild_id = %guild_id, disabled_modules = %disabled_modules, "Disabled modules updated");
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use super::{GameSuggestion, SongSuggestion};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use sqlx::migrate::{Migrate, Migrator};
+use std::collections::HashSet;
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Version written to suggestion exports, bumped when the format changes.
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+}
+
+/// Every song and game suggestion, as written by `bearobot export`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuggestionExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub songs: Vec<SongSuggestion>,
+    pub games: Vec<GameSuggestion>,
+}
+
+/// Suggestions added and skipped by [`import_suggestions`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportCounts {
+    pub songs: usize,
+    pub games: usize,
+    pub skipped: usize,
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
+    tracing::info!("Running database migrations");
+    MIGRATOR
+        .run(pool)
+        .await
+        .context("Failed to run database migrations")?;
+
+    Ok(())
+}
+
+/// Lists the migrations built into the binary and whether each has been applied.
+#[tracing::instrument(skip(pool))]
+pub async fn migration_status(pool: &SqlitePool) -> Result<Vec<MigrationStatus>> {
+    let mut connection = pool
+        .acquire()
+        .await
+        .context("Failed to connect to database")?;
+    connection
+        .ensure_migrations_table()
+        .await
+        .context("Failed to create migrations table")?;
+    let applied: HashSet<i64> = connection
+        .list_applied_migrations()
+        .await
+        .context("Failed to list applied migrations")?
+        .into_iter()
+        .map(|migration| migration.version)
+        .collect();
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|migration| migration.migration_type.is_up_migration())
+        .map(|migration| MigrationStatus {
+            version: migration.version,
+            description: migration.description.to_string(),
+            applied: applied.contains(&migration.version),
+        })
+        .collect())
+}
+
+#[tracing::instrument(skip(pool))]
+pub async fn export_suggestions(pool: &SqlitePool) -> Result<SuggestionExport> {
+    let songs = sqlx::query!(
+        r#"SELECT id as "id!", song_name, artist, suggested_by_id, suggested_by_name, created_at
+           FROM song_suggestions ORDER BY id"#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch song suggestions")?
+    .into_iter()
+    .map(|row| SongSuggestion {
+        id: row.id,
+        song_name: row.song_name,
+        artist: row.artist,
+        suggested_by_id: row.suggested_by_id,
+        suggested_by_name: row.suggested_by_name,
+        created_at: row.created_at.and_utc(),
+    })
+    .collect();
+
+    let games = sqlx::query!(
+        r#"SELECT id as "id!", game_name, developer, suggested_by_id, suggested_by_name, created_at
+           FROM game_suggestions ORDER BY id"#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch game suggestions")?
+    .into_iter()
+    .map(|row| GameSuggestion {
+        id: row.id,
+        game_name: row.game_name,
+        developer: row.developer,
+        suggested_by_id: row.suggested_by_id,
+        suggested_by_name: row.suggested_by_name,
+        created_at: row.created_at.and_utc(),
+    })
+    .collect();
+
+    Ok(SuggestionExport {
+        version: EXPORT_VERSION,
+        exported_at: Utc::now(),
+        songs,
+        games,
+    })
+}
+
+/// Adds the suggestions of an export in one transaction, keeping their original dates.
+///
+/// Ids are reassigned. A suggestion already present with the same text, author and date is
+/// skipped, so importing the same file twice is harmless.
+#[tracing::instrument(skip(pool, export))]
+pub async fn import_suggestions(
+    pool: &SqlitePool,
+    export: &SuggestionExport,
+) -> Result<ImportCounts> {
+    if export.version != EXPORT_VERSION {
+        bail!(
+            "Unsupported export version {}, expected {}",
+            export.version,
+            EXPORT_VERSION
+        );
+    }
+
+    let mut counts = ImportCounts::default();
+    let mut tx = pool.begin().await.context("Failed to start import")?;
+
+    for song in &export.songs {
+        let created_at = song.created_at.naive_utc();
+        let result = sqlx::query!(
+            "INSERT INTO song_suggestions
+                (song_name, artist, suggested_by_id, suggested_by_name, created_at)
+             SELECT ?, ?, ?, ?, ?
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM song_suggestions
+                 WHERE song_name = ? AND artist = ? AND suggested_by_id = ? AND created_at = ?
+             )",
+            song.song_name,
+            song.artist,
+            song.suggested_by_id,
+            song.suggested_by_name,
+            created_at,
+            song.song_name,
+            song.artist,
+            song.suggested_by_id,
+            created_at
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to import song suggestion")?;
+
+        match result.rows_affected() {
+            0 => counts.skipped += 1,
+            _ => counts.songs += 1,
+        }
+    }
+
+    for game in &export.games {
+        let created_at = game.created_at.naive_utc();
+        let result = sqlx::query!(
+            "INSERT INTO game_suggestions
+                (game_name, developer, suggested_by_id, suggested_by_name, created_at)
+             SELECT ?, ?, ?, ?, ?
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM game_suggestions
+                 WHERE game_name = ? AND developer = ? AND suggested_by_id = ? AND created_at = ?
+             )",
+            game.game_name,
+            game.developer,
+            game.suggested_by_id,
+            game.suggested_by_name,
+            created_at,
+            game.game_name,
+            game.developer,
+            game.suggested_by_id,
+            created_at
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to import game suggestion")?;
+
+        match result.rows_affected() {
+            0 => counts.skipped += 1,
+            _ => counts.games += 1,
+        }
+    }
+
+    tx.commit().await.context("Failed to commit import")?;
+
+    tracing::info!(
+        songs = %counts.songs,
+        games = %counts.games,
+        skipped = %counts.skipped,
+        "Suggestions imported"
+    );
+
+    Ok(counts)
+}
@@ -0,0 +1,56 @@
+use anyhow::{Context, Result};
+use sqlx::SqlitePool;
+
+/// Channels of `guild_id` whose edits and deletions are logged.
+#[tracing::instrument]
+pub async fn get_message_log_channels(pool: &SqlitePool, guild_id: &str) -> Result<Vec<String>> {
+    let channels = sqlx::query_scalar!(
+        "SELECT channel_id FROM message_log_channels WHERE guild_id = ? ORDER BY created_at",
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch message log channels")?;
+
+    Ok(channels)
+}
+
+/// Starts logging `channel_id`, returning `false` if it was already logged.
+#[tracing::instrument]
+pub async fn add_message_log_channel(
+    pool: &SqlitePool,
+    guild_id: &str,
+    channel_id: &str,
+    added_by_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "INSERT OR IGNORE INTO message_log_channels (guild_id, channel_id, added_by_id)
+         VALUES (?, ?, ?)",
+        guild_id,
+        channel_id,
+        added_by_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to add message log channel")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[tracing::instrument]
+pub async fn remove_message_log_channel(
+    pool: &SqlitePool,
+    guild_id: &str,
+    channel_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM message_log_channels WHERE guild_id = ? AND channel_id = ?",
+        guild_id,
+        channel_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to remove message log channel")?;
+
+    Ok(result.rows_affected() > 0)
+}
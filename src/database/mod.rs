@@ -0,0 +1,1089 @@
+mod antispam;
+mod approved_guilds;
+mod audit;
+mod auto_clean;
+mod automod;
+mod cases;
+mod channel_locks;
+mod guild_settings;
+mod maintenance;
+mod message_log;
+mod permission_rules;
+mod role_menus;
+mod temporary_bans;
+mod user_settings;
+mod warnings;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, migrate::MigrateDatabase};
+
+pub use antispam::*;
+pub use approved_guilds::*;
+pub use audit::*;
+pub use auto_clean::*;
+pub use automod::*;
+pub use cases::*;
+pub use channel_locks::*;
+pub use guild_settings::*;
+pub use maintenance::*;
+pub use message_log::*;
+pub use permission_rules::*;
+pub use role_menus::*;
+pub use temporary_bans::*;
+pub use user_settings::*;
+pub use warnings::*;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SongSuggestion {
+    pub id: i64,
+    pub song_name: String,
+    pub artist: String,
+    pub suggested_by_id: String,
+    pub suggested_by_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSuggestion {
+    pub id: i64,
+    pub game_name: String,
+    pub developer: String,
+    pub suggested_by_id: String,
+    pub suggested_by_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Opens the database, creating the file if needed, and applies pending migrations.
+#[tracing::instrument]
+pub async fn init_database(database_path: &str) -> Result<SqlitePool> {
+    let pool = connect_database(database_path).await?;
+    run_migrations(&pool).await?;
+
+    tracing::info!("Database initialized successfully with all migrations applied");
+    Ok(pool)
+}
+
+/// Opens the database without migrating it, creating the file if needed.
+#[tracing::instrument]
+pub async fn connect_database(database_path: &str) -> Result<SqlitePool> {
+    tracing::info!("Initializing database at: {}", database_path);
+
+    if !sqlx::Sqlite::database_exists(database_path)
+        .await
+        .unwrap_or(false)
+    {
+        tracing::info!("Database doesn't exist, creating: {}", database_path);
+        sqlx::Sqlite::create_database(database_path)
+            .await
+            .with_context(|| format!("Failed to create database at {}", database_path))?;
+    }
+
+    let database_url = if database_path.starts_with("sqlite://") {
+        database_path.to_string()
+    } else {
+        format!("sqlite://{}", database_path)
+    };
+
+    let pool = SqlitePool::connect(&database_url)
+        .await
+        .with_context(|| format!("Failed to connect to database at {}", database_path))?;
+
+    Ok(pool)
+}
+
+#[tracing::instrument]
+pub async fn save_song_suggestion(
+    pool: &SqlitePool,
+    song_name: &str,
+    artist: &str,
+    suggested_by_id: &str,
+    suggested_by_name: &str,
+) -> Result<i64> {
+    tracing::debug!(
+        song_name = %song_name,
+        artist = %artist,
+        user_id = %suggested_by_id,
+        user_name = %suggested_by_name,
+        "Saving song suggestion to database"
+    );
+
+    let result = sqlx::query!(
+        "INSERT INTO song_suggestions (song_name, artist, suggested_by_id, suggested_by_name) VALUES (?, ?, ?, ?)",
+        song_name,
+        artist,
+        suggested_by_id,
+        suggested_by_name
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save song suggestion")?;
+
+    tracing::info!(
+        suggestion_id = %result.last_insert_rowid(),
+        song_name = %song_name,
+        artist = %artist,
+        "Song suggestion saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn save_game_suggestion(
+    pool: &SqlitePool,
+    game_name: &str,
+    developer: &str,
+    suggested_by_id: &str,
+    suggested_by_name: &str,
+) -> Result<i64> {
+    tracing::debug!(
+        game_name = %game_name,
+        developer = %developer,
+        user_id = %suggested_by_id,
+        user_name = %suggested_by_name,
+        "Saving game suggestion to database"
+    );
+
+    let result = sqlx::query!(
+        "INSERT INTO game_suggestions (game_name, developer, suggested_by_id, suggested_by_name) VALUES (?, ?, ?, ?)",
+        game_name,
+        developer,
+        suggested_by_id,
+        suggested_by_name
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save game suggestion")?;
+
+    tracing::info!(
+        suggestion_id = %result.last_insert_rowid(),
+        song_name = %game_name,
+        artist = %developer,
+        "Game suggestion saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_song_suggestions(
+    pool: &SqlitePool,
+    limit: Option<i32>,
+) -> Result<Vec<SongSuggestion>> {
+    let limit = limit.unwrap_or(50);
+
+    tracing::debug!(limit = %limit, "Fetching song suggestions from database");
+
+    let rows = sqlx::query!(
+        "SELECT id, song_name, artist, suggested_by_id, suggested_by_name, created_at 
+         FROM song_suggestions ORDER BY created_at DESC LIMIT ?",
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch song suggestions")?;
+
+    let suggestions: Vec<SongSuggestion> = rows
+        .into_iter()
+        .map(|row| SongSuggestion {
+            id: row.id.unwrap_or(0),
+            song_name: row.song_name,
+            artist: row.artist,
+            suggested_by_id: row.suggested_by_id,
+            suggested_by_name: row.suggested_by_name,
+            created_at: DateTime::from_timestamp(row.created_at.and_utc().timestamp(), 0)
+                .unwrap_or_else(Utc::now),
+        })
+        .collect();
+
+    tracing::debug!(count = %suggestions.len(), "Fetched song suggestions successfully");
+    Ok(suggestions)
+}
+
+#[tracing::instrument]
+pub async fn get_game_suggestions(
+    pool: &SqlitePool,
+    limit: Option<i32>,
+) -> Result<Vec<GameSuggestion>> {
+    let limit = limit.unwrap_or(50);
+
+    tracing::debug!(limit = %limit, "Fetching game suggestions from database");
+
+    let rows = sqlx::query!(
+        "SELECT id, game_name, developer, suggested_by_id, suggested_by_name, created_at 
+         FROM game_suggestions ORDER BY created_at DESC LIMIT ?",
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch game suggestions")?;
+
+    let suggestions: Vec<GameSuggestion> = rows
+        .into_iter()
+        .map(|row| GameSuggestion {
+            id: row.id.unwrap_or(0),
+            game_name: row.game_name,
+            developer: row.developer,
+            suggested_by_id: row.suggested_by_id,
+            suggested_by_name: row.suggested_by_name,
+            created_at: DateTime::from_timestamp(row.created_at.and_utc().timestamp(), 0)
+                .unwrap_or_else(Utc::now),
+        })
+        .collect();
+
+    tracing::debug!(count = %suggestions.len(), "Fetched game suggestions successfully");
+    Ok(suggestions)
+}
+
+#[tracing::instrument]
+pub async fn get_song_suggestions_by_user(
+    pool: &SqlitePool,
+    user_id: &str,
+    limit: Option<i32>,
+) -> Result<Vec<SongSuggestion>> {
+    let limit = limit.unwrap_or(10);
+
+    tracing::debug!(user_id = %user_id, limit = %limit, "Fetching song suggestions by user");
+
+    let rows = sqlx::query!(
+        "SELECT id, song_name, artist, suggested_by_id, suggested_by_name, created_at
+         FROM song_suggestions
+         WHERE suggested_by_id = ?
+         ORDER BY created_at DESC
+         LIMIT ?",
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch song suggestions by user")?;
+
+    let suggestions: Vec<SongSuggestion> = rows
+        .into_iter()
+        .map(|row| SongSuggestion {
+            id: row.id.unwrap_or(0),
+            song_name: row.song_name,
+            artist: row.artist,
+            suggested_by_id: row.suggested_by_id,
+            suggested_by_name: row.suggested_by_name,
+            created_at: DateTime::from_timestamp(row.created_at.and_utc().timestamp(), 0)
+                .unwrap_or_else(Utc::now),
+        })
+        .collect();
+
+    tracing::info!(
+        user_id = %user_id,
+        count = %suggestions.len(),
+        "Fetched user song suggestions successfully"
+    );
+
+    Ok(suggestions)
+}
+
+#[tracing::instrument]
+pub async fn get_game_suggestions_by_user(
+    pool: &SqlitePool,
+    user_id: &str,
+    limit: Option<i32>,
+) -> Result<Vec<GameSuggestion>> {
+    let limit = limit.unwrap_or(10);
+
+    tracing::debug!(user_id = %user_id, limit = %limit, "Fetching game suggestions by user");
+
+    let rows = sqlx::query!(
+        "SELECT id, game_name, developer, suggested_by_id, suggested_by_name, created_at
+         FROM game_suggestions
+         WHERE suggested_by_id = ?
+         ORDER BY created_at DESC
+         LIMIT ?",
+        user_id,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch game suggestions by user")?;
+
+    let suggestions: Vec<GameSuggestion> = rows
+        .into_iter()
+        .map(|row| GameSuggestion {
+            id: row.id.unwrap_or(0),
+            game_name: row.game_name,
+            developer: row.developer,
+            suggested_by_id: row.suggested_by_id,
+            suggested_by_name: row.suggested_by_name,
+            created_at: DateTime::from_timestamp(row.created_at.and_utc().timestamp(), 0)
+                .unwrap_or_else(Utc::now),
+        })
+        .collect();
+
+    tracing::info!(
+        user_id = %user_id,
+        count = %suggestions.len(),
+        "Fetched user game suggestions successfully"
+    );
+
+    Ok(suggestions)
+}
+
+#[tracing::instrument]
+pub async fn delete_song_suggestion(
+    pool: &SqlitePool,
+    suggestion_id: i64,
+    user_id: &str,
+) -> Result<bool> {
+    tracing::debug!(
+        suggestion_id = %suggestion_id,
+        user_id = %user_id,
+        "Attempting to delete song suggestion"
+    );
+
+    let result = sqlx::query!(
+        "DELETE FROM song_suggestions WHERE id = ? AND suggested_by_id = ?",
+        suggestion_id,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete song suggestion")?;
+
+    let deleted = result.rows_affected() > 0;
+
+    if deleted {
+        tracing::info!(
+            suggestion_id = %suggestion_id,
+            user_id = %user_id,
+            "Song suggestion deleted successfully"
+        );
+    } else {
+        tracing::warn!(
+            suggestion_id = %suggestion_id,
+            user_id = %user_id,
+            "Song suggestion not found or user not authorized to delete"
+        );
+    }
+
+    Ok(deleted)
+}
+
+#[tracing::instrument]
+pub async fn delete_game_suggestion(
+    pool: &SqlitePool,
+    suggestion_id: i64,
+    user_id: &str,
+) -> Result<bool> {
+    tracing::debug!(
+        suggestion_id = %suggestion_id,
+        user_id = %user_id,
+        "Attempting to delete game suggestion"
+    );
+
+    let result = sqlx::query!(
+        "DELETE FROM game_suggestions WHERE id = ? AND suggested_by_id = ?",
+        suggestion_id,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete song suggestion")?;
+
+    let deleted = result.rows_affected() > 0;
+
+    if deleted {
+        tracing::info!(
+            suggestion_id = %suggestion_id,
+            user_id = %user_id,
+            "Game suggestion deleted successfully"
+        );
+    } else {
+        tracing::warn!(
+            suggestion_id = %suggestion_id,
+            user_id = %user_id,
+            "Game suggestion not found or user not authorized to delete"
+        );
+    }
+
+    Ok(deleted)
+}
+
+/// Deletes a song suggestion whoever made it, for curators.
+#[tracing::instrument]
+pub async fn remove_song_suggestion(pool: &SqlitePool, suggestion_id: i64) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM song_suggestions WHERE id = ?", suggestion_id)
+        .execute(pool)
+        .await
+        .context("Failed to remove song suggestion")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Deletes a game suggestion whoever made it, for curators.
+#[tracing::instrument]
+pub async fn remove_game_suggestion(pool: &SqlitePool, suggestion_id: i64) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM game_suggestions WHERE id = ?", suggestion_id)
+        .execute(pool)
+        .await
+        .context("Failed to remove game suggestion")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_database_operations() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let suggestion_id =
+            save_song_suggestion(&pool, "Test Song", "Test Artist", "123456789", "TestUser")
+                .await?;
+
+        assert!(suggestion_id > 0);
+
+        let suggestions = get_song_suggestions(&pool, None).await?;
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].song_name, "Test Song");
+
+        let user_suggestions = get_song_suggestions_by_user(&pool, "123456789", None).await?;
+        assert_eq!(user_suggestions.len(), 1);
+
+        let deleted = delete_song_suggestion(&pool, suggestion_id, "123456789").await?;
+        assert!(deleted);
+
+        let suggestions_after_delete = get_song_suggestions(&pool, None).await?;
+        assert_eq!(suggestions_after_delete.len(), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auto_clean_rules() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let rule_id = save_auto_clean_rule(
+            &pool,
+            &NewAutoCleanRule {
+                guild_id: "1",
+                channel_id: "2",
+                interval_minutes: 360,
+                max_age_minutes: Some(1440),
+                pattern: None,
+                case_insensitive: false,
+                multi_line: false,
+                literal: false,
+                created_by_id: "123456789",
+                created_by_name: "TestUser",
+            },
+        )
+        .await?;
+
+        let due = get_due_auto_clean_rules(&pool).await?;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, rule_id);
+
+        record_auto_clean_run(&pool, rule_id, 10, 4, 0, None).await?;
+        assert!(get_due_auto_clean_rules(&pool).await?.is_empty());
+
+        let rules = get_auto_clean_rules(&pool, "1").await?;
+        assert_eq!(rules[0].last_deleted, Some(4));
+        assert!(rules[0].last_run_at.is_some());
+
+        assert!(!delete_auto_clean_rule(&pool, rule_id, "other guild").await?);
+        assert!(delete_auto_clean_rule(&pool, rule_id, "1").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_audit_entries() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        for (actor_id, action) in [("1", "purge"), ("2", "purge"), ("1", "auto_clean")] {
+            save_audit_entry(
+                &pool,
+                &NewAuditEntry {
+                    guild_id: "42",
+                    actor_id,
+                    actor_name: "TestUser",
+                    action,
+                    target: Some("<#7>"),
+                    parameters: "{}",
+                    outcome: "success",
+                    checked_count: 10,
+                    affected_count: 2,
+                    failed_count: 0,
+                },
+            )
+            .await?;
+        }
+
+        let all = get_audit_entries(&pool, "42", &AuditQuery::default()).await?;
+        assert_eq!(all.len(), 3);
+
+        let by_actor = AuditQuery {
+            actor_id: Some("1"),
+            ..Default::default()
+        };
+        assert_eq!(get_audit_entries(&pool, "42", &by_actor).await?.len(), 2);
+
+        let by_action = AuditQuery {
+            actor_id: Some("1"),
+            action: Some("purge"),
+            ..Default::default()
+        };
+        assert_eq!(get_audit_entries(&pool, "42", &by_action).await?.len(), 1);
+
+        let future = AuditQuery {
+            since: Some(Utc::now() + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(get_audit_entries(&pool, "42", &future).await?.is_empty());
+
+        let past = AuditQuery {
+            until: Some(Utc::now() + chrono::Duration::days(1)),
+            since: Some(Utc::now() - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert_eq!(get_audit_entries(&pool, "42", &past).await?.len(), 3);
+
+        assert!(
+            get_audit_entries(&pool, "other", &AuditQuery::default())
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_warnings() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let first = save_warning(&pool, "42", "7", "Target", "1", "Mod", "spam").await?;
+        save_warning(&pool, "42", "7", "Target", "1", "Mod", "more spam").await?;
+        save_warning(&pool, "42", "8", "Other", "1", "Mod", "rude").await?;
+
+        assert_eq!(count_active_warnings(&pool, "42", "7").await?, 2);
+
+        let revoked = revoke_warning(&pool, "42", first, "1").await?;
+        assert!(revoked.is_some_and(|w| !w.active));
+        assert!(revoke_warning(&pool, "42", first, "1").await?.is_none());
+        assert!(
+            revoke_warning(&pool, "other", first + 1, "1")
+                .await?
+                .is_none()
+        );
+
+        assert_eq!(count_active_warnings(&pool, "42", "7").await?, 1);
+        assert_eq!(get_warnings(&pool, "42", "7", false).await?.len(), 1);
+        assert_eq!(get_warnings(&pool, "42", "7", true).await?.len(), 2);
+
+        set_warning_escalation(&pool, "42", 3, "timeout", Some(60), "1").await?;
+        set_warning_escalation(&pool, "42", 3, "kick", None, "1").await?;
+
+        let escalation = get_warning_escalation(&pool, "42", 3)
+            .await?
+            .expect("escalation should exist");
+        assert_eq!(escalation.action, "kick");
+        assert_eq!(escalation.duration_minutes, None);
+        assert_eq!(get_warning_escalations(&pool, "42").await?.len(), 1);
+
+        assert!(delete_warning_escalation(&pool, "42", 3).await?);
+        assert!(get_warning_escalation(&pool, "42", 3).await?.is_none());
+
+        // Each threshold fires once per member, even after a warning is revoked and re-issued.
+        assert!(mark_warning_escalation_fired(&pool, "42", "7", 3, first).await?);
+        assert!(!mark_warning_escalation_fired(&pool, "42", "7", 3, first + 3).await?);
+        assert!(mark_warning_escalation_fired(&pool, "42", "8", 3, first + 2).await?);
+        assert!(mark_warning_escalation_fired(&pool, "42", "7", 4, first + 3).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_temporary_bans() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let ban = |user_id, expires_at| NewTemporaryBan {
+            guild_id: "42",
+            user_id,
+            user_name: "Target",
+            moderator_id: "1",
+            moderator_name: "Mod",
+            reason: "spam",
+            expires_at,
+        };
+
+        let expired =
+            save_temporary_ban(&pool, &ban("7", Utc::now() - chrono::Duration::hours(1))).await?;
+        save_temporary_ban(&pool, &ban("8", Utc::now() + chrono::Duration::days(1))).await?;
+
+        let due = get_due_temporary_bans(&pool).await?;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, expired);
+
+        assert_eq!(
+            record_temporary_ban_failure(&pool, expired, "boom").await?,
+            1
+        );
+        assert_eq!(
+            record_temporary_ban_failure(&pool, expired, "boom").await?,
+            2
+        );
+
+        mark_temporary_ban_lifted(&pool, expired, None).await?;
+        assert!(get_due_temporary_bans(&pool).await?.is_empty());
+
+        // A new ban replaces the pending one instead of stacking.
+        save_temporary_ban(&pool, &ban("8", Utc::now() - chrono::Duration::minutes(1))).await?;
+        assert_eq!(get_due_temporary_bans(&pool).await?.len(), 1);
+
+        assert_eq!(cancel_temporary_bans(&pool, "42", "8").await?, 1);
+        assert!(get_due_temporary_bans(&pool).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_automod_rules() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let rule_id = save_automod_rule(
+            &pool,
+            &NewAutomodRule {
+                guild_id: "42",
+                name: "invites",
+                kind: "links",
+                pattern: Some("discord.gg"),
+                threshold: None,
+                case_insensitive: false,
+                actions: "delete,log",
+                timeout_minutes: None,
+                created_by_id: "1",
+                created_by_name: "Mod",
+            },
+        )
+        .await?;
+
+        record_automod_hit(&pool, rule_id).await?;
+        record_automod_hit(&pool, rule_id).await?;
+
+        let rules = get_automod_rules(&pool, "42").await?;
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].hit_count, 2);
+        assert!(rules[0].last_hit_at.is_some());
+
+        assert!(!set_automod_rule_enabled(&pool, rule_id, "other", false).await?);
+        assert!(set_automod_rule_enabled(&pool, rule_id, "42", false).await?);
+        assert!(!get_automod_rules(&pool, "42").await?[0].enabled);
+
+        assert!(delete_automod_rule(&pool, rule_id, "42").await?);
+        assert!(get_automod_rules(&pool, "42").await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_antispam_settings() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        assert!(get_antispam_settings(&pool, "42").await?.is_none());
+
+        let mut settings = crate::antispam::default_settings(serenity::all::GuildId::new(42));
+        save_antispam_settings(&pool, &settings, "1").await?;
+
+        settings.enabled = true;
+        settings.flood_messages = 3;
+        save_antispam_settings(&pool, &settings, "1").await?;
+
+        assert_eq!(get_antispam_settings(&pool, "42").await?, Some(settings));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_guild_settings() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        assert!(get_guild_settings(&pool, "42").await?.is_none());
+
+        set_mod_log_channel(&pool, "42", Some("100")).await?;
+        let settings = get_guild_settings(&pool, "42").await?.unwrap();
+        assert_eq!(settings.mod_log_channel_id.as_deref(), Some("100"));
+
+        set_mod_log_channel(&pool, "42", None).await?;
+        let settings = get_guild_settings(&pool, "42").await?.unwrap();
+        assert!(settings.mod_log_channel_id.is_none());
+
+        set_guild_prefix(&pool, "42", Some("!")).await?;
+        set_guild_locale(&pool, "42", Some("de")).await?;
+        set_suggestion_channel(&pool, "42", Some("300")).await?;
+        let settings = get_guild_settings(&pool, "42").await?.unwrap();
+        assert_eq!(settings.prefix.as_deref(), Some("!"));
+        assert_eq!(settings.locale.as_deref(), Some("de"));
+        assert_eq!(settings.suggestion_channel_id.as_deref(), Some("300"));
+
+        // A guild that never changed anything has no modules disabled.
+        set_guild_prefix(&pool, "43", None).await?;
+        let settings = get_guild_settings(&pool, "43").await?.unwrap();
+        assert!(settings.prefix.is_none());
+        assert_eq!(settings.disabled_modules, "");
+
+        set_disabled_modules(&pool, "43", "music,admin").await?;
+        let settings = get_guild_settings(&pool, "43").await?.unwrap();
+        assert_eq!(settings.disabled_modules, "music,admin");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_channel_locks() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let lock = NewChannelLock {
+            guild_id: "42",
+            channel_id: "100",
+            previous_overwrites: r#"[{"kind":"role","id":"42","allow":"1024","deny":"2048"}]"#,
+            moderator_id: "1",
+            moderator_name: "mod",
+            reason: "raid",
+            expires_at: Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+        };
+        let lock_id = save_channel_lock(&pool, &lock).await?.unwrap();
+
+        // A second lock must not replace the snapshot taken before the first one.
+        let relock = NewChannelLock {
+            previous_overwrites: "[]",
+            ..lock
+        };
+        assert!(save_channel_lock(&pool, &relock).await?.is_none());
+
+        let active = get_active_channel_locks(&pool, "42").await?;
+        assert_eq!(active.len(), 1);
+        assert_eq!(
+            active[0].previous_overwrites.as_deref(),
+            Some(lock.previous_overwrites)
+        );
+        assert!(active[0].previous_allow.is_none());
+
+        let due = get_due_channel_locks(&pool).await?;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, lock_id);
+
+        assert_eq!(
+            record_channel_unlock_failure(&pool, lock_id, "boom").await?,
+            1
+        );
+        mark_channel_unlocked(&pool, lock_id, None).await?;
+        assert!(get_active_channel_locks(&pool, "42").await?.is_empty());
+        assert!(get_due_channel_locks(&pool).await?.is_empty());
+
+        // Once unlocked, the channel can be locked again.
+        assert!(save_channel_lock(&pool, &relock).await?.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_role_menus() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let menu_id = save_role_menu(
+            &pool,
+            &NewRoleMenu {
+                guild_id: "42",
+                title: "Games",
+                description: None,
+                style: "buttons",
+                exclusive: false,
+                max_selections: Some(2),
+                required_role_id: Some("7"),
+                created_by_id: "1",
+            },
+        )
+        .await?;
+
+        let option = NewRoleMenuOption {
+            menu_id,
+            role_id: "100",
+            label: "Chess",
+            emoji: None,
+            description: None,
+        };
+        save_role_menu_option(&pool, &option).await?;
+        save_role_menu_option(
+            &pool,
+            &NewRoleMenuOption {
+                label: "Chess club",
+                ..option
+            },
+        )
+        .await?;
+
+        let options = get_role_menu_options(&pool, menu_id).await?;
+        assert_eq!(options.len(), 1);
+        assert_eq!(options[0].label, "Chess club");
+
+        assert!(get_role_menu(&pool, menu_id, "other").await?.is_none());
+        set_role_menu_message(&pool, menu_id, "200", "300").await?;
+        let menu = get_role_menu(&pool, menu_id, "42").await?.unwrap();
+        assert_eq!(menu.message_id.as_deref(), Some("300"));
+        assert_eq!(menu.max_selections, Some(2));
+
+        assert!(!delete_role_menu_option(&pool, menu_id, "999").await?);
+        assert!(delete_role_menu(&pool, menu_id, "42").await?);
+        assert!(get_role_menus(&pool, "42").await?.is_empty());
+        assert!(get_role_menu_options(&pool, menu_id).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_message_log_channels() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        assert!(add_message_log_channel(&pool, "42", "100", "1").await?);
+        assert!(!add_message_log_channel(&pool, "42", "100", "1").await?);
+        assert!(add_message_log_channel(&pool, "42", "101", "1").await?);
+        assert_eq!(get_message_log_channels(&pool, "42").await?.len(), 2);
+
+        assert!(remove_message_log_channel(&pool, "42", "100").await?);
+        assert!(!remove_message_log_channel(&pool, "42", "100").await?);
+        assert_eq!(get_message_log_channels(&pool, "42").await?, vec!["101"]);
+
+        // Both log channels live in the same settings row.
+        set_mod_log_channel(&pool, "42", Some("200")).await?;
+        set_message_log_channel(&pool, "42", Some("300")).await?;
+        let settings = get_guild_settings(&pool, "42").await?.unwrap();
+        assert_eq!(settings.mod_log_channel_id.as_deref(), Some("200"));
+        assert_eq!(settings.message_log_channel_id.as_deref(), Some("300"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_moderation_cases() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let mut numbers = Vec::new();
+        for guild_id in ["42", "42", "43"] {
+            let audit_id = save_audit_entry(
+                &pool,
+                &NewAuditEntry {
+                    guild_id,
+                    actor_id: "1",
+                    actor_name: "TestUser",
+                    action: "ban",
+                    target: Some("<@7>"),
+                    parameters: "{}",
+                    outcome: "success",
+                    checked_count: 0,
+                    affected_count: 1,
+                    failed_count: 0,
+                },
+            )
+            .await?;
+            numbers.push(open_case(&pool, guild_id, audit_id, Some("spam")).await?);
+        }
+        // Numbering is per guild.
+        assert_eq!(numbers, vec![1, 2, 1]);
+
+        let case = get_case(&pool, "42", 2).await?.unwrap();
+        assert_eq!(case.action, "ban");
+        assert_eq!(case.reason.as_deref(), Some("spam"));
+        assert!(case.reason_updated_by_id.is_none());
+        assert!(get_case(&pool, "42", 3).await?.is_none());
+
+        assert!(update_case_reason(&pool, "42", 2, "raiding", "5").await?);
+        assert!(!update_case_reason(&pool, "42", 3, "raiding", "5").await?);
+        let case = get_case(&pool, "42", 2).await?.unwrap();
+        assert_eq!(case.reason.as_deref(), Some("raiding"));
+        assert_eq!(case.reason_updated_by_id.as_deref(), Some("5"));
+        assert!(case.reason_updated_at.is_some());
+
+        add_case_evidence(&pool, case.id, "https://example.com/a.png", "5").await?;
+        add_case_evidence(&pool, case.id, "https://example.com/b.png", "5").await?;
+        let evidence = get_case_evidence(&pool, case.id).await?;
+        assert_eq!(evidence.len(), 2);
+        assert_eq!(evidence[0].url, "https://example.com/a.png");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migration_status() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        let pending = migration_status(&pool).await?;
+        assert!(!pending.is_empty());
+        assert!(pending.iter().all(|m| !m.applied));
+
+        run_migrations(&pool).await?;
+        assert!(migration_status(&pool).await?.iter().all(|m| m.applied));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_import_suggestions() -> Result<()> {
+        let source = SqlitePool::connect("sqlite::memory:").await?;
+        run_migrations(&source).await?;
+
+        save_song_suggestion(&source, "Song", "Artist", "1", "user").await?;
+        save_game_suggestion(&source, "Game", "Studio", "2", "other").await?;
+        let export = export_suggestions(&source).await?;
+        assert_eq!((export.songs.len(), export.games.len()), (1, 1));
+
+        // The export survives a round trip through JSON.
+        let json = serde_json::to_string(&export)?;
+        let export: SuggestionExport = serde_json::from_str(&json)?;
+
+        let target = SqlitePool::connect("sqlite::memory:").await?;
+        run_migrations(&target).await?;
+        save_song_suggestion(&target, "Existing", "Band", "3", "third").await?;
+
+        let counts = import_suggestions(&target, &export).await?;
+        assert_eq!(
+            counts,
+            ImportCounts {
+                songs: 1,
+                games: 1,
+                skipped: 0
+            }
+        );
+
+        let again = import_suggestions(&target, &export).await?;
+        assert_eq!(again.skipped, 2);
+
+        let songs = get_song_suggestions(&target, None).await?;
+        assert_eq!(songs.len(), 2);
+        let imported = songs.iter().find(|s| s.song_name == "Song").unwrap();
+        assert_eq!(imported.created_at, export.songs[0].created_at);
+
+        let mut future = export;
+        future.version = EXPORT_VERSION + 1;
+        assert!(import_suggestions(&target, &future).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_permission_rules() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        let rule = NewPermissionRule {
+            guild_id: "42",
+            command: "suggest remove_song",
+            subject_type: "role",
+            subject_id: "7",
+            effect: "allow",
+            created_by_id: "1",
+        };
+        let id = save_permission_rule(&pool, &rule).await?;
+        save_permission_rule(
+            &pool,
+            &NewPermissionRule {
+                command: "admin",
+                ..rule
+            },
+        )
+        .await?;
+
+        // Saving the same command and subject again replaces the effect.
+        let replaced = save_permission_rule(
+            &pool,
+            &NewPermissionRule {
+                effect: "deny",
+                ..rule
+            },
+        )
+        .await?;
+        assert_eq!(replaced, id);
+
+        let rules = get_permission_rules(&pool, "42").await?;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].command, "admin");
+        assert_eq!(rules[1].effect, "deny");
+        assert!(get_permission_rules(&pool, "43").await?.is_empty());
+
+        assert!(!delete_permission_rule(&pool, id, "43").await?);
+        assert!(delete_permission_rule(&pool, id, "42").await?);
+        assert_eq!(get_permission_rules(&pool, "42").await?.len(), 1);
+
+        let song = save_song_suggestion(&pool, "Song", "Artist", "100", "Someone").await?;
+        assert!(remove_song_suggestion(&pool, song).await?);
+        assert!(!remove_song_suggestion(&pool, song).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_locale() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        assert!(get_user_locale(&pool, "7").await?.is_none());
+
+        set_user_locale(&pool, "7", Some("de")).await?;
+        assert_eq!(get_user_locale(&pool, "7").await?.as_deref(), Some("de"));
+
+        set_user_locale(&pool, "7", None).await?;
+        assert!(get_user_locale(&pool, "7").await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_approved_guilds() -> Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        assert!(!is_guild_approved(&pool, "42").await?);
+
+        assert!(approve_guild(&pool, "42", "1").await?);
+        assert!(!approve_guild(&pool, "42", "2").await?);
+        assert!(is_guild_approved(&pool, "42").await?);
+
+        let approved = get_approved_guilds(&pool).await?;
+        assert_eq!(approved.len(), 1);
+        // Approving again keeps who approved it first.
+        assert_eq!(approved[0].approved_by_id, "1");
+
+        assert!(revoke_guild(&pool, "42").await?);
+        assert!(!revoke_guild(&pool, "42").await?);
+        assert!(!is_guild_approved(&pool, "42").await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionRule {
+    pub id: i64,
+    pub guild_id: String,
+    pub command: String,
+    pub subject_type: String,
+    pub subject_id: String,
+    pub effect: String,
+    pub created_by_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewPermissionRule<'a> {
+    pub guild_id: &'a str,
+    pub command: &'a str,
+    pub subject_type: &'a str,
+    pub subject_id: &'a str,
+    pub effect: &'a str,
+    pub created_by_id: &'a str,
+}
+
+struct PermissionRuleRow {
+    id: i64,
+    guild_id: String,
+    command: String,
+    subject_type: String,
+    subject_id: String,
+    effect: String,
+    created_by_id: String,
+    created_at: NaiveDateTime,
+}
+
+impl From<PermissionRuleRow> for PermissionRule {
+    fn from(row: PermissionRuleRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            command: row.command,
+            subject_type: row.subject_type,
+            subject_id: row.subject_id,
+            effect: row.effect,
+            created_by_id: row.created_by_id,
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+/// Saves a rule, replacing the effect of an existing rule for the same command and subject.
+#[tracing::instrument]
+pub async fn save_permission_rule(pool: &SqlitePool, rule: &NewPermissionRule<'_>) -> Result<i64> {
+    let id = sqlx::query_scalar!(
+        r#"INSERT INTO permission_rules
+               (guild_id, command, subject_type, subject_id, effect, created_by_id)
+           VALUES (?, ?, ?, ?, ?, ?)
+           ON CONFLICT (guild_id, command, subject_type, subject_id) DO UPDATE SET
+               effect = excluded.effect,
+               created_by_id = excluded.created_by_id,
+               created_at = CURRENT_TIMESTAMP
+           RETURNING id as "id!""#,
+        rule.guild_id,
+        rule.command,
+        rule.subject_type,
+        rule.subject_id,
+        rule.effect,
+        rule.created_by_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to save permission rule")?;
+
+    tracing::info!(
+        rule_id = %id,
+        guild_id = %rule.guild_id,
+        command = %rule.command,
+        effect = %rule.effect,
+        "Permission rule saved"
+    );
+
+    Ok(id)
+}
+
+#[tracing::instrument]
+pub async fn get_permission_rules(
+    pool: &SqlitePool,
+    guild_id: &str,
+) -> Result<Vec<PermissionRule>> {
+    let rows = sqlx::query_as!(
+        PermissionRuleRow,
+        r#"SELECT id as "id!", guild_id, command, subject_type, subject_id, effect, created_by_id,
+                  created_at
+           FROM permission_rules
+           WHERE guild_id = ?
+           ORDER BY command, id"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch permission rules")?;
+
+    Ok(rows.into_iter().map(PermissionRule::from).collect())
+}
+
+#[tracing::instrument]
+pub async fn delete_permission_rule(
+    pool: &SqlitePool,
+    rule_id: i64,
+    guild_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM permission_rules WHERE id = ? AND guild_id = ?",
+        rule_id,
+        guild_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete permission rule")?;
+
+    let deleted = result.rows_affected() > 0;
+
+    if deleted {
+        tracing::info!(rule_id = %rule_id, guild_id = %guild_id, "Permission rule deleted");
+    } else {
+        tracing::warn!(rule_id = %rule_id, guild_id = %guild_id, "Permission rule not found");
+    }
+
+    Ok(deleted)
+}
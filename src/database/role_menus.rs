@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleMenu {
+    pub id: i64,
+    pub guild_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub style: String,
+    pub exclusive: bool,
+    pub max_selections: Option<i64>,
+    pub required_role_id: Option<String>,
+    pub channel_id: Option<String>,
+    pub message_id: Option<String>,
+    pub created_by_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewRoleMenu<'a> {
+    pub guild_id: &'a str,
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub style: &'a str,
+    pub exclusive: bool,
+    pub max_selections: Option<i64>,
+    pub required_role_id: Option<&'a str>,
+    pub created_by_id: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleMenuOption {
+    pub id: i64,
+    pub menu_id: i64,
+    pub role_id: String,
+    pub label: String,
+    pub emoji: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct NewRoleMenuOption<'a> {
+    pub menu_id: i64,
+    pub role_id: &'a str,
+    pub label: &'a str,
+    pub emoji: Option<&'a str>,
+    pub description: Option<&'a str>,
+}
+
+struct RoleMenuRow {
+    id: i64,
+    guild_id: String,
+    title: String,
+    description: Option<String>,
+    style: String,
+    exclusive: bool,
+    max_selections: Option<i64>,
+    required_role_id: Option<String>,
+    channel_id: Option<String>,
+    message_id: Option<String>,
+    created_by_id: String,
+    created_at: NaiveDateTime,
+}
+
+impl From<RoleMenuRow> for RoleMenu {
+    fn from(row: RoleMenuRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            title: row.title,
+            description: row.description,
+            style: row.style,
+            exclusive: row.exclusive,
+            max_selections: row.max_selections,
+            required_role_id: row.required_role_id,
+            channel_id: row.channel_id,
+            message_id: row.message_id,
+            created_by_id: row.created_by_id,
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+#[tracing::instrument]
+pub async fn save_role_menu(pool: &SqlitePool, menu: &NewRoleMenu<'_>) -> Result<i64> {
+    let result = sqlx::query!(
+        "INSERT INTO role_menus
+            (guild_id, title, description, style, exclusive, max_selections, required_role_id,
+             created_by_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        menu.guild_id,
+        menu.title,
+        menu.description,
+        menu.style,
+        menu.exclusive,
+        menu.max_selections,
+        menu.required_role_id,
+        menu.created_by_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save role menu")?;
+
+    tracing::info!(
+        menu_id = %result.last_insert_rowid(),
+        guild_id = %menu.guild_id,
+        "Role menu saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_role_menu(
+    pool: &SqlitePool,
+    menu_id: i64,
+    guild_id: &str,
+) -> Result<Option<RoleMenu>> {
+    let row = sqlx::query_as!(
+        RoleMenuRow,
+        r#"SELECT id as "id!", guild_id, title, description, style, exclusive, max_selections,
+                  required_role_id, channel_id, message_id, created_by_id, created_at
+           FROM role_menus
+           WHERE id = ? AND guild_id = ?"#,
+        menu_id,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch role menu")?;
+
+    Ok(row.map(RoleMenu::from))
+}
+
+#[tracing::instrument]
+pub async fn get_role_menus(pool: &SqlitePool, guild_id: &str) -> Result<Vec<RoleMenu>> {
+    let rows = sqlx::query_as!(
+        RoleMenuRow,
+        r#"SELECT id as "id!", guild_id, title, description, style, exclusive, max_selections,
+                  required_role_id, channel_id, message_id, created_by_id, created_at
+           FROM role_menus
+           WHERE guild_id = ?
+           ORDER BY id"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch role menus")?;
+
+    Ok(rows.into_iter().map(RoleMenu::from).collect())
+}
+
+/// Remembers where a menu was posted so later edits update the same message.
+#[tracing::instrument]
+pub async fn set_role_menu_message(
+    pool: &SqlitePool,
+    menu_id: i64,
+    channel_id: &str,
+    message_id: &str,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE role_menus SET channel_id = ?, message_id = ? WHERE id = ?",
+        channel_id,
+        message_id,
+        menu_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save role menu message")?;
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn delete_role_menu(pool: &SqlitePool, menu_id: i64, guild_id: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM role_menus WHERE id = ? AND guild_id = ?",
+        menu_id,
+        guild_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete role menu")?;
+
+    let deleted = result.rows_affected() > 0;
+
+    if deleted {
+        tracing::info!(menu_id = %menu_id, guild_id = %guild_id, "Role menu deleted");
+    } else {
+        tracing::warn!(menu_id = %menu_id, guild_id = %guild_id, "Role menu not found");
+    }
+
+    Ok(deleted)
+}
+
+#[tracing::instrument]
+pub async fn get_role_menu_options(pool: &SqlitePool, menu_id: i64) -> Result<Vec<RoleMenuOption>> {
+    let options = sqlx::query_as!(
+        RoleMenuOption,
+        r#"SELECT id as "id!", menu_id, role_id, label, emoji, description
+           FROM role_menu_options
+           WHERE menu_id = ?
+           ORDER BY id"#,
+        menu_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch role menu options")?;
+
+    Ok(options)
+}
+
+/// Adds a role to a menu, or updates its label, emoji and description if it is already there.
+#[tracing::instrument]
+pub async fn save_role_menu_option(
+    pool: &SqlitePool,
+    option: &NewRoleMenuOption<'_>,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO role_menu_options (menu_id, role_id, label, emoji, description)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT (menu_id, role_id) DO UPDATE SET
+            label = excluded.label,
+            emoji = excluded.emoji,
+            description = excluded.description",
+        option.menu_id,
+        option.role_id,
+        option.label,
+        option.emoji,
+        option.description
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save role menu option")?;
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn delete_role_menu_option(
+    pool: &SqlitePool,
+    menu_id: i64,
+    role_id: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM role_menu_options WHERE menu_id = ? AND role_id = ?",
+        menu_id,
+        role_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete role menu option")?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporaryBan {
+    pub id: i64,
+    pub guild_id: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub moderator_id: String,
+    pub moderator_name: String,
+    pub reason: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i64,
+}
+
+#[derive(Debug)]
+pub struct NewTemporaryBan<'a> {
+    pub guild_id: &'a str,
+    pub user_id: &'a str,
+    pub user_name: &'a str,
+    pub moderator_id: &'a str,
+    pub moderator_name: &'a str,
+    pub reason: &'a str,
+    pub expires_at: DateTime<Utc>,
+}
+
+struct TemporaryBanRow {
+    id: i64,
+    guild_id: String,
+    user_id: String,
+    user_name: String,
+    moderator_id: String,
+    moderator_name: String,
+    reason: String,
+    expires_at: NaiveDateTime,
+    created_at: NaiveDateTime,
+    attempts: i64,
+}
+
+impl From<TemporaryBanRow> for TemporaryBan {
+    fn from(row: TemporaryBanRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            user_id: row.user_id,
+            user_name: row.user_name,
+            moderator_id: row.moderator_id,
+            moderator_name: row.moderator_name,
+            reason: row.reason,
+            expires_at: row.expires_at.and_utc(),
+            created_at: row.created_at.and_utc(),
+            attempts: row.attempts,
+        }
+    }
+}
+
+/// Stores a temporary ban, replacing any pending one for the same member.
+#[tracing::instrument]
+pub async fn save_temporary_ban(pool: &SqlitePool, ban: &NewTemporaryBan<'_>) -> Result<i64> {
+    let expires_at = ban.expires_at.naive_utc();
+
+    let mut tx = pool.begin().await.context("Failed to start transaction")?;
+
+    sqlx::query!(
+        "UPDATE temporary_bans
+         SET lifted_at = CURRENT_TIMESTAMP, last_error = 'Replaced by a newer ban'
+         WHERE guild_id = ? AND user_id = ? AND lifted_at IS NULL",
+        ban.guild_id,
+        ban.user_id
+    )
+    .execute(&mut *tx)
+    .await
+    .context("Failed to replace pending temporary ban")?;
+
+    let result = sqlx::query!(
+        "INSERT INTO temporary_bans
+            (guild_id, user_id, user_name, moderator_id, moderator_name, reason, expires_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        ban.guild_id,
+        ban.user_id,
+        ban.user_name,
+        ban.moderator_id,
+        ban.moderator_name,
+        ban.reason,
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await
+    .context("Failed to save temporary ban")?;
+
+    tx.commit()
+        .await
+        .context("Failed to commit temporary ban")?;
+
+    tracing::info!(
+        ban_id = %result.last_insert_rowid(),
+        guild_id = %ban.guild_id,
+        user_id = %ban.user_id,
+        expires_at = %ban.expires_at,
+        "Temporary ban saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Pending temporary bans whose expiry has passed, oldest first.
+#[tracing::instrument]
+pub async fn get_due_temporary_bans(pool: &SqlitePool) -> Result<Vec<TemporaryBan>> {
+    let rows = sqlx::query_as!(
+        TemporaryBanRow,
+        r#"SELECT id as "id!", guild_id, user_id, user_name, moderator_id, moderator_name, reason,
+                  expires_at, created_at, attempts
+           FROM temporary_bans
+           WHERE lifted_at IS NULL AND datetime(expires_at) <= datetime('now')
+           ORDER BY expires_at"#
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch due temporary bans")?;
+
+    tracing::debug!(count = %rows.len(), "Fetched due temporary bans");
+    Ok(rows.into_iter().map(TemporaryBan::from).collect())
+}
+
+/// Marks a temporary ban as finished. `error` is kept when the scheduler gave up on it.
+#[tracing::instrument]
+pub async fn mark_temporary_ban_lifted(
+    pool: &SqlitePool,
+    ban_id: i64,
+    error: Option<&str>,
+) -> Result<()> {
+    sqlx::query!(
+        "UPDATE temporary_bans SET lifted_at = CURRENT_TIMESTAMP, last_error = ? WHERE id = ?",
+        error,
+        ban_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to mark temporary ban as lifted")?;
+
+    Ok(())
+}
+
+/// Records a failed attempt to lift a temporary ban and returns the attempt count.
+#[tracing::instrument]
+pub async fn record_temporary_ban_failure(
+    pool: &SqlitePool,
+    ban_id: i64,
+    error: &str,
+) -> Result<i64> {
+    let attempts = sqlx::query_scalar!(
+        "UPDATE temporary_bans SET attempts = attempts + 1, last_error = ?
+         WHERE id = ?
+         RETURNING attempts",
+        error,
+        ban_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to record temporary ban failure")?;
+
+    Ok(attempts)
+}
+
+/// Closes the pending temporary ban of a member who was unbanned by hand.
+#[tracing::instrument]
+pub async fn cancel_temporary_bans(
+    pool: &SqlitePool,
+    guild_id: &str,
+    user_id: &str,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        "UPDATE temporary_bans
+         SET lifted_at = CURRENT_TIMESTAMP, last_error = 'Unbanned manually'
+         WHERE guild_id = ? AND user_id = ? AND lifted_at IS NULL",
+        guild_id,
+        user_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to cancel temporary bans")?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,36 @@
+use anyhow::{Context, Result};
+use sqlx::SqlitePool;
+
+/// The language a user picked for themselves, if any.
+#[tracing::instrument]
+pub async fn get_user_locale(pool: &SqlitePool, user_id: &str) -> Result<Option<String>> {
+    let locale = sqlx::query_scalar!(
+        "SELECT locale FROM user_settings WHERE user_id = ?",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch user locale")?;
+
+    Ok(locale.flatten())
+}
+
+#[tracing::instrument]
+pub async fn set_user_locale(pool: &SqlitePool, user_id: &str, locale: Option<&str>) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO user_settings (user_id, locale)
+         VALUES (?, ?)
+         ON CONFLICT (user_id) DO UPDATE SET
+            locale = excluded.locale,
+            updated_at = CURRENT_TIMESTAMP",
+        user_id,
+        locale
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save user locale")?;
+
+    tracing::info!(user_id = %user_id, locale = ?locale, "User locale updated");
+
+    Ok(())
+}
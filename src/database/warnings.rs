@@ -0,0 +1,332 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Warning {
+    pub id: i64,
+    pub guild_id: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub moderator_id: String,
+    pub moderator_name: String,
+    pub reason: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub revoked_by_id: Option<String>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarningEscalation {
+    pub id: i64,
+    pub guild_id: String,
+    pub threshold: i64,
+    pub action: String,
+    pub duration_minutes: Option<i64>,
+    pub created_by_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+struct WarningRow {
+    id: i64,
+    guild_id: String,
+    user_id: String,
+    user_name: String,
+    moderator_id: String,
+    moderator_name: String,
+    reason: String,
+    active: bool,
+    created_at: NaiveDateTime,
+    revoked_by_id: Option<String>,
+    revoked_at: Option<NaiveDateTime>,
+}
+
+impl From<WarningRow> for Warning {
+    fn from(row: WarningRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            user_id: row.user_id,
+            user_name: row.user_name,
+            moderator_id: row.moderator_id,
+            moderator_name: row.moderator_name,
+            reason: row.reason,
+            active: row.active,
+            created_at: row.created_at.and_utc(),
+            revoked_by_id: row.revoked_by_id,
+            revoked_at: row.revoked_at.map(|t| t.and_utc()),
+        }
+    }
+}
+
+struct WarningEscalationRow {
+    id: i64,
+    guild_id: String,
+    threshold: i64,
+    action: String,
+    duration_minutes: Option<i64>,
+    created_by_id: String,
+    created_at: NaiveDateTime,
+}
+
+impl From<WarningEscalationRow> for WarningEscalation {
+    fn from(row: WarningEscalationRow) -> Self {
+        Self {
+            id: row.id,
+            guild_id: row.guild_id,
+            threshold: row.threshold,
+            action: row.action,
+            duration_minutes: row.duration_minutes,
+            created_by_id: row.created_by_id,
+            created_at: row.created_at.and_utc(),
+        }
+    }
+}
+
+#[tracing::instrument]
+pub async fn save_warning(
+    pool: &SqlitePool,
+    guild_id: &str,
+    user_id: &str,
+    user_name: &str,
+    moderator_id: &str,
+    moderator_name: &str,
+    reason: &str,
+) -> Result<i64> {
+    tracing::debug!(
+        guild_id = %guild_id,
+        user_id = %user_id,
+        moderator_id = %moderator_id,
+        "Saving warning to database"
+    );
+
+    let result = sqlx::query!(
+        "INSERT INTO warnings (guild_id, user_id, user_name, moderator_id, moderator_name, reason)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        guild_id,
+        user_id,
+        user_name,
+        moderator_id,
+        moderator_name,
+        reason
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save warning")?;
+
+    tracing::info!(
+        warning_id = %result.last_insert_rowid(),
+        guild_id = %guild_id,
+        user_id = %user_id,
+        "Warning saved successfully"
+    );
+
+    Ok(result.last_insert_rowid())
+}
+
+#[tracing::instrument]
+pub async fn get_warnings(
+    pool: &SqlitePool,
+    guild_id: &str,
+    user_id: &str,
+    include_revoked: bool,
+) -> Result<Vec<Warning>> {
+    tracing::debug!(guild_id = %guild_id, user_id = %user_id, "Fetching warnings");
+
+    let rows = sqlx::query_as!(
+        WarningRow,
+        r#"SELECT id as "id!", guild_id, user_id, user_name, moderator_id, moderator_name, reason,
+                  active, created_at, revoked_by_id, revoked_at
+           FROM warnings
+           WHERE guild_id = ? AND user_id = ? AND (active = 1 OR ?)
+           ORDER BY created_at DESC, id DESC"#,
+        guild_id,
+        user_id,
+        include_revoked
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch warnings")?;
+
+    Ok(rows.into_iter().map(Warning::from).collect())
+}
+
+#[tracing::instrument]
+pub async fn count_active_warnings(
+    pool: &SqlitePool,
+    guild_id: &str,
+    user_id: &str,
+) -> Result<i64> {
+    let count = sqlx::query_scalar!(
+        "SELECT COUNT(*) FROM warnings WHERE guild_id = ? AND user_id = ? AND active = 1",
+        guild_id,
+        user_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to count warnings")?;
+
+    Ok(count)
+}
+
+/// Marks a warning inactive, returning it if it existed and was still active.
+#[tracing::instrument]
+pub async fn revoke_warning(
+    pool: &SqlitePool,
+    guild_id: &str,
+    warning_id: i64,
+    revoked_by_id: &str,
+) -> Result<Option<Warning>> {
+    let row = sqlx::query_as!(
+        WarningRow,
+        r#"UPDATE warnings
+           SET active = 0, revoked_by_id = ?, revoked_at = CURRENT_TIMESTAMP
+           WHERE id = ? AND guild_id = ? AND active = 1
+           RETURNING id as "id!", guild_id, user_id, user_name, moderator_id, moderator_name,
+                     reason, active, created_at, revoked_by_id, revoked_at"#,
+        revoked_by_id,
+        warning_id,
+        guild_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to revoke warning")?;
+
+    match &row {
+        Some(_) => {
+            tracing::info!(warning_id = %warning_id, guild_id = %guild_id, "Warning revoked")
+        }
+        None => tracing::warn!(
+            warning_id = %warning_id,
+            guild_id = %guild_id,
+            "Warning not found or already revoked"
+        ),
+    }
+
+    Ok(row.map(Warning::from))
+}
+
+#[tracing::instrument]
+pub async fn get_warning_escalations(
+    pool: &SqlitePool,
+    guild_id: &str,
+) -> Result<Vec<WarningEscalation>> {
+    let rows = sqlx::query_as!(
+        WarningEscalationRow,
+        r#"SELECT id as "id!", guild_id, threshold, action, duration_minutes, created_by_id, created_at
+           FROM warning_escalations
+           WHERE guild_id = ?
+           ORDER BY threshold"#,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch warning escalations")?;
+
+    Ok(rows.into_iter().map(WarningEscalation::from).collect())
+}
+
+#[tracing::instrument]
+pub async fn get_warning_escalation(
+    pool: &SqlitePool,
+    guild_id: &str,
+    threshold: i64,
+) -> Result<Option<WarningEscalation>> {
+    let row = sqlx::query_as!(
+        WarningEscalationRow,
+        r#"SELECT id as "id!", guild_id, threshold, action, duration_minutes, created_by_id, created_at
+           FROM warning_escalations
+           WHERE guild_id = ? AND threshold = ?"#,
+        guild_id,
+        threshold
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to fetch warning escalation")?;
+
+    Ok(row.map(WarningEscalation::from))
+}
+
+/// Creates or replaces the escalation for `threshold` warnings.
+#[tracing::instrument]
+pub async fn set_warning_escalation(
+    pool: &SqlitePool,
+    guild_id: &str,
+    threshold: i64,
+    action: &str,
+    duration_minutes: Option<i64>,
+    created_by_id: &str,
+) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO warning_escalations (guild_id, threshold, action, duration_minutes, created_by_id)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT (guild_id, threshold) DO UPDATE
+         SET action = excluded.action,
+             duration_minutes = excluded.duration_minutes,
+             created_by_id = excluded.created_by_id,
+             created_at = CURRENT_TIMESTAMP",
+        guild_id,
+        threshold,
+        action,
+        duration_minutes,
+        created_by_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to save warning escalation")?;
+
+    tracing::info!(
+        guild_id = %guild_id,
+        threshold = %threshold,
+        action = %action,
+        "Warning escalation saved successfully"
+    );
+
+    Ok(())
+}
+
+#[tracing::instrument]
+pub async fn delete_warning_escalation(
+    pool: &SqlitePool,
+    guild_id: &str,
+    threshold: i64,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "DELETE FROM warning_escalations WHERE guild_id = ? AND threshold = ?",
+        guild_id,
+        threshold
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete warning escalation")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Records that `user_id` reached the escalation for `threshold` warnings. Returns false when it
+/// already fired for them, so revoking a warning and issuing another does not punish twice.
+#[tracing::instrument]
+pub async fn mark_warning_escalation_fired(
+    pool: &SqlitePool,
+    guild_id: &str,
+    user_id: &str,
+    threshold: i64,
+    warning_id: i64,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        "INSERT INTO fired_warning_escalations (guild_id, user_id, threshold, warning_id)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT (guild_id, user_id, threshold) DO NOTHING",
+        guild_id,
+        user_id,
+        threshold,
+        warning_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to record fired warning escalation")?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,108 @@
+use chrono::Duration;
+
+/// Parses human durations such as `90s`, `30m`, `6h`, `7d`, `2w` or combinations like `1h30m`.
+///
+/// A bare number is read as minutes, matching the older `duration_minutes` options.
+pub fn parse(input: &str) -> Option<Duration> {
+    let input = input.trim().to_ascii_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Ok(minutes) = input.parse::<i64>() {
+        return Duration::try_minutes(minutes).filter(|_| minutes > 0);
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+
+        let part = match c {
+            's' => Duration::try_seconds(value)?,
+            'm' => Duration::try_minutes(value)?,
+            'h' => Duration::try_hours(value)?,
+            'd' => Duration::try_days(value)?,
+            'w' => Duration::try_weeks(value)?,
+            _ => return None,
+        };
+        total = total.checked_add(&part)?;
+    }
+
+    if !number.is_empty() || total <= Duration::zero() {
+        return None;
+    }
+
+    Some(total)
+}
+
+/// Formats a duration with its two most significant units, e.g. `1d 6h` or `45m`.
+pub fn format(duration: Duration) -> String {
+    let mut seconds = duration.num_seconds().max(0);
+    if seconds == 0 {
+        return "0s".to_string();
+    }
+
+    let units = [
+        ("w", 604_800),
+        ("d", 86_400),
+        ("h", 3_600),
+        ("m", 60),
+        ("s", 1),
+    ];
+    let mut parts = Vec::new();
+
+    for (suffix, size) in units {
+        if seconds >= size {
+            parts.push(format!("{}{}", seconds / size, suffix));
+            seconds %= size;
+        }
+        if parts.len() == 2 {
+            break;
+        }
+    }
+
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_durations() {
+        assert_eq!(parse("60"), Some(Duration::minutes(60)));
+        assert_eq!(parse("6h"), Some(Duration::hours(6)));
+        assert_eq!(parse("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse("2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse("1d 12h"), Some(Duration::hours(36)));
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("0"), None);
+        assert_eq!(parse("h"), None);
+        assert_eq!(parse("10"), Some(Duration::minutes(10)));
+        assert_eq!(parse("5x"), None);
+        assert_eq!(parse("5h3"), None);
+        // Too large for chrono, refused instead of panicking.
+        assert_eq!(parse("999999999999999"), None);
+        assert_eq!(parse("999999999999999m"), None);
+    }
+
+    #[test]
+    fn test_format_durations() {
+        assert_eq!(format(Duration::minutes(90)), "1h 30m");
+        assert_eq!(format(Duration::days(8)), "1w 1d");
+        assert_eq!(format(Duration::seconds(45)), "45s");
+        assert_eq!(format(Duration::hours(24)), "1d");
+    }
+}
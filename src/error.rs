@@ -1,3 +1,4 @@
+use crate::i18n::{self, tr};
 use poise::serenity_prelude::RoleParseError;
 use std::fmt;
 
@@ -57,7 +58,31 @@ pub async fn handle_error(error: poise::FrameworkError<'_, crate::bot::Data, Err
                 "Command execution error"
             );
 
-            let response = "An error occurred while processing your command.";
+            let response = match error.downcast_ref::<BotError>() {
+                Some(bot_error) => bot_error.to_string(),
+                None => tr!(i18n::locale(ctx).await, "error-generic"),
+            };
+            if let Err(e) = ctx.say(response).await {
+                tracing::error!(error = %e, "Failed to send error message");
+            }
+        }
+        poise::FrameworkError::CommandCheckFailed {
+            error: Some(error),
+            ctx,
+            ..
+        } => {
+            tracing::info!(
+                command = %ctx.command().qualified_name,
+                user_id = %ctx.author().id,
+                guild_id = ?ctx.guild_id(),
+                error = %error,
+                "Command check failed"
+            );
+
+            let response = match error.downcast_ref::<BotError>() {
+                Some(bot_error) => bot_error.to_string(),
+                None => tr!(i18n::locale(ctx).await, "error-generic"),
+            };
             if let Err(e) = ctx.say(response).await {
                 tracing::error!(error = %e, "Failed to send error message");
             }
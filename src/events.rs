@@ -0,0 +1,148 @@
+use crate::{antispam, approval, automod, bot::Data, error::Error, message_log, role_menus};
+use poise::serenity_prelude::{self as serenity, FullEvent, GuildId, Interaction};
+
+/// Gateway events the bot reacts to outside of commands.
+#[tracing::instrument(skip_all, fields(event = %event.snake_case_name()))]
+pub async fn handle_event(
+    ctx: &serenity::Context,
+    event: &FullEvent,
+    _framework: poise::FrameworkContext<'_, Data, Error>,
+    data: &Data,
+) -> Result<(), Error> {
+    // Guilds waiting for approval get no automod, logging or role menus.
+    if let Some(guild_id) = guild_id(event)
+        && !approval::is_active(data, guild_id).await
+    {
+        return Ok(());
+    }
+
+    match event {
+        FullEvent::GuildCreate { guild, is_new } => {
+            if let Err(e) = approval::check_guild(ctx, data, guild, is_new.unwrap_or(false)).await {
+                tracing::error!(guild_id = %guild.id, error = %e, "Guild approval check failed");
+            }
+        }
+        FullEvent::Message { new_message } => {
+            if let Err(e) = message_log::record_message(data, new_message).await {
+                tracing::error!(
+                    guild_id = ?new_message.guild_id,
+                    message_id = %new_message.id,
+                    error = %e,
+                    "Failed to cache message"
+                );
+            }
+
+            if let Err(e) = automod::check_message(ctx, data, new_message).await {
+                tracing::error!(
+                    guild_id = ?new_message.guild_id,
+                    message_id = %new_message.id,
+                    error = %e,
+                    "Automod check failed"
+                );
+            }
+
+            if let Err(e) = antispam::check_message(ctx, data, new_message).await {
+                tracing::error!(
+                    guild_id = ?new_message.guild_id,
+                    message_id = %new_message.id,
+                    error = %e,
+                    "Anti-spam check failed"
+                );
+            }
+        }
+        FullEvent::MessageUpdate {
+            old_if_available,
+            event,
+            ..
+        } => {
+            if let Err(e) = message_log::log_edit(ctx, data, old_if_available.as_ref(), event).await
+            {
+                tracing::error!(
+                    guild_id = ?event.guild_id,
+                    message_id = %event.id,
+                    error = %e,
+                    "Failed to log message edit"
+                );
+            }
+        }
+        FullEvent::MessageDelete {
+            channel_id,
+            deleted_message_id,
+            guild_id,
+        } => {
+            if let Err(e) =
+                message_log::log_delete(ctx, data, *guild_id, *channel_id, *deleted_message_id)
+                    .await
+            {
+                tracing::error!(
+                    guild_id = ?guild_id,
+                    message_id = %deleted_message_id,
+                    error = %e,
+                    "Failed to log message deletion"
+                );
+            }
+        }
+        FullEvent::MessageDeleteBulk {
+            channel_id,
+            multiple_deleted_messages_ids,
+            guild_id,
+        } => {
+            if let Err(e) = message_log::log_bulk_delete(
+                ctx,
+                data,
+                *guild_id,
+                *channel_id,
+                multiple_deleted_messages_ids,
+            )
+            .await
+            {
+                tracing::error!(
+                    guild_id = ?guild_id,
+                    channel_id = %channel_id,
+                    error = %e,
+                    "Failed to log bulk deletion"
+                );
+            }
+        }
+        FullEvent::GuildMemberAddition { new_member } => {
+            if let Err(e) = antispam::check_join(ctx, data, new_member).await {
+                tracing::error!(
+                    guild_id = %new_member.guild_id,
+                    user_id = %new_member.user.id,
+                    error = %e,
+                    "Raid check failed"
+                );
+            }
+        }
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(component),
+        } => {
+            if let Err(e) = role_menus::handle_component(ctx, data, component).await {
+                tracing::error!(
+                    guild_id = ?component.guild_id,
+                    custom_id = %component.data.custom_id,
+                    error = %e,
+                    "Role menu interaction failed"
+                );
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// The guild an event the bot handles happened in.
+fn guild_id(event: &FullEvent) -> Option<GuildId> {
+    match event {
+        FullEvent::Message { new_message } => new_message.guild_id,
+        FullEvent::MessageUpdate { event, .. } => event.guild_id,
+        FullEvent::MessageDelete { guild_id, .. }
+        | FullEvent::MessageDeleteBulk { guild_id, .. } => *guild_id,
+        FullEvent::GuildMemberAddition { new_member } => Some(new_member.guild_id),
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(component),
+        } => component.guild_id,
+        _ => None,
+    }
+}
@@ -0,0 +1,495 @@
+//! Message catalogs for the bot's responses and slash command metadata.
+//!
+//! The catalogs in `locales/` use the syntax of [Fluent](https://projectfluent.org): messages
+//! with `{ $variable }` placeables, attributes and multiline values. Selectors and functions are
+//! not supported, the bot doesn't need them yet.
+
+use crate::bot::Data;
+use crate::database;
+use crate::error::{Context, Error};
+use crate::settings::Locale;
+use anyhow::{Result, bail};
+use poise::serenity_prelude::{GuildId, UserId};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+type Command = poise::Command<Data, Error>;
+
+/// Formats a message of the catalog, e.g. `tr!(locale, "suggestion-removed", id = 4)`.
+macro_rules! tr {
+    ($locale:expr, $id:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::i18n::translate($locale, $id, &[$((stringify!($name), $value.to_string())),*])
+    };
+}
+pub(crate) use tr;
+
+static CATALOGS: LazyLock<HashMap<Locale, Catalog>> = LazyLock::new(|| {
+    [
+        (Locale::EnglishUs, include_str!("../locales/en-US.ftl")),
+        (Locale::German, include_str!("../locales/de.ftl")),
+    ]
+    .into_iter()
+    .map(|(locale, source)| {
+        let catalog = Catalog::parse(source)
+            .unwrap_or_else(|e| panic!("Invalid {} catalog: {:#}", locale.code(), e));
+        (locale, catalog)
+    })
+    .collect()
+});
+
+#[derive(Debug, Default)]
+struct Message {
+    value: Option<String>,
+    attributes: HashMap<String, String>,
+}
+
+/// The messages of one locale.
+#[derive(Debug, Default)]
+struct Catalog {
+    messages: HashMap<String, Message>,
+}
+
+/// Where the lines of a pattern go once it ends.
+#[derive(Debug)]
+enum Target {
+    Value,
+    Attribute(String),
+}
+
+#[derive(Debug)]
+struct Pattern {
+    target: Target,
+    first: String,
+    lines: Vec<String>,
+}
+
+impl Pattern {
+    fn new(target: Target, first: &str) -> Self {
+        Self {
+            target,
+            first: first.trim().to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Joins the lines, removing the indentation they share.
+    fn text(mut self) -> String {
+        while self.lines.last().is_some_and(|line| line.trim().is_empty()) {
+            self.lines.pop();
+        }
+        let indent = self
+            .lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        std::iter::once(self.first)
+            .filter(|first| !first.is_empty())
+            .chain(self.lines.iter().map(|line| {
+                line.get(indent..)
+                    .unwrap_or_default()
+                    .trim_end()
+                    .to_string()
+            }))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn is_identifier(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_alphabetic())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl Catalog {
+    fn parse(source: &str) -> Result<Self> {
+        let mut catalog = Self::default();
+        let mut current: Option<(String, Message, Pattern)> = None;
+
+        for (number, line) in source.lines().enumerate() {
+            let number = number + 1;
+            if line.starts_with('#') {
+                catalog.finish(current.take())?;
+                continue;
+            }
+            if line.trim().is_empty() {
+                if let Some((_, _, pattern)) = &mut current {
+                    pattern.lines.push(String::new());
+                }
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                catalog.finish(current.take())?;
+                let Some((id, value)) = line.split_once('=') else {
+                    bail!("line {}: expected `id = value`", number);
+                };
+                let id = id.trim();
+                if !is_identifier(id) {
+                    bail!("line {}: `{}` is not a valid message id", number, id);
+                }
+                current = Some((
+                    id.to_string(),
+                    Message::default(),
+                    Pattern::new(Target::Value, value),
+                ));
+                continue;
+            }
+
+            let Some((_, message, pattern)) = &mut current else {
+                bail!("line {}: indented line outside of a message", number);
+            };
+            // `.name = value` starts an attribute, any other indented line continues the pattern.
+            let attribute = line
+                .trim_start()
+                .strip_prefix('.')
+                .and_then(|rest| rest.split_once('='))
+                .filter(|(name, _)| is_identifier(name.trim()));
+            match attribute {
+                Some((name, value)) => {
+                    let previous = std::mem::replace(
+                        pattern,
+                        Pattern::new(Target::Attribute(name.trim().to_string()), value),
+                    );
+                    Self::finish_pattern(message, previous)?;
+                }
+                None => pattern.lines.push(line.to_string()),
+            }
+        }
+        catalog.finish(current)?;
+
+        Ok(catalog)
+    }
+
+    fn finish_pattern(message: &mut Message, pattern: Pattern) -> Result<()> {
+        match pattern.target {
+            Target::Value => {
+                let text = pattern.text();
+                message.value = (!text.is_empty()).then_some(text);
+            }
+            Target::Attribute(ref name) => {
+                let name = name.clone();
+                let text = pattern.text();
+                if text.is_empty() {
+                    bail!("attribute `.{}` has no value", name);
+                }
+                message.attributes.insert(name, text);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, current: Option<(String, Message, Pattern)>) -> Result<()> {
+        let Some((id, mut message, pattern)) = current else {
+            return Ok(());
+        };
+        Self::finish_pattern(&mut message, pattern)
+            .map_err(|e| e.context(format!("in `{}`", id)))?;
+        if message.value.is_none() && message.attributes.is_empty() {
+            bail!("message `{}` has no value", id);
+        }
+        if self.messages.insert(id.clone(), message).is_some() {
+            bail!("message `{}` is defined twice", id);
+        }
+
+        Ok(())
+    }
+}
+
+/// Replaces the placeables of `pattern`: `{ $name }` with the argument, `{ "text" }` with the
+/// text. Unknown arguments stay as they are so the mistake shows.
+fn format(pattern: &str, args: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let placeable = &rest[start..start + end + 1];
+        let expression = placeable[1..placeable.len() - 1].trim();
+
+        let value = match expression.strip_prefix('$') {
+            Some(name) => args
+                .iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| value.as_str()),
+            None => expression
+                .strip_prefix('"')
+                .and_then(|text| text.strip_suffix('"')),
+        };
+        output.push_str(value.unwrap_or(placeable));
+        rest = &rest[start + end + 1..];
+    }
+    output.push_str(rest);
+
+    output
+}
+
+fn message(locale: Locale, id: &str) -> Option<&'static str> {
+    CATALOGS
+        .get(&locale)
+        .and_then(|catalog| catalog.messages.get(id))
+        .and_then(|message| message.value.as_deref())
+}
+
+/// Formats message `id` in `locale`, falling back to English. Use [`tr!`] rather than calling
+/// this directly.
+pub fn translate(locale: Locale, id: &str, args: &[(&str, String)]) -> String {
+    match message(locale, id).or_else(|| message(Locale::default(), id)) {
+        Some(pattern) => format(pattern, args),
+        None => {
+            tracing::warn!(id = %id, locale = %locale.code(), "Missing message in catalog");
+            id.to_string()
+        }
+    }
+}
+
+/// Like [`translate`], but `None` when no catalog has the message.
+pub fn try_translate(locale: Locale, id: &str) -> Option<String> {
+    message(locale, id)
+        .or_else(|| message(Locale::default(), id))
+        .map(|pattern| format(pattern, &[]))
+}
+
+/// Copies the `cmd-` messages of every non-English catalog into poise's localization fields.
+/// English stays in the doc comments on the commands.
+pub fn localize_commands(mut commands: Vec<Command>) -> Vec<Command> {
+    fn visit(commands: &mut [Command], parents: &mut Vec<String>) {
+        for command in commands {
+            parents.push(command.name.clone());
+            let id = format!("cmd-{}", parents.join("-"));
+
+            for locale in Locale::ALL.into_iter().filter(|l| *l != Locale::default()) {
+                let Some(message) = CATALOGS[&locale].messages.get(&id) else {
+                    continue;
+                };
+                let code = locale.code().to_string();
+
+                if let Some(name) = &message.value {
+                    command
+                        .name_localizations
+                        .insert(code.clone(), name.clone());
+                }
+                if let Some(description) = message.attributes.get("description") {
+                    command
+                        .description_localizations
+                        .insert(code.clone(), description.clone());
+                }
+                for parameter in &mut command.parameters {
+                    // `.description` always belongs to the command, even when a
+                    // parameter shares the name.
+                    if parameter.name != "description"
+                        && let Some(name) = message.attributes.get(&parameter.name)
+                    {
+                        parameter
+                            .name_localizations
+                            .insert(code.clone(), name.clone());
+                    }
+                    let description = format!("{}-description", parameter.name);
+                    if let Some(description) = message.attributes.get(&description) {
+                        parameter
+                            .description_localizations
+                            .insert(code.clone(), description.clone());
+                    }
+                }
+            }
+
+            visit(&mut command.subcommands, parents);
+            parents.pop();
+        }
+    }
+
+    visit(&mut commands, &mut Vec::new());
+    commands
+}
+
+/// Languages users picked for themselves, loaded on first use and dropped when they change.
+#[derive(Debug, Clone, Default)]
+pub struct LocaleCache {
+    users: Arc<RwLock<HashMap<UserId, Option<Locale>>>>,
+}
+
+impl LocaleCache {
+    pub async fn user(&self, database: &SqlitePool, user_id: UserId) -> Result<Option<Locale>> {
+        if let Some(locale) = self.users.read().unwrap().get(&user_id) {
+            return Ok(*locale);
+        }
+
+        let locale = database::get_user_locale(database, &user_id.to_string())
+            .await?
+            .as_deref()
+            .and_then(Locale::from_code);
+        self.users.write().unwrap().insert(user_id, locale);
+
+        Ok(locale)
+    }
+
+    pub fn invalidate(&self, user_id: UserId) {
+        self.users.write().unwrap().remove(&user_id);
+    }
+}
+
+/// The language to answer in: the user's own choice, then the guild's, then the language of
+/// their Discord client for slash commands, then English.
+pub async fn locale(ctx: Context<'_>) -> Locale {
+    user_locale(ctx.data(), ctx.author().id, ctx.guild_id(), ctx.locale()).await
+}
+
+/// [`locale`] outside of commands, e.g. for component clicks or DMs. `discord` is the language
+/// of the user's client when the event carries it.
+pub async fn user_locale(
+    data: &Data,
+    user_id: UserId,
+    guild_id: Option<GuildId>,
+    discord: Option<&str>,
+) -> Locale {
+    match data.locales.user(&data.database, user_id).await {
+        Ok(Some(locale)) => return locale,
+        Ok(None) => {}
+        Err(e) => tracing::warn!(user_id = %user_id, error = %e, "Failed to load user locale"),
+    }
+
+    if let Some(guild_id) = guild_id
+        && let Some(locale) = configured_guild_locale(data, guild_id).await
+    {
+        return locale;
+    }
+
+    discord.and_then(Locale::from_discord).unwrap_or_default()
+}
+
+/// The language of messages a guild as a whole reads, such as mod-log entries.
+pub async fn guild_locale(data: &Data, guild_id: GuildId) -> Locale {
+    configured_guild_locale(data, guild_id)
+        .await
+        .unwrap_or_default()
+}
+
+async fn configured_guild_locale(data: &Data, guild_id: GuildId) -> Option<Locale> {
+    match data.settings.get(&data.database, guild_id).await {
+        Ok(settings) => settings.locale.as_deref().and_then(Locale::from_code),
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load guild settings");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::get_commands;
+
+    #[test]
+    fn test_parse() {
+        let catalog = Catalog::parse(
+            "# comment\n\
+             hello = Hello { $name }!\n\
+             multi =\n    first\n      indented\n\n    last\n\
+             cmd-x = x\n    .description = Does x\n    .arg-description = The arg\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.messages["hello"].value.as_deref(),
+            Some("Hello { $name }!")
+        );
+        assert_eq!(
+            catalog.messages["multi"].value.as_deref(),
+            Some("first\n  indented\n\nlast")
+        );
+        let command = &catalog.messages["cmd-x"];
+        assert_eq!(command.value.as_deref(), Some("x"));
+        assert_eq!(command.attributes["description"], "Does x");
+        assert_eq!(command.attributes["arg-description"], "The arg");
+
+        assert!(Catalog::parse("no value here").is_err());
+        assert!(Catalog::parse("a = 1\na = 2").is_err());
+        assert!(Catalog::parse("    orphan").is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let args = [("id", "4".to_string())];
+        assert_eq!(format("Removed #{ $id }", &args), "Removed #4");
+        assert_eq!(format("{ \"   \" }x", &args), "   x");
+        assert_eq!(format("Missing { $other }", &args), "Missing { $other }");
+        assert_eq!(format("Unclosed { $id", &args), "Unclosed { $id");
+        assert_eq!(
+            tr!(Locale::German, "suggestion-removed", id = 4),
+            "Vorschlag #4 wurde entfernt"
+        );
+    }
+
+    #[test]
+    fn test_catalogs_are_complete() {
+        let english = &CATALOGS[&Locale::EnglishUs];
+        for locale in Locale::ALL {
+            let catalog = &CATALOGS[&locale];
+            for id in english.messages.keys() {
+                assert!(
+                    catalog.messages.contains_key(id),
+                    "{} is missing {}",
+                    locale.code(),
+                    id
+                );
+            }
+            for id in catalog.messages.keys() {
+                assert!(
+                    english.messages.contains_key(id) || id.starts_with("cmd-"),
+                    "{} has {}, which English doesn't",
+                    locale.code(),
+                    id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_command_localizations() {
+        fn visit(commands: &[Command], parents: &mut Vec<String>, ids: &mut Vec<String>) {
+            for command in commands {
+                parents.push(command.name.clone());
+                ids.push(format!("cmd-{}", parents.join("-")));
+                let parameter_names = command
+                    .parameters
+                    .iter()
+                    .flat_map(|parameter| parameter.name_localizations.values());
+                for name in command.name_localizations.values().chain(parameter_names) {
+                    // Discord rejects slash command names that aren't lowercase words.
+                    assert!(name.chars().count() <= 32, "{} is too long", name);
+                    assert!(
+                        name.chars()
+                            .all(|c| c == '-' || c == '_' || c.is_lowercase() || c.is_numeric()),
+                        "{} is not a valid command name",
+                        name
+                    );
+                }
+                visit(&command.subcommands, parents, ids);
+                parents.pop();
+            }
+        }
+
+        let commands = localize_commands(get_commands());
+        let mut ids = Vec::new();
+        visit(&commands, &mut Vec::new(), &mut ids);
+
+        let german = &CATALOGS[&Locale::German];
+        for id in german.messages.keys().filter(|id| id.starts_with("cmd-")) {
+            assert!(ids.contains(id), "{} is not a command", id);
+        }
+
+        let suggest = commands.iter().find(|c| c.name == "suggest").unwrap();
+        assert_eq!(suggest.name_localizations["de"], "vorschlag");
+        let request = &suggest.subcommands[0];
+        assert_eq!(request.parameters[0].name_localizations["de"], "songname");
+    }
+}
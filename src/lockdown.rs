@@ -0,0 +1,488 @@
+use crate::database::{self, ChannelLock, NewChannelLock};
+use crate::moderation::Moderator;
+use crate::scheduler::parse_id;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, ChannelType, Guild, GuildId, Http, HttpError, PermissionOverwrite,
+    PermissionOverwriteType, Permissions, RoleId, UserId,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// Permissions taken from @everyone, and from any overwrite granting them, while a channel is
+/// locked.
+pub const LOCK_PERMISSIONS: Permissions = Permissions::SEND_MESSAGES
+    .union(Permissions::SEND_MESSAGES_IN_THREADS)
+    .union(Permissions::ADD_REACTIONS)
+    .union(Permissions::CREATE_PUBLIC_THREADS)
+    .union(Permissions::CREATE_PRIVATE_THREADS);
+
+/// Permissions that mark a role or overwrite as belonging to moderators, who keep talking in
+/// locked channels.
+const MODERATOR_PERMISSIONS: Permissions = Permissions::ADMINISTRATOR
+    .union(Permissions::MANAGE_MESSAGES)
+    .union(Permissions::MANAGE_CHANNELS);
+
+/// Longest slowmode Discord accepts, in seconds.
+pub const MAX_SLOWMODE_SECONDS: u16 = 21_600;
+
+/// Discord's error code for a channel that no longer exists.
+const UNKNOWN_CHANNEL: isize = 10003;
+
+/// Discord's error code for a role that no longer exists.
+const UNKNOWN_ROLE: isize = 10011;
+
+/// What happened to each channel of a lock or unlock request.
+#[derive(Debug, Default)]
+pub struct LockReport {
+    pub changed: Vec<ChannelId>,
+    /// Channels that were already in the requested state.
+    pub skipped: Vec<ChannelId>,
+    pub errors: Vec<String>,
+}
+
+/// Text channels where @everyone can currently send messages.
+pub fn public_channels(guild: &Guild) -> Vec<ChannelId> {
+    let everyone = guild.id.everyone_role();
+    let base = guild
+        .roles
+        .get(&everyone)
+        .map_or(Permissions::empty(), |role| role.permissions);
+
+    let mut channels: Vec<_> = guild
+        .channels
+        .values()
+        .filter(|channel| matches!(channel.kind, ChannelType::Text | ChannelType::News))
+        .filter(|channel| {
+            let overwrite = channel
+                .permission_overwrites
+                .iter()
+                .find(|o| o.kind == PermissionOverwriteType::Role(everyone));
+            match overwrite {
+                Some(o) if o.deny.contains(Permissions::SEND_MESSAGES) => false,
+                Some(o) if o.allow.contains(Permissions::SEND_MESSAGES) => true,
+                _ => base.contains(Permissions::SEND_MESSAGES),
+            }
+        })
+        .map(|channel| channel.id)
+        .collect();
+    channels.sort();
+
+    channels
+}
+
+/// Denies @everyone from talking in `channels`, saving each channel's overwrites first so the
+/// unlock restores them exactly. Locks with an `expires_at` are lifted by the scheduler.
+#[tracing::instrument(skip(http, database))]
+pub async fn lock_channels(
+    http: &Http,
+    database: &SqlitePool,
+    guild_id: GuildId,
+    channels: &[ChannelId],
+    actor: &Moderator,
+    reason: &str,
+    expires_at: Option<DateTime<Utc>>,
+) -> LockReport {
+    let mut report = LockReport::default();
+
+    let exempt = match Exempt::load(http, guild_id).await {
+        Ok(exempt) => exempt,
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load roles for a lock");
+            report.errors.extend(
+                channels
+                    .iter()
+                    .map(|channel_id| format!("<#{}>: {}", channel_id, e)),
+            );
+            return report;
+        }
+    };
+
+    for &channel_id in channels {
+        match lock_channel(
+            http, database, guild_id, channel_id, &exempt, actor, reason, expires_at,
+        )
+        .await
+        {
+            Ok(true) => report.changed.push(channel_id),
+            Ok(false) => report.skipped.push(channel_id),
+            Err(e) => {
+                tracing::warn!(channel_id = %channel_id, error = %e, "Failed to lock channel");
+                report.errors.push(format!("<#{}>: {}", channel_id, e));
+            }
+        }
+    }
+
+    report
+}
+
+/// One permission overwrite as saved before a lock, with the raw bits so permissions this
+/// version doesn't know about survive the restore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedOverwrite {
+    /// `role` or `member`.
+    pub kind: String,
+    pub id: String,
+    pub allow: String,
+    pub deny: String,
+}
+
+impl SavedOverwrite {
+    fn new(overwrite: &PermissionOverwrite) -> Option<Self> {
+        let (kind, id) = match overwrite.kind {
+            PermissionOverwriteType::Role(role_id) => ("role", role_id.get()),
+            PermissionOverwriteType::Member(user_id) => ("member", user_id.get()),
+            _ => return None,
+        };
+
+        Some(Self {
+            kind: kind.to_string(),
+            id: id.to_string(),
+            allow: overwrite.allow.bits().to_string(),
+            deny: overwrite.deny.bits().to_string(),
+        })
+    }
+
+    fn to_overwrite(&self) -> Result<PermissionOverwrite> {
+        let id = parse_id(&self.id).ok_or_else(|| anyhow!("Invalid snapshot"))?;
+        let kind = match self.kind.as_str() {
+            "role" => PermissionOverwriteType::Role(RoleId::new(id)),
+            "member" => PermissionOverwriteType::Member(UserId::new(id)),
+            other => return Err(anyhow!("Unknown overwrite kind `{}` in snapshot", other)),
+        };
+
+        Ok(PermissionOverwrite {
+            allow: Permissions::from_bits_retain(self.allow.parse().context("Invalid snapshot")?),
+            deny: Permissions::from_bits_retain(self.deny.parse().context("Invalid snapshot")?),
+            kind,
+        })
+    }
+}
+
+/// Overwrites a lock leaves alone: those of moderator roles and of the bot, so moderators keep
+/// talking and the bot can still post in the locked channel.
+#[derive(Debug)]
+struct Exempt {
+    roles: HashSet<RoleId>,
+    bot_id: UserId,
+}
+
+impl Exempt {
+    async fn load(http: &Http, guild_id: GuildId) -> Result<Self> {
+        let bot_id = http
+            .get_current_user()
+            .await
+            .context("Failed to fetch the bot user")?
+            .id;
+        let bot = guild_id
+            .member(http, bot_id)
+            .await
+            .context("Failed to fetch the bot member")?;
+        let roles = guild_id
+            .roles(http)
+            .await
+            .context("Failed to fetch roles")?;
+
+        let everyone = guild_id.everyone_role();
+        let roles = roles
+            .values()
+            .filter(|role| role.id != everyone)
+            .filter(|role| {
+                role.permissions.intersects(MODERATOR_PERMISSIONS) || bot.roles.contains(&role.id)
+            })
+            .map(|role| role.id)
+            .collect();
+
+        Ok(Self { roles, bot_id })
+    }
+
+    fn contains(&self, overwrite: &PermissionOverwrite) -> bool {
+        if overwrite.allow.intersects(MODERATOR_PERMISSIONS) {
+            return true;
+        }
+
+        match overwrite.kind {
+            PermissionOverwriteType::Role(role_id) => self.roles.contains(&role_id),
+            PermissionOverwriteType::Member(user_id) => self.bot_id == user_id,
+            _ => false,
+        }
+    }
+}
+
+/// The overwrites that lock a channel: @everyone denied [`LOCK_PERMISSIONS`], and every other
+/// overwrite that allowed one of them denied them too, so nobody keeps talking through an
+/// overwrite of their own. Moderator and bot overwrites stay as they are.
+fn locked_overwrites(
+    overwrites: &[PermissionOverwrite],
+    everyone: PermissionOverwriteType,
+    exempt: &Exempt,
+) -> Vec<PermissionOverwrite> {
+    let mut locked: Vec<_> = overwrites
+        .iter()
+        .filter(|o| {
+            o.kind == everyone || (o.allow.intersects(LOCK_PERMISSIONS) && !exempt.contains(o))
+        })
+        .map(|o| PermissionOverwrite {
+            allow: o.allow - LOCK_PERMISSIONS,
+            deny: o.deny | LOCK_PERMISSIONS,
+            kind: o.kind,
+        })
+        .collect();
+    if !overwrites.iter().any(|o| o.kind == everyone) {
+        locked.push(PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: LOCK_PERMISSIONS,
+            kind: everyone,
+        });
+    }
+
+    locked
+}
+
+/// Locks one channel, returning `false` when it was already locked.
+#[allow(clippy::too_many_arguments)]
+async fn lock_channel(
+    http: &Http,
+    database: &SqlitePool,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    exempt: &Exempt,
+    actor: &Moderator,
+    reason: &str,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<bool> {
+    // The snapshot is read from the API rather than the cache so it is never stale.
+    let channel = channel_id
+        .to_channel(http)
+        .await
+        .context("Failed to fetch channel")?
+        .guild()
+        .filter(|channel| channel.guild_id == guild_id)
+        .ok_or_else(|| anyhow!("Not a channel of this server"))?;
+
+    // Only role and member overwrites exist today, others couldn't be restored.
+    let previous: Vec<_> = channel
+        .permission_overwrites
+        .into_iter()
+        .filter(|o| SavedOverwrite::new(o).is_some())
+        .collect();
+    let snapshot: Vec<_> = previous.iter().filter_map(SavedOverwrite::new).collect();
+    let previous_overwrites =
+        serde_json::to_string(&snapshot).context("Failed to save overwrites")?;
+
+    let Some(lock_id) = database::save_channel_lock(
+        database,
+        &NewChannelLock {
+            guild_id: &guild_id.to_string(),
+            channel_id: &channel_id.to_string(),
+            previous_overwrites: &previous_overwrites,
+            moderator_id: &actor.id.to_string(),
+            moderator_name: &actor.name,
+            reason,
+            expires_at,
+        },
+    )
+    .await?
+    else {
+        return Ok(false);
+    };
+
+    let everyone = PermissionOverwriteType::Role(guild_id.everyone_role());
+    for overwrite in locked_overwrites(&previous, everyone, exempt) {
+        if let Err(e) = channel_id.create_permission(http, overwrite).await {
+            // Put back what was already changed before giving up on the lock.
+            if let Err(e) = restore_overwrites(http, channel_id, everyone, &previous).await {
+                tracing::error!(channel_id = %channel_id, error = %e, "Failed to undo partial lock");
+            }
+            let error = e.to_string();
+            if let Err(e) = database::mark_channel_unlocked(database, lock_id, Some(&error)).await {
+                tracing::error!(lock_id = %lock_id, error = %e, "Failed to close channel lock");
+            }
+            return Err(e.into());
+        }
+    }
+
+    tracing::info!(guild_id = %guild_id, channel_id = %channel_id, "Channel locked");
+
+    Ok(true)
+}
+
+/// Restores the snapshots of `locks` and closes them. Failed restores stay active so they can
+/// be retried.
+#[tracing::instrument(skip(http, database, locks))]
+pub async fn unlock_channels(
+    http: &Http,
+    database: &SqlitePool,
+    locks: &[ChannelLock],
+) -> LockReport {
+    let mut report = LockReport::default();
+
+    for lock in locks {
+        let channel_id = parse_id(&lock.channel_id).map(ChannelId::new);
+
+        match restore_snapshot(http, lock).await {
+            Ok(()) => {
+                if let Err(e) = database::mark_channel_unlocked(database, lock.id, None).await {
+                    tracing::error!(lock_id = %lock.id, error = %e, "Failed to close channel lock");
+                }
+                report.changed.extend(channel_id);
+            }
+            Err(e) => {
+                let error = e.to_string();
+                if let Err(e) =
+                    database::record_channel_unlock_failure(database, lock.id, &error).await
+                {
+                    tracing::error!(lock_id = %lock.id, error = %e, "Failed to record unlock failure");
+                }
+                report
+                    .errors
+                    .push(format!("<#{}>: {}", lock.channel_id, error));
+            }
+        }
+    }
+
+    report
+}
+
+/// Puts back the overwrites saved when `lock` was taken.
+///
+/// A channel deleted while locked has nothing left to restore and counts as unlocked.
+#[tracing::instrument(skip(http))]
+pub async fn restore_snapshot(http: &Http, lock: &ChannelLock) -> Result<()> {
+    let (Some(guild_id), Some(channel_id)) = (parse_id(&lock.guild_id), parse_id(&lock.channel_id))
+    else {
+        return Err(anyhow!("Channel lock has invalid ids"));
+    };
+    let channel_id = ChannelId::new(channel_id);
+    let everyone = PermissionOverwriteType::Role(GuildId::new(guild_id).everyone_role());
+
+    let saved: Vec<SavedOverwrite> = match &lock.previous_overwrites {
+        Some(overwrites) => serde_json::from_str(overwrites).context("Invalid snapshot")?,
+        // Locks taken before every overwrite was saved only changed @everyone.
+        None => match (&lock.previous_allow, &lock.previous_deny) {
+            (Some(allow), Some(deny)) => vec![SavedOverwrite {
+                kind: "role".to_string(),
+                id: guild_id.to_string(),
+                allow: allow.clone(),
+                deny: deny.clone(),
+            }],
+            _ => Vec::new(),
+        },
+    };
+
+    let previous = saved
+        .iter()
+        .map(SavedOverwrite::to_overwrite)
+        .collect::<Result<Vec<_>>>()?;
+
+    let result = restore_overwrites(http, channel_id, everyone, &previous).await;
+
+    match result {
+        Ok(()) => {
+            tracing::info!(channel_id = %channel_id, "Channel unlocked");
+            Ok(())
+        }
+        Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+            if response.error.code == UNKNOWN_CHANNEL =>
+        {
+            tracing::info!(channel_id = %channel_id, "Locked channel no longer exists");
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Writes back every overwrite of `previous`, and removes the @everyone one when the channel
+/// had none. Roles deleted in the meantime are skipped.
+async fn restore_overwrites(
+    http: &Http,
+    channel_id: ChannelId,
+    everyone: PermissionOverwriteType,
+    previous: &[PermissionOverwrite],
+) -> std::result::Result<(), serenity::Error> {
+    for overwrite in previous {
+        match channel_id.create_permission(http, overwrite.clone()).await {
+            Ok(()) => {}
+            Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+                if response.error.code == UNKNOWN_ROLE =>
+            {
+                tracing::debug!(channel_id = %channel_id, overwrite = ?overwrite.kind, "Role of saved overwrite no longer exists");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if !previous.iter().any(|o| o.kind == everyone) {
+        channel_id.delete_permission(http, everyone).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_overwrites() {
+        let everyone = PermissionOverwriteType::Role(RoleId::new(1));
+        let helpers = PermissionOverwriteType::Role(RoleId::new(2));
+        let member = PermissionOverwriteType::Member(UserId::new(3));
+        let moderators = PermissionOverwriteType::Role(RoleId::new(4));
+        let bot = PermissionOverwriteType::Member(UserId::new(5));
+        let channel_moderator = PermissionOverwriteType::Member(UserId::new(6));
+        let talk = |kind| PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind,
+        };
+        let overwrites = [
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS,
+                deny: Permissions::empty(),
+                kind: helpers,
+            },
+            PermissionOverwrite {
+                allow: Permissions::VIEW_CHANNEL,
+                deny: Permissions::empty(),
+                kind: member,
+            },
+            talk(moderators),
+            talk(bot),
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES,
+                deny: Permissions::empty(),
+                kind: channel_moderator,
+            },
+        ];
+        let exempt = Exempt {
+            roles: HashSet::from([RoleId::new(4)]),
+            bot_id: UserId::new(5),
+        };
+
+        let locked = locked_overwrites(&overwrites, everyone, &exempt);
+        // The helpers' own allow would let them keep talking, so it is locked too. Moderator and
+        // bot overwrites are left alone.
+        assert_eq!(locked.len(), 2);
+        assert_eq!(locked[0].kind, helpers);
+        assert_eq!(locked[0].allow, Permissions::EMBED_LINKS);
+        assert_eq!(locked[0].deny, LOCK_PERMISSIONS);
+        assert_eq!(locked[1].kind, everyone);
+        assert_eq!(locked[1].deny, LOCK_PERMISSIONS);
+    }
+
+    #[test]
+    fn test_saved_overwrite_keeps_unknown_bits() {
+        let overwrite = PermissionOverwrite {
+            allow: Permissions::from_bits_retain(1 << 62),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Member(UserId::new(3)),
+        };
+
+        let saved = SavedOverwrite::new(&overwrite).unwrap();
+        let json = serde_json::to_string(&[&saved]).unwrap();
+        let restored: Vec<SavedOverwrite> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored[0].to_overwrite().unwrap(), overwrite);
+    }
+}
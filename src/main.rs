@@ -1,24 +1,33 @@
+mod antispam;
+mod approval;
+mod audit;
+mod automod;
 mod bot;
+mod cli;
 mod commands;
 mod config;
 mod database;
+mod duration;
 mod error;
+mod events;
+mod i18n;
+mod lockdown;
+mod message_log;
+mod moderation;
+mod pattern;
+mod permissions;
+mod purge;
+mod registration;
+mod reload;
+mod role_menus;
+mod scheduler;
+mod settings;
 
 use anyhow::Result;
-use bot::create_bot;
-use config::Config;
-use tracing::Level;
+use clap::Parser;
+use cli::Cli;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::FmtSubscriber::builder()
-        .with_max_level(Level::WARN)
-        .init();
-
-    let config = Config::from_env()?;
-    let mut client = create_bot(config).await?;
-
-    client.start().await?;
-
-    Ok(())
+    Cli::parse().run().await
 }
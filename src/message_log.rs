@@ -0,0 +1,516 @@
+use crate::i18n::{self, tr};
+use crate::settings::Locale;
+use crate::{audit::truncate_field, bot::Data, database, scheduler::parse_id};
+use anyhow::Result;
+use poise::serenity_prelude::{
+    self as serenity, ChannelId, Colour, CreateEmbed, CreateEmbedFooter, CreateMessage, GuildId,
+    Mentionable, Message, MessageId, MessageUpdateEvent, Timestamp, UserId,
+};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Messages kept for edit and delete logs unless configured otherwise. Older ones are
+/// forgotten first.
+pub const DEFAULT_MESSAGE_CACHE_CAPACITY: usize = 10_000;
+
+/// Discord's limit for an embed description.
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// A logged message as it looked when last seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedMessage {
+    pub channel_id: ChannelId,
+    pub author_id: UserId,
+    pub author_name: String,
+    pub content: String,
+    pub attachments: Vec<String>,
+    pub created_at: Timestamp,
+}
+
+impl From<&Message> for CachedMessage {
+    fn from(message: &Message) -> Self {
+        Self {
+            channel_id: message.channel_id,
+            author_id: message.author.id,
+            author_name: message.author.name.clone(),
+            content: message.content.clone(),
+            attachments: message.attachments.iter().map(|a| a.url.clone()).collect(),
+            created_at: message.timestamp,
+        }
+    }
+}
+
+/// Where a guild's logs go and which channels are watched.
+#[derive(Debug, Default)]
+pub struct LogConfig {
+    pub log_channel: Option<ChannelId>,
+    pub channels: HashSet<ChannelId>,
+}
+
+impl LogConfig {
+    fn watches(&self, channel_id: ChannelId) -> bool {
+        self.log_channel.is_some_and(|log| log != channel_id) && self.channels.contains(&channel_id)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    configs: HashMap<GuildId, Arc<LogConfig>>,
+    messages: HashMap<MessageId, CachedMessage>,
+    /// Insertion order of `messages`, used to evict the oldest entries.
+    order: VecDeque<MessageId>,
+}
+
+/// Bounded cache of messages in watched channels, shared through [`Data`].
+///
+/// Serenity's cache does not always hold a message when it is edited or deleted, so watched
+/// messages are copied here as they arrive.
+#[derive(Debug, Clone)]
+pub struct MessageLog {
+    state: Arc<Mutex<State>>,
+    capacity: Arc<AtomicUsize>,
+}
+
+impl Default for MessageLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_MESSAGE_CACHE_CAPACITY)
+    }
+}
+
+impl MessageLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::default(),
+            capacity: Arc::new(AtomicUsize::new(capacity)),
+        }
+    }
+
+    /// Changes how many messages are kept. A smaller cache is trimmed on the next insert.
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+    }
+
+    /// Logging configuration of `guild_id`, loaded on first use.
+    pub async fn config(&self, database: &SqlitePool, guild_id: GuildId) -> Result<Arc<LogConfig>> {
+        if let Some(config) = self.state.lock().unwrap().configs.get(&guild_id) {
+            return Ok(config.clone());
+        }
+
+        let guild_key = guild_id.to_string();
+        let log_channel = database::get_guild_settings(database, &guild_key)
+            .await?
+            .and_then(|settings| settings.message_log_channel_id)
+            .as_deref()
+            .and_then(parse_id)
+            .map(ChannelId::new);
+        let channels = database::get_message_log_channels(database, &guild_key)
+            .await?
+            .iter()
+            .filter_map(|id| parse_id(id).map(ChannelId::new))
+            .collect();
+
+        let config = Arc::new(LogConfig {
+            log_channel,
+            channels,
+        });
+        self.state
+            .lock()
+            .unwrap()
+            .configs
+            .insert(guild_id, config.clone());
+
+        Ok(config)
+    }
+
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.state.lock().unwrap().configs.remove(&guild_id);
+    }
+
+    fn insert(&self, message_id: MessageId, message: CachedMessage) {
+        let mut state = self.state.lock().unwrap();
+        if state.messages.insert(message_id, message).is_none() {
+            state.order.push_back(message_id);
+        }
+
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        while state.order.len() > capacity {
+            if let Some(oldest) = state.order.pop_front() {
+                state.messages.remove(&oldest);
+            }
+        }
+    }
+
+    fn get(&self, message_id: MessageId) -> Option<CachedMessage> {
+        self.state
+            .lock()
+            .unwrap()
+            .messages
+            .get(&message_id)
+            .cloned()
+    }
+
+    fn remove(&self, message_id: MessageId) -> Option<CachedMessage> {
+        self.state.lock().unwrap().messages.remove(&message_id)
+    }
+}
+
+/// Copies a new message in a watched channel into the cache.
+pub async fn record_message(data: &Data, message: &Message) -> Result<()> {
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+    if message.author.bot {
+        return Ok(());
+    }
+
+    let config = data.message_log.config(&data.database, guild_id).await?;
+    if config.watches(message.channel_id) {
+        data.message_log
+            .insert(message.id, CachedMessage::from(message));
+    }
+
+    Ok(())
+}
+
+/// Logs the before and after content of an edited message.
+#[tracing::instrument(skip(ctx, data, old, event), fields(message_id = %event.id))]
+pub async fn log_edit(
+    ctx: &serenity::Context,
+    data: &Data,
+    old: Option<&Message>,
+    event: &MessageUpdateEvent,
+) -> Result<()> {
+    let Some(guild_id) = event.guild_id else {
+        return Ok(());
+    };
+    // Embed unfurls also arrive as updates but leave the content alone.
+    let Some(content) = &event.content else {
+        return Ok(());
+    };
+
+    let config = data.message_log.config(&data.database, guild_id).await?;
+    let Some(log_channel) = config
+        .log_channel
+        .filter(|_| config.watches(event.channel_id))
+    else {
+        return Ok(());
+    };
+
+    let before = data
+        .message_log
+        .get(event.id)
+        .or_else(|| old.map(CachedMessage::from));
+    if before.as_ref().is_some_and(|b| b.content == *content) {
+        return Ok(());
+    }
+
+    let after = match (&before, &event.author) {
+        (Some(before), _) => CachedMessage {
+            content: content.clone(),
+            attachments: event
+                .attachments
+                .as_ref()
+                .map(|attachments| attachments.iter().map(|a| a.url.clone()).collect())
+                .unwrap_or_else(|| before.attachments.clone()),
+            ..before.clone()
+        },
+        (None, Some(author)) if !author.bot => CachedMessage {
+            channel_id: event.channel_id,
+            author_id: author.id,
+            author_name: author.name.clone(),
+            content: content.clone(),
+            attachments: event
+                .attachments
+                .iter()
+                .flatten()
+                .map(|a| a.url.clone())
+                .collect(),
+            created_at: event.timestamp.unwrap_or_else(Timestamp::now),
+        },
+        _ => return Ok(()),
+    };
+    data.message_log.insert(event.id, after.clone());
+
+    let locale = i18n::guild_locale(data, guild_id).await;
+    let embed = CreateEmbed::new()
+        .title(tr!(locale, "messagelog-edited-title"))
+        .colour(Colour::GOLD)
+        .description(format!(
+            "{}\n{}",
+            tr!(
+                locale,
+                "messagelog-edited-description",
+                user = after.author_id.mention(),
+                channel = after.channel_id.mention(),
+            ),
+            event.id.link(event.channel_id, Some(guild_id))
+        ))
+        .field(
+            tr!(locale, "messagelog-field-before"),
+            match &before {
+                Some(before) => quote(locale, &before.content),
+                None => tr!(locale, "messagelog-not-cached"),
+            },
+            false,
+        )
+        .field(
+            tr!(locale, "messagelog-field-after"),
+            quote(locale, &after.content),
+            false,
+        );
+
+    post(
+        ctx,
+        log_channel,
+        with_details(locale, embed, &after, event.id),
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Logs the content of a deleted message, if it was cached.
+#[tracing::instrument(skip(ctx, data))]
+pub async fn log_delete(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<()> {
+    let Some(guild_id) = guild_id else {
+        return Ok(());
+    };
+    let deleted = data.message_log.remove(message_id);
+
+    let config = data.message_log.config(&data.database, guild_id).await?;
+    let Some(log_channel) = config.log_channel.filter(|_| config.watches(channel_id)) else {
+        return Ok(());
+    };
+
+    let locale = i18n::guild_locale(data, guild_id).await;
+    let embed = CreateEmbed::new()
+        .title(tr!(locale, "messagelog-deleted-title"))
+        .colour(Colour::RED);
+    let embed = match &deleted {
+        Some(message) => with_details(
+            locale,
+            embed
+                .description(tr!(
+                    locale,
+                    "messagelog-deleted-description",
+                    user = message.author_id.mention(),
+                    channel = channel_id.mention(),
+                ))
+                .field(
+                    tr!(locale, "messagelog-field-content"),
+                    quote(locale, &message.content),
+                    false,
+                ),
+            message,
+            message_id,
+        ),
+        None => embed
+            .description(tr!(
+                locale,
+                "messagelog-deleted-uncached",
+                channel = channel_id.mention(),
+            ))
+            .footer(CreateEmbedFooter::new(tr!(
+                locale,
+                "messagelog-footer-message",
+                id = message_id,
+            ))),
+    };
+
+    post(ctx, log_channel, embed).await;
+
+    Ok(())
+}
+
+/// Logs a bulk deletion as one summary instead of one post per message.
+#[tracing::instrument(skip(ctx, data, message_ids), fields(count = %message_ids.len()))]
+pub async fn log_bulk_delete(
+    ctx: &serenity::Context,
+    data: &Data,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    message_ids: &[MessageId],
+) -> Result<()> {
+    let Some(guild_id) = guild_id else {
+        return Ok(());
+    };
+    let mut deleted: Vec<CachedMessage> = message_ids
+        .iter()
+        .filter_map(|id| data.message_log.remove(*id))
+        .collect();
+    deleted.sort_by_key(|message| message.created_at);
+
+    let config = data.message_log.config(&data.database, guild_id).await?;
+    let Some(log_channel) = config.log_channel.filter(|_| config.watches(channel_id)) else {
+        return Ok(());
+    };
+
+    let locale = i18n::guild_locale(data, guild_id).await;
+    let lines: Vec<String> = deleted
+        .iter()
+        .map(|message| {
+            let mut line = format!("**{}:** {}", message.author_name, message.content);
+            if !message.attachments.is_empty() {
+                line.push(' ');
+                line.push_str(&tr!(
+                    locale,
+                    "messagelog-bulk-attachments",
+                    count = message.attachments.len(),
+                ));
+            }
+            line
+        })
+        .collect();
+
+    let summary = tr!(
+        locale,
+        "messagelog-bulk-description",
+        count = message_ids.len(),
+        channel = channel_id.mention(),
+        cached = deleted.len(),
+    );
+    let embed = CreateEmbed::new()
+        .title(tr!(locale, "messagelog-bulk-title"))
+        .colour(Colour::DARK_RED)
+        .description(truncate_description(&format!(
+            "{}\n\n{}",
+            summary,
+            lines.join("\n")
+        )))
+        .timestamp(Timestamp::now());
+
+    post(ctx, log_channel, embed).await;
+
+    Ok(())
+}
+
+/// Adds the author, attachments and ids shared by edit and delete logs.
+fn with_details(
+    locale: Locale,
+    embed: CreateEmbed,
+    message: &CachedMessage,
+    message_id: MessageId,
+) -> CreateEmbed {
+    let mut embed = embed
+        .field(
+            tr!(locale, "messagelog-field-author"),
+            format!("<@{}> ({})", message.author_id, message.author_name),
+            true,
+        )
+        .field(
+            tr!(locale, "messagelog-field-sent"),
+            format!("<t:{}:f>", message.created_at.unix_timestamp()),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(tr!(
+            locale,
+            "messagelog-footer-details",
+            user = message.author_id,
+            id = message_id,
+        )))
+        .timestamp(Timestamp::now());
+
+    if !message.attachments.is_empty() {
+        embed = embed.field(
+            tr!(locale, "messagelog-field-attachments"),
+            truncate_field(&message.attachments.join("\n")),
+            false,
+        );
+    }
+
+    embed
+}
+
+fn quote(locale: Locale, content: &str) -> String {
+    if content.is_empty() {
+        return tr!(locale, "messagelog-no-text");
+    }
+
+    truncate_field(content)
+}
+
+fn truncate_description(value: &str) -> String {
+    if value.chars().count() <= MAX_DESCRIPTION_LENGTH {
+        return value.to_string();
+    }
+
+    let mut truncated: String = value.chars().take(MAX_DESCRIPTION_LENGTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+async fn post(ctx: &serenity::Context, log_channel: ChannelId, embed: CreateEmbed) {
+    if let Err(e) = log_channel
+        .send_message(&ctx.http, CreateMessage::new().embed(embed))
+        .await
+    {
+        tracing::warn!(channel_id = %log_channel, error = %e, "Failed to post to message log");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &str) -> CachedMessage {
+        CachedMessage {
+            channel_id: ChannelId::new(1),
+            author_id: UserId::new(2),
+            author_name: "member".to_string(),
+            content: content.to_string(),
+            attachments: Vec::new(),
+            created_at: Timestamp::now(),
+        }
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest() {
+        let log = MessageLog::new(10);
+        for id in 1..=12 {
+            log.insert(MessageId::new(id), message("hi"));
+        }
+
+        assert!(log.get(MessageId::new(1)).is_none());
+        assert!(log.get(MessageId::new(2)).is_none());
+        assert!(log.get(MessageId::new(3)).is_some());
+        assert_eq!(log.state.lock().unwrap().messages.len(), 10);
+
+        log.set_capacity(5);
+        log.insert(MessageId::new(13), message("hi"));
+        assert_eq!(log.state.lock().unwrap().messages.len(), 5);
+        assert!(log.get(MessageId::new(13)).is_some());
+    }
+
+    #[test]
+    fn test_edits_replace_cached_content() {
+        let log = MessageLog::default();
+        log.insert(MessageId::new(1), message("before"));
+        log.insert(MessageId::new(1), message("after"));
+
+        assert_eq!(log.get(MessageId::new(1)).unwrap().content, "after");
+        assert_eq!(log.state.lock().unwrap().order.len(), 1);
+        assert!(log.remove(MessageId::new(1)).is_some());
+        assert!(log.get(MessageId::new(1)).is_none());
+    }
+
+    #[test]
+    fn test_log_channel_is_never_watched() {
+        let config = LogConfig {
+            log_channel: Some(ChannelId::new(1)),
+            channels: HashSet::from([ChannelId::new(1), ChannelId::new(2)]),
+        };
+
+        assert!(config.watches(ChannelId::new(2)));
+        assert!(!config.watches(ChannelId::new(1)));
+        assert!(!config.watches(ChannelId::new(3)));
+        assert!(!LogConfig::default().watches(ChannelId::new(2)));
+    }
+}
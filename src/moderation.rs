@@ -0,0 +1,575 @@
+use crate::audit::{self, AuditAction, AuditOutcome, AuditRecord};
+use crate::database;
+use crate::i18n::tr;
+use crate::settings::Locale;
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{
+    CacheHttp, CreateMessage, EditMember, Guild, GuildId, Http, HttpError, RoleId, Timestamp, User,
+    UserId,
+};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Longest timeout Discord accepts.
+pub const MAX_TIMEOUT_DAYS: i64 = 28;
+
+/// Discord truncates audit log reasons longer than this.
+const MAX_REASON_LENGTH: usize = 512;
+
+/// Discord's error code for unbanning a user who is not banned.
+const UNKNOWN_BAN: isize = 10026;
+
+/// Discord's error code for a user who is not a member of the guild.
+const UNKNOWN_MEMBER: isize = 10007;
+
+/// A punishment applied to a guild member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sanction {
+    Timeout(Duration),
+    Kick,
+    Ban { delete_message_days: u8 },
+}
+
+impl Sanction {
+    pub fn audit_action(&self) -> AuditAction {
+        match self {
+            Self::Timeout(_) => AuditAction::Timeout,
+            Self::Kick => AuditAction::Kick,
+            Self::Ban { .. } => AuditAction::Ban,
+        }
+    }
+
+    /// Name of the sanction in replies, e.g. "10m timeout".
+    pub fn label(&self, locale: Locale) -> String {
+        match self {
+            Self::Timeout(duration) => tr!(
+                locale,
+                "sanction-timeout",
+                duration = crate::duration::format(*duration)
+            ),
+            Self::Kick => tr!(locale, "sanction-kick"),
+            Self::Ban { .. } => tr!(locale, "sanction-ban"),
+        }
+    }
+}
+
+impl fmt::Display for Sanction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout(duration) => write!(f, "{} timeout", crate::duration::format(*duration)),
+            Self::Kick => f.write_str("kick"),
+            Self::Ban { .. } => f.write_str("ban"),
+        }
+    }
+}
+
+/// Automatic punishments configurable for warning thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum EscalationAction {
+    #[name = "timeout"]
+    Timeout,
+    #[name = "kick"]
+    Kick,
+    #[name = "ban"]
+    Ban,
+}
+
+impl EscalationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Kick => "kick",
+            Self::Ban => "ban",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "timeout" => Some(Self::Timeout),
+            "kick" => Some(Self::Kick),
+            "ban" => Some(Self::Ban),
+            _ => None,
+        }
+    }
+
+    pub fn sanction(&self, duration_minutes: Option<i64>) -> Sanction {
+        match self {
+            Self::Timeout => Sanction::Timeout(Duration::minutes(duration_minutes.unwrap_or(60))),
+            Self::Kick => Sanction::Kick,
+            Self::Ban => Sanction::Ban {
+                delete_message_days: 0,
+            },
+        }
+    }
+}
+
+/// Who performed a moderation action.
+#[derive(Debug, Clone)]
+pub struct Moderator {
+    pub id: UserId,
+    pub name: String,
+}
+
+impl Moderator {
+    pub fn new(id: UserId, name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+        }
+    }
+}
+
+impl From<&User> for Moderator {
+    fn from(user: &User) -> Self {
+        Self::new(user.id, user.name.clone())
+    }
+}
+
+/// Role positions of a guild, copied out of the cache so they can be held across awaits.
+#[derive(Debug, Clone)]
+pub struct Hierarchy {
+    owner_id: UserId,
+    positions: HashMap<RoleId, u16>,
+}
+
+impl Hierarchy {
+    pub fn new(guild: &Guild) -> Self {
+        Self {
+            owner_id: guild.owner_id,
+            positions: guild
+                .roles
+                .values()
+                .map(|role| (role.id, role.position))
+                .collect(),
+        }
+    }
+
+    /// Position of the highest role in `roles`, 0 when only @everyone applies.
+    fn highest(&self, roles: &[RoleId]) -> u16 {
+        roles
+            .iter()
+            .filter_map(|role| self.positions.get(role))
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether `actor` may act on `target`: the owner outranks everyone, otherwise the actor's
+    /// highest role must be strictly above the target's.
+    pub fn outranks(
+        &self,
+        actor: UserId,
+        actor_roles: &[RoleId],
+        target: UserId,
+        target_roles: &[RoleId],
+    ) -> bool {
+        if actor == target || target == self.owner_id {
+            return false;
+        }
+        if actor == self.owner_id {
+            return true;
+        }
+
+        self.highest(actor_roles) > self.highest(target_roles)
+    }
+
+    /// Whether `actor` may hand out `role`, which Discord only allows below their highest role.
+    pub fn outranks_role(&self, actor: UserId, actor_roles: &[RoleId], role: RoleId) -> bool {
+        if actor == self.owner_id {
+            return true;
+        }
+
+        self.positions
+            .get(&role)
+            .is_some_and(|position| self.highest(actor_roles) > *position)
+    }
+}
+
+pub fn truncate_reason(reason: &str) -> String {
+    reason.chars().take(MAX_REASON_LENGTH).collect()
+}
+
+/// Applies `sanction` to `user_id` through the Discord API.
+#[tracing::instrument(skip(http))]
+pub async fn apply_sanction(
+    http: &Http,
+    guild_id: GuildId,
+    user_id: UserId,
+    sanction: Sanction,
+    reason: &str,
+) -> serenity::Result<()> {
+    let reason = truncate_reason(reason);
+
+    match sanction {
+        Sanction::Timeout(duration) => {
+            let duration = duration.min(Duration::days(MAX_TIMEOUT_DAYS));
+            let until = Timestamp::from_unix_timestamp((Utc::now() + duration).timestamp())
+                .map_err(|_| serenity::Error::Other("Invalid timeout duration"))?;
+
+            guild_id
+                .edit_member(
+                    http,
+                    user_id,
+                    EditMember::new()
+                        .disable_communication_until_datetime(until)
+                        .audit_log_reason(&reason),
+                )
+                .await?;
+        }
+        Sanction::Kick => guild_id.kick_with_reason(http, user_id, &reason).await?,
+        Sanction::Ban {
+            delete_message_days,
+        } => {
+            guild_id
+                .ban_with_reason(http, user_id, delete_message_days.min(7), &reason)
+                .await?
+        }
+    }
+
+    tracing::info!(
+        guild_id = %guild_id,
+        user_id = %user_id,
+        sanction = %sanction,
+        "Sanction applied"
+    );
+
+    Ok(())
+}
+
+/// Lifts the ban of `user_id`, returning `false` when they were not banned.
+#[tracing::instrument(skip(http))]
+pub async fn lift_ban(
+    http: &Http,
+    guild_id: GuildId,
+    user_id: UserId,
+    reason: &str,
+) -> serenity::Result<bool> {
+    let reason = truncate_reason(reason);
+
+    match http.remove_ban(guild_id, user_id, Some(&reason)).await {
+        Ok(()) => {
+            tracing::info!(guild_id = %guild_id, user_id = %user_id, "Ban lifted");
+            Ok(true)
+        }
+        Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+            if response.error.code == UNKNOWN_BAN =>
+        {
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether `error` means the user is not (or no longer) a member of the guild.
+pub fn is_unknown_member(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.error.code == UNKNOWN_MEMBER
+    )
+}
+
+/// Tells `user` about `sanction` by DM in `locale`, their language, returning whether the
+/// message was delivered.
+///
+/// Members often have DMs from servers disabled, so failures are only logged.
+#[tracing::instrument(skip(cache_http))]
+pub async fn notify_target(
+    cache_http: impl CacheHttp,
+    locale: Locale,
+    user: &User,
+    guild_name: &str,
+    sanction: Sanction,
+    expires_in: Option<Duration>,
+    reason: &str,
+) -> bool {
+    let content = match (sanction, expires_in) {
+        (Sanction::Timeout(duration), _) => tr!(
+            locale,
+            "sanction-dm-timeout",
+            server = guild_name,
+            duration = crate::duration::format(duration),
+            reason = reason
+        ),
+        (Sanction::Kick, _) => tr!(
+            locale,
+            "sanction-dm-kick",
+            server = guild_name,
+            reason = reason
+        ),
+        (Sanction::Ban { .. }, Some(expires_in)) => tr!(
+            locale,
+            "sanction-dm-tempban",
+            server = guild_name,
+            duration = crate::duration::format(expires_in),
+            reason = reason
+        ),
+        (Sanction::Ban { .. }, None) => tr!(
+            locale,
+            "sanction-dm-ban",
+            server = guild_name,
+            reason = reason
+        ),
+    };
+
+    let message = CreateMessage::new().content(content);
+
+    match user.dm(cache_http, message).await {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::debug!(user_id = %user.id, error = %e, "Could not DM sanctioned user");
+            false
+        }
+    }
+}
+
+/// Times out `user_id` on behalf of `actor` and records it, returning the error if it failed.
+#[tracing::instrument(skip(http, database))]
+pub async fn timeout_member(
+    http: &Http,
+    database: &SqlitePool,
+    guild_id: GuildId,
+    user_id: UserId,
+    duration: Duration,
+    actor: &Moderator,
+    reason: &str,
+) -> Option<String> {
+    let sanction = Sanction::Timeout(duration);
+    let error = apply_sanction(http, guild_id, user_id, sanction, reason)
+        .await
+        .err()
+        .map(|e| e.to_string());
+
+    audit::record(
+        http,
+        database,
+        AuditRecord::new(guild_id, actor.id, &actor.name, AuditAction::Timeout)
+            .target(format!("<@{}>", user_id))
+            .parameters(serde_json::json!({
+                "reason": reason,
+                "duration_minutes": duration.num_minutes(),
+                "error": error,
+            }))
+            .outcome(match error {
+                Some(_) => AuditOutcome::Failure,
+                None => AuditOutcome::Success,
+            })
+            .counts(0, error.is_none() as i64, error.is_some() as i64),
+    )
+    .await;
+
+    error
+}
+
+/// Result of [`issue_warning`].
+#[derive(Debug)]
+pub struct WarningOutcome {
+    pub warning_id: i64,
+    pub case_number: Option<i64>,
+    pub active_warnings: i64,
+    pub escalation: Option<EscalationOutcome>,
+}
+
+#[derive(Debug)]
+pub struct EscalationOutcome {
+    pub sanction: Sanction,
+    pub threshold: i64,
+    pub error: Option<String>,
+}
+
+/// Stores a warning, records it, and applies the escalation configured for the new warning count.
+///
+/// Escalations fire when the active warning count reaches a threshold, once per member and
+/// threshold, so revoking a warning and issuing another does not punish twice for the same
+/// level.
+#[tracing::instrument(skip(http, database))]
+pub async fn issue_warning(
+    http: &Http,
+    database: &SqlitePool,
+    guild_id: GuildId,
+    target: &User,
+    moderator: &Moderator,
+    escalator: &Moderator,
+    reason: &str,
+) -> Result<WarningOutcome> {
+    let guild_key = guild_id.to_string();
+    let user_key = target.id.to_string();
+
+    let warning_id = database::save_warning(
+        database,
+        &guild_key,
+        &user_key,
+        &target.name,
+        &moderator.id.to_string(),
+        &moderator.name,
+        reason,
+    )
+    .await?;
+
+    let active_warnings = database::count_active_warnings(database, &guild_key, &user_key).await?;
+
+    let case_number = audit::record(
+        http,
+        database,
+        AuditRecord::new(guild_id, moderator.id, &moderator.name, AuditAction::Warn)
+            .target(format!("<@{}>", target.id))
+            .parameters(serde_json::json!({
+                "warning_id": warning_id,
+                "reason": reason,
+                "active_warnings": active_warnings,
+            }))
+            .counts(0, 1, 0),
+    )
+    .await
+    .and_then(|recorded| recorded.case_number);
+
+    let escalation = database::get_warning_escalation(database, &guild_key, active_warnings)
+        .await
+        .context("Failed to look up warning escalation")?;
+
+    let escalation = match escalation {
+        Some(_)
+            if !database::mark_warning_escalation_fired(
+                database,
+                &guild_key,
+                &user_key,
+                active_warnings,
+                warning_id,
+            )
+            .await? =>
+        {
+            tracing::info!(
+                guild_id = %guild_id,
+                user_id = %target.id,
+                threshold = %active_warnings,
+                "Warning escalation already fired for this member, skipping"
+            );
+            None
+        }
+        Some(escalation) => {
+            let Some(action) = EscalationAction::parse(&escalation.action) else {
+                tracing::warn!(action = %escalation.action, "Unknown escalation action");
+                return Ok(WarningOutcome {
+                    warning_id,
+                    case_number,
+                    active_warnings,
+                    escalation: None,
+                });
+            };
+
+            let sanction = action.sanction(escalation.duration_minutes);
+            let escalation_reason =
+                format!("Reached {} warnings (latest: {})", active_warnings, reason);
+
+            let result =
+                apply_sanction(http, guild_id, target.id, sanction, &escalation_reason).await;
+            let error = result.err().map(|e| e.to_string());
+
+            if let Some(error) = &error {
+                tracing::warn!(
+                    guild_id = %guild_id,
+                    user_id = %target.id,
+                    sanction = %sanction,
+                    error = %error,
+                    "Warning escalation failed"
+                );
+            }
+
+            audit::record(
+                http,
+                database,
+                AuditRecord::new(
+                    guild_id,
+                    escalator.id,
+                    &escalator.name,
+                    sanction.audit_action(),
+                )
+                .target(format!("<@{}>", target.id))
+                .parameters(serde_json::json!({
+                    "reason": escalation_reason,
+                    "escalation_threshold": active_warnings,
+                    "warning_id": warning_id,
+                    "duration_minutes": match sanction {
+                        Sanction::Timeout(duration) => Some(duration.num_minutes()),
+                        _ => None,
+                    },
+                    "error": error,
+                }))
+                .outcome(match error {
+                    Some(_) => AuditOutcome::Failure,
+                    None => AuditOutcome::Success,
+                })
+                .counts(0, error.is_none() as i64, error.is_some() as i64),
+            )
+            .await;
+
+            Some(EscalationOutcome {
+                sanction,
+                threshold: active_warnings,
+                error,
+            })
+        }
+        None => None,
+    };
+
+    Ok(WarningOutcome {
+        warning_id,
+        case_number,
+        active_warnings,
+        escalation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hierarchy() -> Hierarchy {
+        Hierarchy {
+            owner_id: UserId::new(1),
+            positions: HashMap::from([(RoleId::new(10), 5), (RoleId::new(11), 2)]),
+        }
+    }
+
+    #[test]
+    fn test_higher_role_outranks_lower() {
+        let h = hierarchy();
+        let (moderator, member) = (UserId::new(2), UserId::new(3));
+
+        assert!(h.outranks(moderator, &[RoleId::new(10)], member, &[RoleId::new(11)]));
+        assert!(h.outranks(moderator, &[RoleId::new(11)], member, &[]));
+        assert!(!h.outranks(member, &[RoleId::new(11)], moderator, &[RoleId::new(10)]));
+    }
+
+    #[test]
+    fn test_equal_roles_do_not_outrank() {
+        let h = hierarchy();
+        let roles = [RoleId::new(10)];
+
+        assert!(!h.outranks(UserId::new(2), &roles, UserId::new(3), &roles));
+        assert!(!h.outranks(UserId::new(2), &[], UserId::new(3), &[]));
+        assert!(!h.outranks(UserId::new(2), &roles, UserId::new(2), &[]));
+    }
+
+    #[test]
+    fn test_owner_outranks_everyone() {
+        let h = hierarchy();
+        let owner = UserId::new(1);
+
+        assert!(h.outranks(owner, &[], UserId::new(2), &[RoleId::new(10)]));
+        assert!(!h.outranks(UserId::new(2), &[RoleId::new(10)], owner, &[]));
+    }
+
+    #[test]
+    fn test_outranks_role() {
+        let h = hierarchy();
+        let moderator = UserId::new(2);
+
+        assert!(h.outranks_role(moderator, &[RoleId::new(10)], RoleId::new(11)));
+        assert!(!h.outranks_role(moderator, &[RoleId::new(10)], RoleId::new(10)));
+        assert!(!h.outranks_role(moderator, &[RoleId::new(10)], RoleId::new(99)));
+        assert!(h.outranks_role(UserId::new(1), &[], RoleId::new(10)));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::error::{Result, bot_error};
+use crate::i18n::tr;
+use crate::settings::Locale;
+use regex::{Regex, RegexBuilder};
+
+/// Longest pattern a moderator may submit, in characters.
+pub const MAX_PATTERN_LENGTH: usize = 512;
+
+/// Upper bound on the compiled program size, well below the regex crate default of 10 MiB.
+const REGEX_SIZE_LIMIT: usize = 256 * 1024;
+
+/// Upper bound on the lazy DFA cache used while matching.
+const DFA_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Maximum nesting depth of groups and repetitions.
+const NEST_LIMIT: u32 = 32;
+
+/// Most entries accepted in a word list.
+pub const MAX_WORDS: usize = 200;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PatternOptions {
+    pub case_insensitive: bool,
+    pub multi_line: bool,
+    pub literal: bool,
+}
+
+impl PatternOptions {
+    fn flags(&self) -> String {
+        let mut flags = String::new();
+        if self.literal {
+            flags.push_str("literal ");
+        }
+        if self.case_insensitive {
+            flags.push('i');
+        }
+        if self.multi_line {
+            flags.push('m');
+        }
+
+        flags.trim_end().to_string()
+    }
+
+    /// Short flag summary for audit records, e.g. `im` or `literal`.
+    pub fn describe(&self) -> String {
+        let flags = self.flags();
+        if flags.is_empty() {
+            "none".to_string()
+        } else {
+            flags
+        }
+    }
+
+    /// [`describe`](Self::describe) for replies in `locale`.
+    pub fn label(&self, locale: Locale) -> String {
+        let flags = self.flags();
+        if flags.is_empty() {
+            tr!(locale, "pattern-no-flags")
+        } else {
+            flags
+        }
+    }
+}
+
+/// Compiles moderator supplied input with size and complexity limits.
+///
+/// Errors are returned as [`crate::error::BotError`]s in `locale` so the reason is shown to the
+/// invoker.
+#[tracing::instrument]
+pub fn compile(locale: Locale, pattern: &str, options: PatternOptions) -> Result<Regex> {
+    if pattern.is_empty() {
+        return Err(bot_error(tr!(locale, "pattern-empty")));
+    }
+
+    let length = pattern.chars().count();
+    if length > MAX_PATTERN_LENGTH {
+        return Err(bot_error(tr!(
+            locale,
+            "pattern-too-long",
+            length = length,
+            max = MAX_PATTERN_LENGTH
+        )));
+    }
+
+    let source = if options.literal {
+        regex::escape(pattern)
+    } else {
+        pattern.to_string()
+    };
+
+    RegexBuilder::new(&source)
+        .case_insensitive(options.case_insensitive)
+        .multi_line(options.multi_line)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(DFA_SIZE_LIMIT)
+        .nest_limit(NEST_LIMIT)
+        .build()
+        .map_err(|e| {
+            tracing::debug!(pattern = %pattern, error = %e, "Rejected pattern");
+            match e {
+                regex::Error::CompiledTooBig(_) => bot_error(tr!(locale, "pattern-too-complex")),
+                regex::Error::Syntax(message) => {
+                    bot_error(tr!(locale, "pattern-invalid", error = message))
+                }
+                other => bot_error(tr!(locale, "pattern-invalid", error = other)),
+            }
+        })
+}
+
+/// Compiles a word list into one case-insensitive regex matching any entry as a whole word.
+///
+/// Entries are matched literally, so moderators can list words containing punctuation.
+#[tracing::instrument]
+pub fn compile_words(locale: Locale, words: &[String]) -> Result<Regex> {
+    if words.is_empty() {
+        return Err(bot_error(tr!(locale, "pattern-words-empty")));
+    }
+    if words.len() > MAX_WORDS {
+        return Err(bot_error(tr!(
+            locale,
+            "pattern-words-too-long",
+            count = words.len(),
+            max = MAX_WORDS
+        )));
+    }
+
+    let alternatives = words
+        .iter()
+        .map(|word| regex::escape(word))
+        .collect::<Vec<_>>()
+        .join("|");
+
+    // `\b` would not match around entries that start or end with punctuation.
+    RegexBuilder::new(&format!(r"(?:^|\W)(?:{})(?:\W|$)", alternatives))
+        .case_insensitive(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(DFA_SIZE_LIMIT)
+        .build()
+        .map_err(|e| {
+            tracing::debug!(error = %e, "Rejected word list");
+            bot_error(tr!(locale, "pattern-words-too-large"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_options() {
+        let literal = PatternOptions {
+            literal: true,
+            ..Default::default()
+        };
+        let regex = compile(Locale::default(), "a.b", literal).unwrap();
+        assert!(regex.is_match("xa.by"));
+        assert!(!regex.is_match("axb"));
+
+        let insensitive = PatternOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert!(
+            compile(Locale::default(), "spam", insensitive)
+                .unwrap()
+                .is_match("SPAM")
+        );
+
+        let multi_line = PatternOptions {
+            multi_line: true,
+            ..Default::default()
+        };
+        assert!(
+            compile(Locale::default(), "^buy", multi_line)
+                .unwrap()
+                .is_match("hi\nbuy now")
+        );
+        assert!(
+            !compile(Locale::default(), "^buy", PatternOptions::default())
+                .unwrap()
+                .is_match("hi\nbuy now")
+        );
+    }
+
+    #[test]
+    fn test_pattern_limits() {
+        assert!(compile(Locale::default(), "", PatternOptions::default()).is_err());
+        assert!(
+            compile(
+                Locale::default(),
+                &"a".repeat(MAX_PATTERN_LENGTH + 1),
+                PatternOptions::default()
+            )
+            .is_err()
+        );
+        assert!(compile(Locale::default(), "(unclosed", PatternOptions::default()).is_err());
+        assert!(
+            compile(
+                Locale::default(),
+                r"(\w{100}){100}",
+                PatternOptions::default()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_compile_words() {
+        let words = vec!["spam".to_string(), "f*ck".to_string()];
+        let regex = compile_words(Locale::default(), &words).unwrap();
+
+        assert!(regex.is_match("no SPAM please"));
+        assert!(regex.is_match("f*ck"));
+        assert!(!regex.is_match("spammer"));
+        assert!(!regex.is_match("fck"));
+
+        assert!(compile_words(Locale::default(), &[]).is_err());
+    }
+}
@@ -0,0 +1,441 @@
+use crate::bot::Data;
+use crate::database::{self, PermissionRule};
+use crate::error::{Context, Error, bot_error};
+use crate::i18n::{self, tr};
+use crate::settings::Locale;
+use anyhow::Result;
+use poise::serenity_prelude::{GuildId, Permissions, RoleId, UserId};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+type Command = poise::Command<Data, Error>;
+
+/// Whether a rule lets its subject use a command or keeps them from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+impl Effect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Deny => "deny",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "allow" => Some(Self::Allow),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Who a rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subject {
+    User(UserId),
+    Role(RoleId),
+    /// Everyone holding all of these permissions.
+    Permission(Permissions),
+}
+
+impl Subject {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::User(_) => "user",
+            Self::Role(_) => "role",
+            Self::Permission(_) => "permission",
+        }
+    }
+
+    /// The stored id: a user or role id, or the permission bits.
+    pub fn id(&self) -> String {
+        match self {
+            Self::User(user_id) => user_id.to_string(),
+            Self::Role(role_id) => role_id.to_string(),
+            Self::Permission(permissions) => permissions.bits().to_string(),
+        }
+    }
+
+    pub fn parse(kind: &str, id: &str) -> Option<Self> {
+        match kind {
+            "user" => id
+                .parse()
+                .ok()
+                .filter(|&id| id != 0)
+                .map(|id| Self::User(UserId::new(id))),
+            "role" => id
+                .parse()
+                .ok()
+                .filter(|&id| id != 0)
+                .map(|id| Self::Role(RoleId::new(id))),
+            "permission" => id
+                .parse()
+                .ok()
+                .map(Permissions::from_bits_truncate)
+                .filter(|permissions| !permissions.is_empty())
+                .map(Self::Permission),
+            _ => None,
+        }
+    }
+
+    fn matches(&self, invoker: &Invoker) -> bool {
+        match self {
+            Self::User(user_id) => *user_id == invoker.user_id,
+            Self::Role(role_id) => invoker.roles.contains(role_id),
+            Self::Permission(permissions) => invoker.permissions.contains(*permissions),
+        }
+    }
+
+    /// Who the rule applies to, as shown in replies.
+    pub fn label(&self, locale: Locale) -> String {
+        match self {
+            Self::User(user_id) => format!("<@{}>", user_id),
+            Self::Role(role_id) => format!("<@&{}>", role_id),
+            Self::Permission(permissions) => tr!(
+                locale,
+                "permissions-subject-permission",
+                permissions = permissions
+            ),
+        }
+    }
+}
+
+/// A stored rule, parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub id: i64,
+    /// Space separated command path such as `admin` or `suggest remove_song`.
+    pub command: String,
+    pub subject: Subject,
+    pub effect: Effect,
+}
+
+impl Rule {
+    fn from_stored(rule: &PermissionRule) -> Option<Self> {
+        Some(Self {
+            id: rule.id,
+            command: rule.command.clone(),
+            subject: Subject::parse(&rule.subject_type, &rule.subject_id)?,
+            effect: Effect::parse(&rule.effect)?,
+        })
+    }
+}
+
+/// The member running a command, as far as rules care.
+#[derive(Debug, Clone)]
+pub struct Invoker {
+    pub user_id: UserId,
+    pub roles: Vec<RoleId>,
+    pub permissions: Permissions,
+}
+
+/// The decision of the rules for `command`, or `None` when no rule applies.
+///
+/// Rules on the command itself beat rules on the groups it is in. For the same command a rule
+/// for the user beats rules for their roles and permissions, and among those a deny beats an
+/// allow.
+pub fn evaluate(rules: &[Rule], command: &str, invoker: &Invoker) -> Option<Effect> {
+    let parts: Vec<&str> = command.split(' ').collect();
+
+    for depth in (1..=parts.len()).rev() {
+        let target = parts[..depth].join(" ");
+        let matching: Vec<&Rule> = rules
+            .iter()
+            .filter(|rule| rule.command == target && rule.subject.matches(invoker))
+            .collect();
+
+        if let Some(rule) = matching
+            .iter()
+            .find(|rule| matches!(rule.subject, Subject::User(_)))
+        {
+            return Some(rule.effect);
+        }
+        if matching.iter().any(|rule| rule.effect == Effect::Deny) {
+            return Some(Effect::Deny);
+        }
+        if !matching.is_empty() {
+            return Some(Effect::Allow);
+        }
+    }
+
+    None
+}
+
+/// Permissions a command asked for with `required_permissions`, kept in its `custom_data` once
+/// [`take_over`] has moved them out of poise's hands.
+struct DefaultPermissions(Permissions);
+
+/// Moves `required_permissions` of every command into [`permission_check`], so rules can grant a
+/// command to members without those permissions. Members without rules keep needing them.
+///
+/// `default_member_permissions` stays, so Discord keeps hiding the slash commands from members
+/// without them until a server admin allows them under Integrations.
+pub fn take_over(mut commands: Vec<Command>) -> Vec<Command> {
+    fn visit(commands: &mut [Command]) {
+        for command in commands {
+            let required = std::mem::take(&mut command.required_permissions);
+            command.custom_data = Box::new(DefaultPermissions(required));
+            visit(&mut command.subcommands);
+        }
+    }
+
+    visit(&mut commands);
+    commands
+}
+
+/// Permissions a command and its parents ask for when no rule applies.
+pub fn default_permissions(command: &Command, parents: &[&Command]) -> Permissions {
+    parents
+        .iter()
+        .copied()
+        .chain(std::iter::once(command))
+        .map(|command| match command.custom_data.downcast_ref() {
+            Some(DefaultPermissions(permissions)) => *permissions,
+            None => command.required_permissions,
+        })
+        .fold(Permissions::empty(), |all, permissions| all | permissions)
+}
+
+/// Space separated path of a command, e.g. `admin automod add`.
+pub fn command_path(command: &Command, parents: &[&Command]) -> String {
+    parents
+        .iter()
+        .copied()
+        .chain(std::iter::once(command))
+        .map(|command| command.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds a command or group by its path, ignoring case and extra spaces. Returns the normalized
+/// path.
+pub fn find_path(commands: &[Command], path: &str) -> Option<String> {
+    let mut found = Vec::new();
+    let mut level = commands;
+
+    for part in path.split_whitespace() {
+        let command = level
+            .iter()
+            .find(|command| command.name.eq_ignore_ascii_case(part))?;
+        found.push(command.name.as_str());
+        level = &command.subcommands;
+    }
+
+    (!found.is_empty()).then(|| found.join(" "))
+}
+
+/// Parsed permission rules, loaded on first use and dropped when they change.
+#[derive(Debug, Clone, Default)]
+pub struct PermissionCache {
+    rules: Arc<RwLock<HashMap<GuildId, Arc<Vec<Rule>>>>>,
+}
+
+impl PermissionCache {
+    pub async fn rules(&self, database: &SqlitePool, guild_id: GuildId) -> Result<Arc<Vec<Rule>>> {
+        if let Some(rules) = self.rules.read().unwrap().get(&guild_id) {
+            return Ok(rules.clone());
+        }
+
+        let stored = database::get_permission_rules(database, &guild_id.to_string()).await?;
+        let rules: Vec<Rule> = stored
+            .iter()
+            .filter_map(|rule| {
+                let parsed = Rule::from_stored(rule);
+                if parsed.is_none() {
+                    tracing::warn!(rule_id = %rule.id, "Skipping invalid permission rule");
+                }
+                parsed
+            })
+            .collect();
+
+        let rules = Arc::new(rules);
+        self.rules.write().unwrap().insert(guild_id, rules.clone());
+
+        Ok(rules)
+    }
+
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.rules.write().unwrap().remove(&guild_id);
+    }
+}
+
+/// The invoking member with their permissions in the current channel, or `None` if they can't be
+/// looked up.
+async fn invoker(ctx: Context<'_>, guild_id: GuildId) -> Option<Invoker> {
+    let member = ctx.author_member().await?;
+
+    // Slash commands carry the resolved permissions, text commands have to work them out.
+    let permissions = match member.permissions {
+        Some(permissions) => permissions,
+        None => {
+            let guild = guild_id.to_partial_guild(ctx).await.ok()?;
+            let channel = ctx.channel_id().to_channel(ctx).await.ok()?.guild()?;
+            guild.user_permissions_in(&channel, &member)
+        }
+    };
+
+    Some(Invoker {
+        user_id: member.user.id,
+        roles: member.roles.clone(),
+        permissions,
+    })
+}
+
+/// Global command check: applies the guild's permission rules, falling back to the permissions
+/// the command asks for. Administrators always pass, so nobody can be locked out of the rules.
+pub async fn permission_check(ctx: Context<'_>) -> std::result::Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let required = default_permissions(ctx.command(), ctx.parent_commands());
+    let rules = ctx
+        .data()
+        .permissions
+        .rules(&ctx.data().database, guild_id)
+        .await?;
+    if required.is_empty() && rules.is_empty() {
+        return Ok(true);
+    }
+
+    let path = command_path(ctx.command(), ctx.parent_commands());
+    let locale = i18n::locale(ctx).await;
+    let invoker = invoker(ctx, guild_id)
+        .await
+        .ok_or_else(|| bot_error(tr!(locale, "check-permissions-unavailable")))?;
+    if invoker.permissions.administrator() {
+        return Ok(true);
+    }
+
+    match evaluate(&rules, &path, &invoker) {
+        Some(Effect::Allow) => Ok(true),
+        Some(Effect::Deny) => {
+            tracing::info!(
+                guild_id = %guild_id,
+                user_id = %invoker.user_id,
+                command = %path,
+                "Command refused by permission rule"
+            );
+            Err(bot_error(tr!(locale, "check-not-allowed", command = path)))
+        }
+        None if invoker.permissions.contains(required) => Ok(true),
+        None => Err(bot_error(tr!(
+            locale,
+            "check-missing-permission",
+            permission = required - invoker.permissions,
+            command = path
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(command: &str, subject: Subject, effect: Effect) -> Rule {
+        Rule {
+            id: 0,
+            command: command.to_string(),
+            subject,
+            effect,
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let curators = RoleId::new(10);
+        let muted = RoleId::new(11);
+        let invoker = Invoker {
+            user_id: UserId::new(1),
+            roles: vec![curators, muted],
+            permissions: Permissions::SEND_MESSAGES,
+        };
+
+        let rules = vec![
+            rule("suggest", Subject::Role(curators), Effect::Allow),
+            rule("admin", Subject::Role(curators), Effect::Allow),
+            rule("admin", Subject::Role(muted), Effect::Deny),
+            rule("admin warn", Subject::User(UserId::new(1)), Effect::Allow),
+        ];
+
+        assert_eq!(
+            evaluate(&rules, "suggest remove_song", &invoker),
+            Some(Effect::Allow)
+        );
+        // Deny beats allow among roles on the same command.
+        assert_eq!(evaluate(&rules, "admin kick", &invoker), Some(Effect::Deny));
+        // A rule on the command beats the rules on its group.
+        assert_eq!(
+            evaluate(&rules, "admin warn", &invoker),
+            Some(Effect::Allow)
+        );
+        assert_eq!(evaluate(&rules, "help", &invoker), None);
+
+        // A user rule beats role rules on the same command.
+        let rules = vec![
+            rule("admin", Subject::Role(muted), Effect::Deny),
+            rule("admin", Subject::User(UserId::new(1)), Effect::Allow),
+            rule(
+                "admin",
+                Subject::Permission(Permissions::KICK_MEMBERS),
+                Effect::Allow,
+            ),
+        ];
+        assert_eq!(
+            evaluate(&rules, "admin case", &invoker),
+            Some(Effect::Allow)
+        );
+
+        let other = Invoker {
+            user_id: UserId::new(2),
+            roles: Vec::new(),
+            permissions: Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES,
+        };
+        assert_eq!(evaluate(&rules, "admin case", &other), Some(Effect::Allow));
+    }
+
+    #[test]
+    fn test_subject_roundtrip() {
+        for subject in [
+            Subject::User(UserId::new(5)),
+            Subject::Role(RoleId::new(6)),
+            Subject::Permission(Permissions::MANAGE_MESSAGES | Permissions::KICK_MEMBERS),
+        ] {
+            assert_eq!(Subject::parse(subject.kind(), &subject.id()), Some(subject));
+        }
+        assert_eq!(Subject::parse("role", "0"), None);
+        assert_eq!(Subject::parse("channel", "5"), None);
+    }
+
+    #[test]
+    fn test_take_over() {
+        let commands = take_over(crate::commands::get_commands());
+        let admin = commands.iter().find(|c| c.name == "admin").unwrap();
+        let warn = admin.subcommands.iter().find(|c| c.name == "warn").unwrap();
+
+        assert!(admin.required_permissions.is_empty());
+        assert_eq!(
+            admin.default_member_permissions,
+            Permissions::MANAGE_MESSAGES
+        );
+        assert!(warn.required_permissions.is_empty());
+        assert_eq!(
+            default_permissions(warn, &[admin]),
+            Permissions::MANAGE_MESSAGES
+        );
+        assert_eq!(command_path(warn, &[admin]), "admin warn");
+
+        assert_eq!(
+            find_path(&commands, " Admin  WARN"),
+            Some("admin warn".to_string())
+        );
+        assert_eq!(find_path(&commands, "admin nothing"), None);
+        assert_eq!(find_path(&commands, ""), None);
+    }
+}
@@ -0,0 +1,424 @@
+use chrono::{DateTime, Duration, Utc};
+use futures::{StreamExt, stream};
+use poise::serenity_prelude::{
+    ChannelId, ChannelType, Guild, GuildChannel, Http, HttpError, Member, Message, MessageId,
+    Permissions,
+};
+use regex::Regex;
+use serde::Serialize;
+use serenity::all::GetMessages;
+use std::future::Future;
+
+/// Selects which messages a purge deletes. Every set condition must hold.
+#[derive(Debug, Clone, Default)]
+pub struct PurgeFilter {
+    /// Only messages whose content matches.
+    pub regex: Option<Regex>,
+    /// Only messages sent after this instant; scanning stops at the first older message.
+    pub since: Option<DateTime<Utc>>,
+    /// Only messages sent before this instant; newer history is skipped without being fetched.
+    pub before: Option<DateTime<Utc>>,
+    /// Leave pinned messages alone.
+    pub skip_pinned: bool,
+    /// Stop after checking this many messages.
+    pub scan_limit: Option<u32>,
+}
+
+impl PurgeFilter {
+    fn matches(&self, message: &Message) -> bool {
+        if self.skip_pinned && message.pinned {
+            return false;
+        }
+
+        if let Some(before) = self.before
+            && message.timestamp.timestamp() >= before.timestamp()
+        {
+            return false;
+        }
+
+        self.regex
+            .as_ref()
+            .is_none_or(|regex| regex.is_match(&message.content))
+    }
+}
+
+/// Old messages deleted in parallel. Serenity's ratelimiter still spaces the requests to fit the
+/// route's budget, this only keeps enough of them in flight to use it fully.
+const INDIVIDUAL_DELETE_CONCURRENCY: usize = 5;
+
+/// Attempts per deletion request before it counts as failed.
+const MAX_DELETE_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry, doubled for every further attempt.
+const RETRY_BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Discord's JSON error code for a message that no longer exists.
+const UNKNOWN_MESSAGE: isize = 10008;
+
+/// Milliseconds between the Unix epoch and the Discord epoch (2015-01-01).
+const DISCORD_EPOCH_MS: i64 = 1_420_070_400_000;
+
+/// The smallest snowflake created at `time`, usable as a pagination cursor.
+fn snowflake_at(time: DateTime<Utc>) -> MessageId {
+    let millis = (time.timestamp_millis() - DISCORD_EPOCH_MS).max(1) as u64;
+    MessageId::new(millis << 22)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PurgeProgress {
+    Scanning { checked: u32, matched: usize },
+    Deleting { done: usize, total: usize },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelReport {
+    pub channel_id: ChannelId,
+    pub checked: u32,
+    pub deleted: usize,
+    pub failed: usize,
+    pub error: Option<String>,
+}
+
+impl ChannelReport {
+    fn new(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            checked: 0,
+            deleted: 0,
+            failed: 0,
+            error: None,
+        }
+    }
+}
+
+/// Channel kinds whose history can be purged.
+pub fn is_purgeable(channel: &GuildChannel) -> bool {
+    matches!(channel.kind, ChannelType::Text | ChannelType::News)
+}
+
+/// Whether the bot can see and page through the history of `channel`.
+pub fn can_read(guild: &Guild, channel: &GuildChannel, bot_member: &Member) -> bool {
+    guild
+        .user_permissions_in(channel, bot_member)
+        .contains(Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY)
+}
+
+/// Whether `member` may read `channel` and delete other people's messages in it.
+pub fn can_moderate(guild: &Guild, channel: &GuildChannel, member: &Member) -> bool {
+    guild.user_permissions_in(channel, member).contains(
+        Permissions::VIEW_CHANNEL
+            | Permissions::READ_MESSAGE_HISTORY
+            | Permissions::MANAGE_MESSAGES,
+    )
+}
+
+/// Every purgeable channel of the guild the bot can read, in sidebar order.
+pub fn readable_channels(guild: &Guild, bot_member: &Member) -> Vec<ChannelId> {
+    let mut channels: Vec<&GuildChannel> = guild
+        .channels
+        .values()
+        .filter(|channel| is_purgeable(channel) && can_read(guild, channel, bot_member))
+        .collect();
+
+    channels.sort_by_key(|channel| (channel.position, channel.id));
+    channels.into_iter().map(|channel| channel.id).collect()
+}
+
+/// Purgeable channels the bot can read inside `category`.
+pub fn category_channels(
+    guild: &Guild,
+    category: ChannelId,
+    bot_member: &Member,
+) -> Vec<ChannelId> {
+    let mut channels: Vec<&GuildChannel> = guild
+        .channels
+        .values()
+        .filter(|channel| channel.parent_id == Some(category))
+        .filter(|channel| is_purgeable(channel) && can_read(guild, channel, bot_member))
+        .collect();
+
+    channels.sort_by_key(|channel| (channel.position, channel.id));
+    channels.into_iter().map(|channel| channel.id).collect()
+}
+
+/// Parses a list of channel mentions or raw ids separated by spaces or commas.
+pub fn parse_channel_list(input: &str) -> std::result::Result<Vec<ChannelId>, String> {
+    input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            serenity::utils::parse_channel_mention(token)
+                .or_else(|| {
+                    token
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|id| *id != 0)
+                        .map(ChannelId::new)
+                })
+                .ok_or_else(|| token.to_string())
+        })
+        .collect()
+}
+
+/// Scans `channel_id` from the newest message backwards and collects the ids matching `filter`.
+#[tracing::instrument(skip(http, on_progress))]
+pub async fn collect_matches<F, Fut>(
+    http: &Http,
+    channel_id: ChannelId,
+    filter: &PurgeFilter,
+    mut on_progress: F,
+) -> serenity::Result<(u32, Vec<MessageId>)>
+where
+    F: FnMut(PurgeProgress) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut matches = Vec::new();
+    let mut total_checked = 0u32;
+    let mut last_message_id = filter.before.map(snowflake_at);
+
+    tracing::debug!("Collecting victims for the purge");
+
+    loop {
+        let mut builder = GetMessages::new().limit(100);
+
+        if let Some(before_id) = last_message_id {
+            builder = builder.before(before_id);
+        }
+
+        let messages = channel_id.messages(http, builder).await?;
+
+        if messages.is_empty() {
+            tracing::debug!("No more messages to check");
+            break;
+        }
+
+        let mut finished = false;
+        for message in messages {
+            if filter
+                .scan_limit
+                .is_some_and(|limit| total_checked >= limit)
+            {
+                finished = true;
+                tracing::debug!("Scan limit reached, stopping collection");
+                break;
+            }
+
+            total_checked += 1;
+
+            if let Some(since) = filter.since
+                && message.timestamp.timestamp() < since.timestamp()
+            {
+                finished = true;
+                tracing::debug!(
+                    message_id = %message.id,
+                    "Message is older than threshold, stopping collection"
+                );
+                break;
+            }
+
+            if filter.matches(&message) {
+                matches.push(message.id);
+                tracing::debug!(
+                    message_id = %message.id,
+                    content_preview = %message.content.chars().take(50).collect::<String>(),
+                    "Message matched pattern and will be deleted"
+                );
+            }
+
+            last_message_id = Some(message.id);
+        }
+
+        if finished {
+            break;
+        }
+
+        if total_checked.is_multiple_of(500) {
+            on_progress(PurgeProgress::Scanning {
+                checked: total_checked,
+                matched: matches.len(),
+            })
+            .await;
+        }
+    }
+
+    tracing::info!(
+        total_checked = %total_checked,
+        messages_to_delete = %matches.len(),
+        "Message collection completed"
+    );
+
+    Ok((total_checked, matches))
+}
+
+/// Deletes `message_ids` from `channel_id`, returning the deleted and failed counts.
+///
+/// Messages younger than 14 days go through bulk deletes of up to 100; older ones must be
+/// deleted one by one and are sent concurrently. Pacing is left to serenity's ratelimiter.
+#[tracing::instrument(skip(http, message_ids, on_progress), fields(count = message_ids.len()))]
+pub async fn delete_messages<F, Fut>(
+    http: &Http,
+    channel_id: ChannelId,
+    message_ids: &[MessageId],
+    mut on_progress: F,
+) -> (usize, usize)
+where
+    F: FnMut(PurgeProgress) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut deleted_count = 0;
+    let mut failed_deletes = 0;
+
+    let two_weeks_ago = Utc::now() - Duration::days(14);
+    let mut bulk_delete_ids = Vec::new();
+    let mut individual_delete_ids = Vec::new();
+
+    for &message_id in message_ids {
+        let message_timestamp = message_id.created_at();
+        if message_timestamp.timestamp() > two_weeks_ago.timestamp() {
+            bulk_delete_ids.push(message_id);
+        } else {
+            individual_delete_ids.push(message_id);
+        }
+    }
+
+    tracing::info!(
+        bulk_delete_count = %bulk_delete_ids.len(),
+        individual_delete_count = %individual_delete_ids.len(),
+        "Starting message deletion"
+    );
+
+    for chunk in bulk_delete_ids.chunks(100) {
+        match with_retries(|| channel_id.delete_messages(http, chunk)).await {
+            Ok(_) => {
+                deleted_count += chunk.len();
+                tracing::debug!(count = %chunk.len(), "Bulk deleted messages");
+            }
+            Err(e) => {
+                failed_deletes += chunk.len();
+                tracing::warn!(error = %e, count = %chunk.len(), "Failed to bulk delete messages");
+            }
+        }
+    }
+
+    let mut deletions = stream::iter(individual_delete_ids)
+        .map(|message_id| async move {
+            let result = with_retries(|| channel_id.delete_message(http, message_id)).await;
+            (message_id, result)
+        })
+        .buffer_unordered(INDIVIDUAL_DELETE_CONCURRENCY);
+
+    while let Some((message_id, result)) = deletions.next().await {
+        match result {
+            Ok(_) => {
+                deleted_count += 1;
+                tracing::debug!(message_id = %message_id, "Individually deleted message");
+            }
+            Err(e) => {
+                failed_deletes += 1;
+                tracing::warn!(error = %e, message_id = %message_id, "Failed to delete message");
+            }
+        }
+
+        if (deleted_count + failed_deletes) % 10 == 0 {
+            on_progress(PurgeProgress::Deleting {
+                done: deleted_count + failed_deletes,
+                total: message_ids.len(),
+            })
+            .await;
+        }
+    }
+
+    (deleted_count, failed_deletes)
+}
+
+/// Runs `request`, retrying server errors and dropped connections with exponential backoff.
+///
+/// Rate limits are not handled here: serenity's ratelimiter already waits for exhausted buckets
+/// using the `x-ratelimit-*` headers and replays 429 responses after their `retry-after`.
+async fn with_retries<F, Fut>(mut request: F) -> serenity::Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = serenity::Result<()>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        match request().await {
+            Ok(()) => return Ok(()),
+            Err(e) if is_unknown_message(&e) => {
+                tracing::debug!("Message was already deleted");
+                return Ok(());
+            }
+            Err(e) if attempt < MAX_DELETE_ATTEMPTS && is_transient(&e) => {
+                let delay = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                tracing::debug!(error = %e, attempt = %attempt, delay = ?delay, "Retrying deletion");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn is_transient(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+            response.status_code.is_server_error()
+        }
+        serenity::Error::Http(HttpError::Request(e)) => e.is_timeout() || e.is_connect(),
+        _ => false,
+    }
+}
+
+fn is_unknown_message(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.error.code == UNKNOWN_MESSAGE
+    )
+}
+
+/// Collects and deletes every message in `channel_id` matching `filter`.
+///
+/// Failures are recorded in the returned report instead of aborting, so one unreadable
+/// channel does not stop a multi-channel purge.
+pub async fn purge_channel<F, Fut>(
+    http: &Http,
+    channel_id: ChannelId,
+    filter: &PurgeFilter,
+    mut on_progress: F,
+) -> ChannelReport
+where
+    F: FnMut(PurgeProgress) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut report = ChannelReport::new(channel_id);
+
+    let matches = match collect_matches(http, channel_id, filter, &mut on_progress).await {
+        Ok((checked, matches)) => {
+            report.checked = checked;
+            matches
+        }
+        Err(e) => {
+            tracing::warn!(channel_id = %channel_id, error = %e, "Failed to read channel history");
+            report.error = Some(e.to_string());
+            return report;
+        }
+    };
+
+    if matches.is_empty() {
+        return report;
+    }
+
+    on_progress(PurgeProgress::Deleting {
+        done: 0,
+        total: matches.len(),
+    })
+    .await;
+
+    let (deleted, failed) = delete_messages(http, channel_id, &matches, &mut on_progress).await;
+    report.deleted = deleted;
+    report.failed = failed;
+
+    report
+}
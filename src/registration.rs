@@ -0,0 +1,119 @@
+use crate::bot::Data;
+use crate::config::{CommandsConfig, Registration};
+use crate::error::Error;
+use anyhow::Result;
+use poise::serenity_prelude::{Command as SlashCommand, GuildId, Http};
+use std::fmt;
+
+/// Where a set of slash commands lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Global,
+    Guild(GuildId),
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => f.write_str("global"),
+            Self::Guild(guild_id) => write!(f, "guild {}", guild_id),
+        }
+    }
+}
+
+/// The scopes `config` registers commands in.
+pub fn scopes(config: &CommandsConfig) -> Vec<Scope> {
+    match config.registration {
+        Registration::Global => vec![Scope::Global],
+        Registration::Guilds => config.guild_ids().into_iter().map(Scope::Guild).collect(),
+        Registration::None => Vec::new(),
+    }
+}
+
+/// Every scope commands may have been left in: global and each configured guild.
+pub fn all_scopes(config: &CommandsConfig) -> Vec<Scope> {
+    std::iter::once(Scope::Global)
+        .chain(config.guild_ids().into_iter().map(Scope::Guild))
+        .collect()
+}
+
+/// Replaces the commands registered in each of `scopes` with `commands`.
+#[tracing::instrument(skip(http, commands))]
+pub async fn register(
+    http: &Http,
+    commands: &[poise::Command<Data, Error>],
+    scopes: &[Scope],
+) -> Result<()> {
+    for &scope in scopes {
+        match scope {
+            Scope::Global => poise::builtins::register_globally(http, commands).await?,
+            Scope::Guild(guild_id) => {
+                poise::builtins::register_in_guild(http, commands, guild_id).await?
+            }
+        }
+        tracing::info!(scope = %scope, count = %commands.len(), "Commands registered");
+    }
+
+    if scopes.is_empty() {
+        tracing::info!(
+            "Command registration is turned off, leaving registered commands as they are"
+        );
+    }
+
+    Ok(())
+}
+
+/// Removes every registered command from `scopes`.
+#[tracing::instrument(skip(http))]
+pub async fn clear(http: &Http, scopes: &[Scope]) -> Result<()> {
+    for &scope in scopes {
+        match scope {
+            Scope::Global => {
+                SlashCommand::set_global_commands(http, Vec::new()).await?;
+            }
+            Scope::Guild(guild_id) => {
+                guild_id.set_commands(http, Vec::new()).await?;
+            }
+        }
+        tracing::info!(scope = %scope, "Commands removed");
+    }
+
+    Ok(())
+}
+
+/// Lists scopes for a message, e.g. "global, guild 123".
+pub fn describe(scopes: &[Scope]) -> String {
+    match scopes.is_empty() {
+        true => "nowhere".to_string(),
+        false => scopes
+            .iter()
+            .map(Scope::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes() {
+        let mut config = CommandsConfig {
+            registration: Registration::Global,
+            guilds: vec![1, 2],
+        };
+        assert_eq!(scopes(&config), vec![Scope::Global]);
+
+        config.registration = Registration::Guilds;
+        assert_eq!(
+            scopes(&config),
+            vec![Scope::Guild(GuildId::new(1)), Scope::Guild(GuildId::new(2))]
+        );
+        assert_eq!(describe(&scopes(&config)), "guild 1, guild 2");
+
+        config.registration = Registration::None;
+        assert!(scopes(&config).is_empty());
+        assert_eq!(all_scopes(&config).len(), 3);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::config::{Config, LogFilter};
+use crate::i18n::tr;
+use crate::settings::Locale;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use tracing_subscriber::EnvFilter;
+
+/// The running configuration, shared through [`crate::bot::Data`] and swapped on reload.
+#[derive(Debug, Clone)]
+pub struct ConfigHandle {
+    current: Arc<RwLock<Arc<Config>>>,
+    /// The `--config` path the bot was started with, re-read on reload.
+    path: Option<PathBuf>,
+    log_filter: Option<LogFilter>,
+}
+
+/// Which changed fields a reload applied and which only take effect after a restart.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    pub applied: Vec<&'static str>,
+    pub needs_restart: Vec<&'static str>,
+}
+
+impl ConfigHandle {
+    pub fn new(config: Config, path: Option<PathBuf>, log_filter: Option<LogFilter>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(config))),
+            path,
+            log_filter,
+        }
+    }
+
+    pub fn get(&self) -> Arc<Config> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Re-reads the config file and environment and swaps in the fields that are safe to change
+    /// while running. Nothing changes when the new config is invalid.
+    pub fn reload(&self) -> Result<ReloadReport> {
+        let loaded = Config::load(self.path.as_deref())?;
+
+        let mut current = self.current.write().unwrap();
+        let (merged, report) = merge(&current, loaded);
+
+        if merged.logging.level != current.logging.level
+            && let Some(log_filter) = &self.log_filter
+        {
+            log_filter
+                .reload(EnvFilter::new(&merged.logging.level))
+                .context("Failed to apply logging.level")?;
+        }
+
+        *current = Arc::new(merged);
+
+        Ok(report)
+    }
+}
+
+/// Copies the reloadable fields of `loaded` onto `current`.
+fn merge(current: &Config, loaded: Config) -> (Config, ReloadReport) {
+    let mut merged = current.clone();
+    let mut report = ReloadReport::default();
+
+    macro_rules! apply {
+        ($($field:ident).+) => {
+            if merged.$($field).+ != loaded.$($field).+ {
+                merged.$($field).+ = loaded.$($field).+.clone();
+                report.applied.push(stringify!($($field).+));
+            }
+        };
+    }
+    macro_rules! restart {
+        ($($field:ident).+) => {
+            if current.$($field).+ != loaded.$($field).+ {
+                report.needs_restart.push(stringify!($($field).+));
+            }
+        };
+    }
+
+    apply!(discord.prefix);
+    apply!(logging.level);
+    apply!(message_log.cache_capacity);
+    // Used by `/owner sync`, startup registration has already happened.
+    apply!(commands.registration);
+    apply!(commands.guilds);
+    apply!(guilds.approval);
+
+    restart!(discord.token);
+    restart!(discord.intents);
+    restart!(discord.owners);
+    restart!(logging.format);
+    restart!(database.path);
+
+    (merged, report)
+}
+
+impl ReloadReport {
+    pub fn describe(&self, locale: Locale) -> String {
+        let list = |fields: &[&str]| match fields.is_empty() {
+            true => tr!(locale, "reload-none"),
+            false => fields
+                .iter()
+                .map(|f| format!("`{}`", f))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        let mut description = tr!(locale, "reload-applied", fields = list(&self.applied));
+        if !self.needs_restart.is_empty() {
+            description.push('\n');
+            description.push_str(&tr!(
+                locale,
+                "reload-needs-restart",
+                fields = list(&self.needs_restart)
+            ));
+        }
+
+        description
+    }
+}
+
+/// Reloads the config whenever the process receives SIGHUP.
+#[cfg(unix)]
+pub fn spawn_sighup_handler(data: crate::bot::Data) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to listen for SIGHUP, config reload is only available by command");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            tracing::info!("SIGHUP received, reloading configuration");
+            match data.config.reload() {
+                Ok(report) => {
+                    data.apply_config();
+                    tracing::warn!(
+                        applied = ?report.applied,
+                        needs_restart = ?report.needs_restart,
+                        "Configuration reloaded"
+                    );
+                }
+                Err(e) => {
+                    tracing::error!(error = %format!("{:#}", e), "Configuration reload failed")
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_sighup_handler(_: crate::bot::Data) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::all::GatewayIntents;
+
+    #[test]
+    fn test_merge() {
+        let current = Config::default();
+        let mut loaded = Config::default();
+        loaded.discord.prefix = "!".to_string();
+        loaded.discord.intents = GatewayIntents::GUILDS;
+        loaded.logging.level = "debug".to_string();
+        loaded.database.path = "/elsewhere.sqlite".into();
+
+        let (merged, report) = merge(&current, loaded);
+
+        assert_eq!(merged.discord.prefix, "!");
+        assert_eq!(merged.logging.level, "debug");
+        // Restart-only fields keep running with their old values.
+        assert_eq!(merged.discord.intents, current.discord.intents);
+        assert_eq!(merged.database.path, current.database.path);
+        assert_eq!(
+            report,
+            ReloadReport {
+                applied: vec!["discord.prefix", "logging.level"],
+                needs_restart: vec!["discord.intents", "database.path"],
+            }
+        );
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let (_, report) = merge(&Config::default(), Config::default());
+        assert_eq!(report, ReloadReport::default());
+        assert_eq!(report.describe(Locale::default()), "**Applied:** none");
+    }
+}
@@ -0,0 +1,725 @@
+use crate::{
+    bot::Data,
+    database::{self, RoleMenu, RoleMenuOption},
+    i18n::{self, tr},
+    moderation::Hierarchy,
+    scheduler::parse_id,
+    settings::Locale,
+};
+use anyhow::{Context, Result};
+use poise::serenity_prelude::{
+    self as serenity, ButtonStyle, ChannelId, ComponentInteraction, ComponentInteractionDataKind,
+    CreateActionRow, CreateAllowedMentions, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, CreateSelectMenu,
+    CreateSelectMenuKind, CreateSelectMenuOption, EditMessage, GuildId, Http, Member, MessageId,
+    Permissions, ReactionType, Role, RoleId,
+};
+use sqlx::SqlitePool;
+
+/// Prefix of the custom id of every role menu component.
+const CUSTOM_ID_PREFIX: &str = "rolemenu";
+
+/// Discord allows 25 buttons per message and 25 options per select menu.
+pub const MAX_OPTIONS: usize = 25;
+
+/// Buttons Discord fits in one action row.
+const BUTTONS_PER_ROW: usize = 5;
+
+/// Roles with any of these permissions can't be handed out through a menu.
+const ELEVATED_PERMISSIONS: Permissions = Permissions::ADMINISTRATOR
+    .union(Permissions::MANAGE_GUILD)
+    .union(Permissions::MANAGE_ROLES)
+    .union(Permissions::MANAGE_CHANNELS)
+    .union(Permissions::MANAGE_MESSAGES)
+    .union(Permissions::MANAGE_WEBHOOKS)
+    .union(Permissions::KICK_MEMBERS)
+    .union(Permissions::BAN_MEMBERS)
+    .union(Permissions::MODERATE_MEMBERS)
+    .union(Permissions::MENTION_EVERYONE);
+
+/// How members pick roles from a menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum RoleMenuStyle {
+    #[name = "buttons"]
+    Buttons,
+    #[name = "select"]
+    Select,
+}
+
+impl RoleMenuStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Buttons => "buttons",
+            Self::Select => "select",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "buttons" => Some(Self::Buttons),
+            "select" => Some(Self::Select),
+            _ => None,
+        }
+    }
+}
+
+/// What a member asked for through a menu component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selection {
+    /// A button: toggle one role.
+    Toggle(RoleId),
+    /// A select menu: hold exactly these roles of the menu.
+    Set(Vec<RoleId>),
+}
+
+/// Roles to add to and remove from the member who used a menu.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RoleChange {
+    add: Vec<RoleId>,
+    remove: Vec<RoleId>,
+}
+
+fn custom_id(menu_id: i64, role_id: Option<RoleId>) -> String {
+    match role_id {
+        Some(role_id) => format!("{}:{}:{}", CUSTOM_ID_PREFIX, menu_id, role_id),
+        None => format!("{}:{}", CUSTOM_ID_PREFIX, menu_id),
+    }
+}
+
+/// Menu id and, for buttons, role id of a role menu component.
+fn parse_custom_id(custom_id: &str) -> Option<(i64, Option<RoleId>)> {
+    let mut parts = custom_id.split(':');
+    if parts.next()? != CUSTOM_ID_PREFIX {
+        return None;
+    }
+
+    let menu_id = parts.next()?.parse().ok()?;
+    let role_id = match parts.next() {
+        Some(role_id) => Some(RoleId::new(parse_id(role_id)?)),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some((menu_id, role_id))
+}
+
+/// Parses a unicode emoji or a custom emoji such as `<:name:123>`.
+pub fn parse_emoji(input: &str) -> Option<ReactionType> {
+    let input = input.trim();
+    let custom = input.starts_with('<');
+    if input.is_empty() || (!custom && input.chars().any(|c| c.is_ascii_alphabetic())) {
+        return None;
+    }
+
+    ReactionType::try_from(input).ok()
+}
+
+/// Works out the role changes for `selection`, enforcing the menu's limits.
+fn plan(
+    locale: Locale,
+    menu_roles: &[RoleId],
+    held: &[RoleId],
+    selection: Selection,
+    exclusive: bool,
+    max_selections: Option<usize>,
+) -> std::result::Result<RoleChange, String> {
+    let held: Vec<RoleId> = menu_roles
+        .iter()
+        .filter(|role| held.contains(role))
+        .copied()
+        .collect();
+    let limit = if exclusive { Some(1) } else { max_selections };
+
+    match selection {
+        Selection::Toggle(role) => {
+            if !menu_roles.contains(&role) {
+                return Err(tr!(locale, "rolemenu-role-gone"));
+            }
+
+            if held.contains(&role) {
+                return Ok(RoleChange {
+                    add: Vec::new(),
+                    remove: vec![role],
+                });
+            }
+
+            if exclusive {
+                return Ok(RoleChange {
+                    add: vec![role],
+                    remove: held,
+                });
+            }
+
+            if let Some(limit) = limit
+                && held.len() >= limit
+            {
+                return Err(tr!(locale, "rolemenu-hold-limit", limit = limit));
+            }
+
+            Ok(RoleChange {
+                add: vec![role],
+                remove: Vec::new(),
+            })
+        }
+        Selection::Set(selected) => {
+            let selected: Vec<RoleId> = menu_roles
+                .iter()
+                .filter(|role| selected.contains(role))
+                .copied()
+                .collect();
+
+            if let Some(limit) = limit
+                && selected.len() > limit
+            {
+                return Err(tr!(locale, "rolemenu-pick-limit", limit = limit));
+            }
+
+            Ok(RoleChange {
+                add: selected
+                    .iter()
+                    .filter(|role| !held.contains(role))
+                    .copied()
+                    .collect(),
+                remove: held
+                    .iter()
+                    .filter(|role| !selected.contains(role))
+                    .copied()
+                    .collect(),
+            })
+        }
+    }
+}
+
+/// Builds the embed and components of a published menu.
+fn render(
+    locale: Locale,
+    menu: &RoleMenu,
+    options: &[RoleMenuOption],
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let mut description = menu.description.clone().unwrap_or_default();
+    if !description.is_empty() {
+        description.push_str("\n\n");
+    }
+    for option in options {
+        if let Some(emoji) = &option.emoji {
+            description.push_str(emoji);
+            description.push(' ');
+        }
+        description.push_str(&format!("<@&{}>", option.role_id));
+        if let Some(detail) = &option.description {
+            description.push_str(&format!(": {}", detail));
+        }
+        description.push('\n');
+    }
+
+    // Footers don't render mentions, so the prerequisite goes in the description.
+    if let Some(required) = &menu.required_role_id {
+        description.push('\n');
+        description.push_str(&tr!(
+            locale,
+            "rolemenu-requires",
+            role = format!("<@&{}>", required)
+        ));
+    }
+
+    let mut embed = CreateEmbed::new()
+        .title(&menu.title)
+        .description(description);
+    if menu.exclusive {
+        embed = embed.footer(CreateEmbedFooter::new(tr!(locale, "rolemenu-pick-one")));
+    } else if let Some(max) = menu.max_selections {
+        embed = embed.footer(CreateEmbedFooter::new(tr!(
+            locale,
+            "rolemenu-pick-up-to",
+            max = max
+        )));
+    }
+
+    let components = match RoleMenuStyle::parse(&menu.style) {
+        Some(RoleMenuStyle::Select) => {
+            let choices = options
+                .iter()
+                .map(|option| {
+                    let mut choice = CreateSelectMenuOption::new(&option.label, &option.role_id);
+                    if let Some(detail) = &option.description {
+                        choice = choice.description(detail);
+                    }
+                    if let Some(emoji) = option.emoji.as_deref().and_then(parse_emoji) {
+                        choice = choice.emoji(emoji);
+                    }
+                    choice
+                })
+                .collect::<Vec<_>>();
+
+            let max = match (menu.exclusive, menu.max_selections) {
+                (true, _) => 1,
+                (false, Some(max)) => max.clamp(1, options.len() as i64),
+                (false, None) => options.len() as i64,
+            };
+
+            vec![CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    custom_id(menu.id, None),
+                    CreateSelectMenuKind::String { options: choices },
+                )
+                .placeholder(tr!(locale, "rolemenu-placeholder"))
+                .min_values(0)
+                .max_values(max as u8),
+            )]
+        }
+        _ => options
+            .chunks(BUTTONS_PER_ROW)
+            .map(|row| {
+                CreateActionRow::Buttons(
+                    row.iter()
+                        .filter_map(|option| {
+                            let role_id = RoleId::new(parse_id(&option.role_id)?);
+                            let mut button = CreateButton::new(custom_id(menu.id, Some(role_id)))
+                                .label(&option.label)
+                                .style(ButtonStyle::Secondary);
+                            if let Some(emoji) = option.emoji.as_deref().and_then(parse_emoji) {
+                                button = button.emoji(emoji);
+                            }
+                            Some(button)
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    };
+
+    (embed, components)
+}
+
+/// Posts `menu` in `channel_id`, or updates its message if it is already posted there. The
+/// menu is shown to the whole server, so `locale` should be the guild's.
+#[tracing::instrument(skip(http, database, options))]
+pub async fn publish(
+    http: &Http,
+    database: &SqlitePool,
+    locale: Locale,
+    menu: &RoleMenu,
+    options: &[RoleMenuOption],
+    channel_id: ChannelId,
+) -> Result<MessageId> {
+    let (embed, components) = render(locale, menu, options);
+
+    let existing = menu
+        .message_id
+        .as_deref()
+        .and_then(parse_id)
+        .map(MessageId::new)
+        .filter(|_| menu.channel_id.as_deref() == Some(channel_id.to_string().as_str()));
+
+    if let Some(message_id) = existing {
+        let edit = EditMessage::new()
+            .embed(embed.clone())
+            .components(components.clone());
+        match channel_id.edit_message(http, message_id, edit).await {
+            Ok(_) => return Ok(message_id),
+            Err(e) => tracing::info!(
+                menu_id = %menu.id,
+                message_id = %message_id,
+                error = %e,
+                "Role menu message could not be edited, posting a new one"
+            ),
+        }
+    }
+
+    let message = channel_id
+        .send_message(
+            http,
+            CreateMessage::new()
+                .embed(embed)
+                .components(components)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await
+        .context("Failed to post role menu")?;
+
+    database::set_role_menu_message(
+        database,
+        menu.id,
+        &channel_id.to_string(),
+        &message.id.to_string(),
+    )
+    .await?;
+
+    tracing::info!(menu_id = %menu.id, channel_id = %channel_id, message_id = %message.id, "Role menu published");
+
+    Ok(message.id)
+}
+
+/// Updates the posted message of `menu` after its options changed. Unpublished menus are left
+/// alone.
+pub async fn refresh(
+    http: &Http,
+    database: &SqlitePool,
+    locale: Locale,
+    menu: &RoleMenu,
+) -> Result<()> {
+    let Some(channel_id) = menu.channel_id.as_deref().and_then(parse_id) else {
+        return Ok(());
+    };
+
+    let options = database::get_role_menu_options(database, menu.id).await?;
+    if options.is_empty() {
+        return Ok(());
+    }
+
+    publish(
+        http,
+        database,
+        locale,
+        menu,
+        &options,
+        ChannelId::new(channel_id),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Checks that `role` is safe to hand out through a menu, returning the id of the message
+/// explaining why it isn't otherwise.
+pub fn check_role(role: &Role, hierarchy: &Hierarchy, bot: &Member) -> Result<(), &'static str> {
+    if role.id == role.guild_id.everyone_role() || role.managed {
+        return Err("rolemenu-role-managed");
+    }
+    if role.permissions.intersects(ELEVATED_PERMISSIONS) {
+        return Err("rolemenu-role-elevated");
+    }
+    if !hierarchy.outranks_role(bot.user.id, &bot.roles, role.id) {
+        return Err("rolemenu-role-too-high");
+    }
+
+    Ok(())
+}
+
+/// The first of `roles` that can't be handed out anymore. They were checked when added to the
+/// menu, but their permissions or position may have changed since.
+async fn refused_role(
+    ctx: &serenity::Context,
+    guild_id: GuildId,
+    roles: &[RoleId],
+) -> Result<Option<RoleId>> {
+    let Some(&first) = roles.first() else {
+        return Ok(None);
+    };
+    let bot_id = ctx.cache.current_user().id;
+    let bot = guild_id
+        .member(ctx, bot_id)
+        .await
+        .context("Failed to fetch bot member")?;
+
+    let Some(guild) = ctx.cache.guild(guild_id) else {
+        // Without the roles nothing can be checked, so nothing is handed out.
+        return Ok(Some(first));
+    };
+    let hierarchy = Hierarchy::new(&guild);
+
+    Ok(roles.iter().copied().find(|role_id| {
+        let refusal = match guild.roles.get(role_id) {
+            Some(role) => check_role(role, &hierarchy, &bot).err(),
+            None => Some("rolemenu-role-deleted"),
+        };
+        if let Some(refusal) = refusal {
+            tracing::warn!(guild_id = %guild_id, role_id = %role_id, refusal = %refusal, "Refused to hand out menu role");
+        }
+        refusal.is_some()
+    }))
+}
+
+/// Handles a click on a role menu button or a choice in a role menu select.
+#[tracing::instrument(skip(ctx, data, interaction), fields(custom_id = %interaction.data.custom_id))]
+pub async fn handle_component(
+    ctx: &serenity::Context,
+    data: &Data,
+    interaction: &ComponentInteraction,
+) -> Result<()> {
+    let Some((menu_id, role_id)) = parse_custom_id(&interaction.data.custom_id) else {
+        return Ok(());
+    };
+    let (Some(guild_id), Some(member)) = (interaction.guild_id, &interaction.member) else {
+        return Ok(());
+    };
+    let locale = i18n::user_locale(
+        data,
+        member.user.id,
+        Some(guild_id),
+        Some(&interaction.locale),
+    )
+    .await;
+
+    let response =
+        match database::get_role_menu(&data.database, menu_id, &guild_id.to_string()).await? {
+            Some(menu) => {
+                let options = database::get_role_menu_options(&data.database, menu.id).await?;
+                let menu_roles: Vec<RoleId> = options
+                    .iter()
+                    .filter_map(|option| parse_id(&option.role_id).map(RoleId::new))
+                    .collect();
+
+                let selection = match (&interaction.data.kind, role_id) {
+                    (ComponentInteractionDataKind::Button, Some(role_id)) => {
+                        Some(Selection::Toggle(role_id))
+                    }
+                    (ComponentInteractionDataKind::StringSelect { values }, None) => {
+                        Some(Selection::Set(
+                            values
+                                .iter()
+                                .filter_map(|value| parse_id(value).map(RoleId::new))
+                                .collect(),
+                        ))
+                    }
+                    _ => None,
+                };
+
+                let required = menu
+                    .required_role_id
+                    .as_deref()
+                    .and_then(parse_id)
+                    .map(RoleId::new);
+
+                match (selection, required) {
+                    (None, _) => return Ok(()),
+                    (Some(_), Some(required)) if !member.roles.contains(&required) => {
+                        tr!(
+                            locale,
+                            "rolemenu-required-role",
+                            role = format!("<@&{}>", required)
+                        )
+                    }
+                    (Some(selection), _) => match plan(
+                        locale,
+                        &menu_roles,
+                        &member.roles,
+                        selection,
+                        menu.exclusive,
+                        menu.max_selections.map(|max| max as usize),
+                    ) {
+                        Ok(change) => match refused_role(ctx, guild_id, &change.add).await? {
+                            Some(role_id) => tr!(
+                                locale,
+                                "rolemenu-role-refused",
+                                role = format!("<@&{}>", role_id)
+                            ),
+                            None => apply_change(&ctx.http, locale, &menu, member, change).await,
+                        },
+                        Err(message) => message,
+                    },
+                }
+            }
+            None => tr!(locale, "rolemenu-menu-gone"),
+        };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(response)
+                    .ephemeral(true)
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            ),
+        )
+        .await
+        .context("Failed to respond to role menu interaction")?;
+
+    Ok(())
+}
+
+/// Applies `change` to `member`, returning the summary shown to them.
+async fn apply_change(
+    http: &Http,
+    locale: Locale,
+    menu: &RoleMenu,
+    member: &serenity::Member,
+    change: RoleChange,
+) -> String {
+    if change == RoleChange::default() {
+        return tr!(locale, "rolemenu-unchanged");
+    }
+
+    let reason = format!("Role menu #{}", menu.id);
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+
+    for role_id in change.remove {
+        match http
+            .remove_member_role(member.guild_id, member.user.id, role_id, Some(&reason))
+            .await
+        {
+            Ok(()) => removed.push(format!("<@&{}>", role_id)),
+            Err(e) => {
+                tracing::warn!(role_id = %role_id, error = %e, "Failed to remove menu role");
+                failed.push(format!("<@&{}>", role_id));
+            }
+        }
+    }
+    for role_id in change.add {
+        match http
+            .add_member_role(member.guild_id, member.user.id, role_id, Some(&reason))
+            .await
+        {
+            Ok(()) => added.push(format!("<@&{}>", role_id)),
+            Err(e) => {
+                tracing::warn!(role_id = %role_id, error = %e, "Failed to add menu role");
+                failed.push(format!("<@&{}>", role_id));
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    if !added.is_empty() {
+        lines.push(tr!(locale, "rolemenu-added", roles = added.join(", ")));
+    }
+    if !removed.is_empty() {
+        lines.push(tr!(locale, "rolemenu-removed", roles = removed.join(", ")));
+    }
+    if !failed.is_empty() {
+        lines.push(tr!(locale, "rolemenu-failed", roles = failed.join(", ")));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(ids: &[u64]) -> Vec<RoleId> {
+        ids.iter().copied().map(RoleId::new).collect()
+    }
+
+    #[test]
+    fn test_custom_ids_round_trip() {
+        let role = RoleId::new(99);
+
+        assert_eq!(
+            parse_custom_id(&custom_id(7, Some(role))),
+            Some((7, Some(role)))
+        );
+        assert_eq!(parse_custom_id(&custom_id(7, None)), Some((7, None)));
+        assert_eq!(parse_custom_id("other:7"), None);
+        assert_eq!(parse_custom_id("rolemenu:7:99:1"), None);
+    }
+
+    #[test]
+    fn test_buttons_toggle_roles() {
+        let menu = roles(&[1, 2, 3]);
+
+        let change = plan(
+            Locale::default(),
+            &menu,
+            &roles(&[2, 50]),
+            Selection::Toggle(RoleId::new(2)),
+            false,
+            None,
+        );
+        assert_eq!(change.unwrap().remove, roles(&[2]));
+
+        let change = plan(
+            Locale::default(),
+            &menu,
+            &roles(&[2, 50]),
+            Selection::Toggle(RoleId::new(1)),
+            false,
+            None,
+        );
+        assert_eq!(change.unwrap().add, roles(&[1]));
+
+        assert!(
+            plan(
+                Locale::default(),
+                &menu,
+                &[],
+                Selection::Toggle(RoleId::new(9)),
+                false,
+                None
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_exclusive_menus_swap_roles() {
+        let menu = roles(&[1, 2, 3]);
+
+        let change = plan(
+            Locale::default(),
+            &menu,
+            &roles(&[2, 50]),
+            Selection::Toggle(RoleId::new(3)),
+            true,
+            None,
+        )
+        .unwrap();
+        assert_eq!(change.add, roles(&[3]));
+        assert_eq!(change.remove, roles(&[2]));
+
+        assert!(
+            plan(
+                Locale::default(),
+                &menu,
+                &[],
+                Selection::Set(roles(&[1, 2])),
+                true,
+                None
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_max_selections() {
+        let menu = roles(&[1, 2, 3]);
+
+        assert!(
+            plan(
+                Locale::default(),
+                &menu,
+                &roles(&[1, 2]),
+                Selection::Toggle(RoleId::new(3)),
+                false,
+                Some(2)
+            )
+            .is_err()
+        );
+
+        let change = plan(
+            Locale::default(),
+            &menu,
+            &roles(&[1, 50]),
+            Selection::Set(roles(&[2, 3])),
+            false,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(change.add, roles(&[2, 3]));
+        assert_eq!(change.remove, roles(&[1]));
+
+        assert!(
+            plan(
+                Locale::default(),
+                &menu,
+                &[],
+                Selection::Set(roles(&[1, 2, 3])),
+                false,
+                Some(2)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_emoji() {
+        assert!(parse_emoji("🎮").is_some());
+        assert!(parse_emoji("<:pog:123456789>").is_some());
+        assert!(parse_emoji("gaming").is_none());
+        assert!(parse_emoji("").is_none());
+    }
+}
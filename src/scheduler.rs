@@ -0,0 +1,483 @@
+use crate::{
+    approval,
+    audit::{self, AuditAction, AuditOutcome, AuditRecord},
+    bot::Data,
+    database::{self, AutoCleanRule, ChannelLock, TemporaryBan},
+    lockdown, moderation,
+    pattern::{self, PatternOptions},
+    purge::{self, ChannelReport, PurgeFilter},
+    settings::{self, Locale, Module},
+};
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use poise::serenity_prelude::{ChannelId, GuildId, Http, UserId};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+/// How often the scheduler looks for due auto-clean rules.
+const AUTO_CLEAN_TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Messages checked per max-age rule run, so one busy channel cannot stall the others.
+/// Pattern-only rules read back to their previous run instead, as anything they stop short of
+/// would never be looked at again.
+const AUTO_CLEAN_SCAN_LIMIT: u32 = 1000;
+
+/// How often the scheduler looks for expired temporary bans.
+const TEMPORARY_BAN_TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Failed unban attempts after which a temporary ban is left for a moderator to lift.
+const MAX_UNBAN_ATTEMPTS: i64 = 5;
+
+/// How often the scheduler looks for expired channel locks.
+const CHANNEL_LOCK_TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Failed restores after which a channel lock is left for a moderator to lift.
+const MAX_UNLOCK_ATTEMPTS: i64 = 5;
+
+/// Starts the background tasks. Called once from the framework setup.
+pub fn spawn(http: Arc<Http>, data: Data) {
+    tracing::info!("Starting background scheduler");
+    tokio::spawn(run_auto_clean(http.clone(), data.clone()));
+    tokio::spawn(run_temporary_bans(http.clone(), data.database.clone()));
+    tokio::spawn(run_channel_locks(http, data.database));
+}
+
+async fn run_auto_clean(http: Arc<Http>, data: Data) {
+    let database = &data.database;
+    let mut interval = tokio::time::interval(AUTO_CLEAN_TICK);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let rules = match database::get_due_auto_clean_rules(database).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load due auto-clean rules");
+                continue;
+            }
+        };
+
+        for rule in rules {
+            if !should_run(&data, &rule).await {
+                continue;
+            }
+
+            let (report, error) = match run_rule(&http, &rule).await {
+                Ok(report) => {
+                    let error = report.error.clone();
+                    (Some(report), error)
+                }
+                Err(e) => (None, Some(e.to_string())),
+            };
+
+            let checked = report.as_ref().map_or(0, |r| r.checked as i64);
+            let deleted = report.as_ref().map_or(0, |r| r.deleted as i64);
+            let failed = report.as_ref().map_or(0, |r| r.failed as i64);
+
+            match &error {
+                Some(error) => tracing::warn!(
+                    rule_id = %rule.id,
+                    guild_id = %rule.guild_id,
+                    channel_id = %rule.channel_id,
+                    error = %error,
+                    "Auto-clean run failed"
+                ),
+                None => tracing::info!(
+                    rule_id = %rule.id,
+                    guild_id = %rule.guild_id,
+                    channel_id = %rule.channel_id,
+                    checked = %checked,
+                    deleted = %deleted,
+                    failed = %failed,
+                    "Auto-clean run completed"
+                ),
+            }
+
+            if let Err(e) = database::record_auto_clean_run(
+                database,
+                rule.id,
+                checked,
+                deleted,
+                failed,
+                error.as_deref(),
+            )
+            .await
+            {
+                tracing::error!(rule_id = %rule.id, error = %e, "Failed to record auto-clean run");
+            }
+
+            if deleted > 0 || failed > 0 || error.is_some() {
+                audit_run(
+                    &http,
+                    database,
+                    &rule,
+                    checked,
+                    deleted,
+                    failed,
+                    error.as_deref(),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+async fn run_temporary_bans(http: Arc<Http>, database: SqlitePool) {
+    let mut interval = tokio::time::interval(TEMPORARY_BAN_TICK);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // The first tick completes immediately, so bans that expired while the bot was offline
+    // are lifted right after startup.
+    let mut reconciling = true;
+
+    loop {
+        interval.tick().await;
+
+        let bans = match database::get_due_temporary_bans(&database).await {
+            Ok(bans) => bans,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load expired temporary bans");
+                continue;
+            }
+        };
+
+        if reconciling && !bans.is_empty() {
+            tracing::info!(
+                count = %bans.len(),
+                "Lifting temporary bans that expired while offline"
+            );
+        }
+        reconciling = false;
+
+        for ban in bans {
+            lift_temporary_ban(&http, &database, &ban).await;
+        }
+    }
+}
+
+/// Unbans the member of an expired temporary ban and records the outcome.
+#[tracing::instrument(skip(http, database))]
+async fn lift_temporary_ban(http: &Http, database: &SqlitePool, ban: &TemporaryBan) {
+    let (Some(guild_id), Some(user_id), Some(moderator_id)) = (
+        parse_id(&ban.guild_id),
+        parse_id(&ban.user_id),
+        parse_id(&ban.moderator_id),
+    ) else {
+        tracing::warn!(ban_id = %ban.id, "Temporary ban has invalid ids, dropping it");
+        if let Err(e) =
+            database::mark_temporary_ban_lifted(database, ban.id, Some("Invalid ids")).await
+        {
+            tracing::error!(ban_id = %ban.id, error = %e, "Failed to close temporary ban");
+        }
+        return;
+    };
+    let guild_id = GuildId::new(guild_id);
+
+    let result = moderation::lift_ban(
+        http,
+        guild_id,
+        UserId::new(user_id),
+        &format!("Temporary ban expired (issued by {})", ban.moderator_name),
+    )
+    .await;
+
+    let (closed, error) = match &result {
+        Ok(true) => (
+            database::mark_temporary_ban_lifted(database, ban.id, None).await,
+            None,
+        ),
+        // Already unbanned outside the bot, nothing left to do.
+        Ok(false) => (
+            database::mark_temporary_ban_lifted(database, ban.id, Some("Not banned anymore")).await,
+            None,
+        ),
+        Err(e) => {
+            let error = e.to_string();
+            let attempts = database::record_temporary_ban_failure(database, ban.id, &error).await;
+
+            tracing::warn!(
+                ban_id = %ban.id,
+                guild_id = %guild_id,
+                user_id = %user_id,
+                error = %error,
+                "Failed to lift temporary ban"
+            );
+
+            match attempts {
+                Ok(attempts) if attempts >= MAX_UNBAN_ATTEMPTS => (
+                    database::mark_temporary_ban_lifted(database, ban.id, Some(&error)).await,
+                    Some(error),
+                ),
+                // Retried on the next tick.
+                Ok(_) => return,
+                Err(e) => (Err(e), Some(error)),
+            }
+        }
+    };
+
+    if let Err(e) = closed {
+        tracing::error!(ban_id = %ban.id, error = %e, "Failed to update temporary ban");
+    }
+
+    if matches!(result, Ok(false)) {
+        return;
+    }
+
+    audit::record(
+        http,
+        database,
+        AuditRecord::new(
+            guild_id,
+            UserId::new(moderator_id),
+            &ban.moderator_name,
+            AuditAction::Unban,
+        )
+        .target(format!("<@{}>", ban.user_id))
+        .parameters(serde_json::json!({
+            "reason": "Temporary ban expired",
+            "temporary_ban_id": ban.id,
+            "expires_at": ban.expires_at.to_rfc3339(),
+            "ban_reason": ban.reason,
+            "error": error,
+        }))
+        .outcome(match error {
+            Some(_) => AuditOutcome::Failure,
+            None => AuditOutcome::Success,
+        })
+        .counts(0, error.is_none() as i64, error.is_some() as i64),
+    )
+    .await;
+}
+
+async fn run_channel_locks(http: Arc<Http>, database: SqlitePool) {
+    let mut interval = tokio::time::interval(CHANNEL_LOCK_TICK);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // As with temporary bans, the first tick lifts locks that expired while offline.
+    let mut reconciling = true;
+
+    loop {
+        interval.tick().await;
+
+        let locks = match database::get_due_channel_locks(&database).await {
+            Ok(locks) => locks,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to load expired channel locks");
+                continue;
+            }
+        };
+
+        if reconciling && !locks.is_empty() {
+            tracing::info!(
+                count = %locks.len(),
+                "Unlocking channels whose lock expired while offline"
+            );
+        }
+        reconciling = false;
+
+        for lock in locks {
+            lift_channel_lock(&http, &database, &lock).await;
+        }
+    }
+}
+
+/// Restores the snapshot of an expired channel lock and records the outcome.
+#[tracing::instrument(skip(http, database))]
+async fn lift_channel_lock(http: &Http, database: &SqlitePool, lock: &ChannelLock) {
+    let (Some(guild_id), Some(moderator_id)) =
+        (parse_id(&lock.guild_id), parse_id(&lock.moderator_id))
+    else {
+        tracing::warn!(lock_id = %lock.id, "Channel lock has invalid ids, dropping it");
+        if let Err(e) =
+            database::mark_channel_unlocked(database, lock.id, Some("Invalid ids")).await
+        {
+            tracing::error!(lock_id = %lock.id, error = %e, "Failed to close channel lock");
+        }
+        return;
+    };
+
+    let (closed, error) = match lockdown::restore_snapshot(http, lock).await {
+        Ok(()) => (
+            database::mark_channel_unlocked(database, lock.id, None).await,
+            None,
+        ),
+        Err(e) => {
+            let error = e.to_string();
+            let attempts = database::record_channel_unlock_failure(database, lock.id, &error).await;
+
+            tracing::warn!(
+                lock_id = %lock.id,
+                channel_id = %lock.channel_id,
+                error = %error,
+                "Failed to lift channel lock"
+            );
+
+            match attempts {
+                Ok(attempts) if attempts >= MAX_UNLOCK_ATTEMPTS => (
+                    database::mark_channel_unlocked(database, lock.id, Some(&error)).await,
+                    Some(error),
+                ),
+                // Retried on the next tick.
+                Ok(_) => return,
+                Err(e) => (Err(e), Some(error)),
+            }
+        }
+    };
+
+    if let Err(e) = closed {
+        tracing::error!(lock_id = %lock.id, error = %e, "Failed to update channel lock");
+    }
+
+    audit::record(
+        http,
+        database,
+        AuditRecord::new(
+            GuildId::new(guild_id),
+            UserId::new(moderator_id),
+            &lock.moderator_name,
+            AuditAction::Unlock,
+        )
+        .target(format!("<#{}>", lock.channel_id))
+        .parameters(serde_json::json!({
+            "reason": "Lock expired",
+            "channel_lock_id": lock.id,
+            "lock_reason": lock.reason,
+            "expires_at": lock.expires_at.map(|t| t.to_rfc3339()),
+            "error": error,
+        }))
+        .outcome(match error {
+            Some(_) => AuditOutcome::Failure,
+            None => AuditOutcome::Success,
+        })
+        .counts(1, error.is_none() as i64, error.is_some() as i64),
+    )
+    .await;
+}
+
+/// Records a run that changed something or failed in the audit log.
+///
+/// Runs that found nothing to delete are only kept on the rule itself to avoid flooding the
+/// log with hourly no-ops.
+async fn audit_run(
+    http: &Http,
+    database: &SqlitePool,
+    rule: &AutoCleanRule,
+    checked: i64,
+    deleted: i64,
+    failed: i64,
+    error: Option<&str>,
+) {
+    let (Some(guild_id), Some(actor_id)) =
+        (parse_id(&rule.guild_id), parse_id(&rule.created_by_id))
+    else {
+        tracing::warn!(rule_id = %rule.id, "Auto-clean rule has invalid ids, not auditing run");
+        return;
+    };
+
+    let outcome = match error {
+        Some(_) => AuditOutcome::Failure,
+        None => AuditOutcome::from_counts(deleted, failed),
+    };
+
+    audit::record(
+        http,
+        database,
+        AuditRecord::new(
+            GuildId::new(guild_id),
+            UserId::new(actor_id),
+            &rule.created_by_name,
+            AuditAction::AutoClean,
+        )
+        .target(format!("<#{}>", rule.channel_id))
+        .parameters(serde_json::json!({
+            "rule_id": rule.id,
+            "interval_minutes": rule.interval_minutes,
+            "max_age_minutes": rule.max_age_minutes,
+            "pattern": rule.pattern,
+            "error": error,
+        }))
+        .outcome(outcome)
+        .counts(checked, deleted, failed),
+    )
+    .await;
+}
+
+/// Whether `rule` may run: its guild must be approved and still have the admin module on. Rules
+/// held back stay due and run once that changes.
+async fn should_run(data: &Data, rule: &AutoCleanRule) -> bool {
+    let Some(guild_id) = parse_id(&rule.guild_id).map(GuildId::new) else {
+        return true;
+    };
+
+    if !approval::is_active(data, guild_id).await {
+        tracing::debug!(rule_id = %rule.id, guild_id = %guild_id, "Guild not approved, skipping auto-clean rule");
+        return false;
+    }
+
+    match settings::is_module_enabled(data, guild_id, Module::Admin).await {
+        Ok(true) => true,
+        Ok(false) => {
+            tracing::debug!(rule_id = %rule.id, guild_id = %guild_id, "Admin module disabled, skipping auto-clean rule");
+            false
+        }
+        Err(e) => {
+            tracing::warn!(rule_id = %rule.id, guild_id = %guild_id, error = %e, "Failed to check modules for auto-clean rule");
+            false
+        }
+    }
+}
+
+pub(crate) fn parse_id(id: &str) -> Option<u64> {
+    id.parse::<u64>().ok().filter(|id| *id != 0)
+}
+
+/// Builds the purge filter for `rule` and runs it against its channel.
+#[tracing::instrument(skip(http))]
+async fn run_rule(http: &Http, rule: &AutoCleanRule) -> Result<ChannelReport> {
+    let channel_id = parse_id(&rule.channel_id)
+        .map(ChannelId::new)
+        .context("Rule has an invalid channel id")?;
+
+    let regex = match &rule.pattern {
+        Some(source) => Some(
+            pattern::compile(
+                Locale::default(),
+                source,
+                PatternOptions {
+                    case_insensitive: rule.case_insensitive,
+                    multi_line: rule.multi_line,
+                    literal: rule.literal,
+                },
+            )
+            .map_err(|e| anyhow::anyhow!("{}", e))?,
+        ),
+        None => None,
+    };
+
+    let now = Utc::now();
+    let filter = match rule.max_age_minutes {
+        Some(max_age) => PurgeFilter {
+            regex,
+            before: Some(now - Duration::minutes(max_age)),
+            scan_limit: Some(AUTO_CLEAN_SCAN_LIMIT),
+            ..Default::default()
+        },
+        // Pattern-only rules look at what arrived since the previous run.
+        None => PurgeFilter {
+            regex,
+            since: Some(
+                rule.last_run_at
+                    .unwrap_or_else(|| now - Duration::minutes(rule.interval_minutes)),
+            ),
+            ..Default::default()
+        },
+    };
+
+    let filter = PurgeFilter {
+        skip_pinned: true,
+        ..filter
+    };
+
+    Ok(purge::purge_channel(http, channel_id, &filter, |_| async {}).await)
+}
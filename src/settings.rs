@@ -0,0 +1,329 @@
+use crate::bot::Data;
+use crate::database::{self, GuildSettings};
+use crate::error::{Context, Error, bot_error};
+use crate::i18n::{self, tr};
+use crate::scheduler::parse_id;
+use anyhow::Result;
+use poise::serenity_prelude::{ChannelId, CreateMessage, GuildId, Http};
+use sqlx::SqlitePool;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+type Command = poise::Command<Data, Error>;
+
+/// Longest custom prefix a guild can set.
+pub const MAX_PREFIX_LENGTH: usize = 10;
+
+/// Groups of commands a guild can turn off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, poise::ChoiceParameter)]
+pub enum Module {
+    #[name = "music"]
+    Music,
+    #[name = "games"]
+    Games,
+    #[name = "admin"]
+    Admin,
+}
+
+impl Module {
+    pub const ALL: [Self; 3] = [Self::Music, Self::Games, Self::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Music => "music",
+            Self::Games => "games",
+            Self::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.as_str() == value)
+    }
+
+    /// The command category whose commands belong to this module.
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Music => "Music",
+            Self::Games => "Games",
+            Self::Admin => "Admin",
+        }
+    }
+
+    pub fn from_category(category: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.category() == category)
+    }
+}
+
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Languages the bot can answer in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum Locale {
+    #[default]
+    #[name = "English"]
+    EnglishUs,
+    #[name = "Deutsch"]
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Self; 2] = [Self::EnglishUs, Self::German];
+
+    /// Discord's code for the locale.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::EnglishUs => "en-US",
+            Self::German => "de",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.code() == code)
+    }
+
+    /// The closest locale to a Discord client's, matching on the language when the region
+    /// differs, so `en-GB` gets English.
+    pub fn from_discord(code: &str) -> Option<Self> {
+        let language = |code: &str| code.split('-').next().unwrap_or_default().to_string();
+        Self::from_code(code).or_else(|| {
+            Self::ALL
+                .into_iter()
+                .find(|l| language(l.code()) == language(code))
+        })
+    }
+}
+
+/// Modules named in a `disabled_modules` column. Unknown names are ignored.
+pub fn parse_modules(value: &str) -> BTreeSet<Module> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter_map(Module::parse)
+        .collect()
+}
+
+/// The `disabled_modules` column value for `modules`.
+pub fn format_modules(modules: &BTreeSet<Module>) -> String {
+    modules
+        .iter()
+        .map(Module::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The module a command belongs to, decided by the nearest category set on it or its parents.
+///
+/// `parents` are ordered from the top-level command down, as poise passes them. A command whose
+/// nearest category is not a module, such as the settings commands, can't be turned off.
+pub fn command_module(command: &Command, parents: &[&Command]) -> Option<Module> {
+    std::iter::once(command)
+        .chain(parents.iter().rev().copied())
+        .find_map(|command| command.category.as_deref())
+        .and_then(Module::from_category)
+}
+
+/// Whether `guild_id` left `module` on, for work the bot does outside of commands.
+pub async fn is_module_enabled(data: &Data, guild_id: GuildId, module: Module) -> Result<bool> {
+    let settings = data.settings.get(&data.database, guild_id).await?;
+
+    Ok(!parse_modules(&settings.disabled_modules).contains(&module))
+}
+
+/// Global command check: refuses commands of modules the guild has turned off.
+pub async fn module_check(ctx: Context<'_>) -> std::result::Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+    let Some(module) = command_module(ctx.command(), ctx.parent_commands()) else {
+        return Ok(true);
+    };
+
+    let settings = ctx
+        .data()
+        .settings
+        .get(&ctx.data().database, guild_id)
+        .await?;
+    if parse_modules(&settings.disabled_modules).contains(&module) {
+        tracing::debug!(
+            guild_id = %guild_id,
+            command = %ctx.command().qualified_name,
+            module = %module,
+            "Command refused, module disabled"
+        );
+        let locale = i18n::locale(ctx).await;
+        return Err(bot_error(tr!(
+            locale,
+            "check-module-disabled",
+            module = module
+        )));
+    }
+
+    Ok(true)
+}
+
+/// Checks a custom prefix, returning why it can't be used in `locale`.
+pub fn validate_prefix(locale: Locale, prefix: &str) -> std::result::Result<(), String> {
+    if prefix.is_empty() {
+        return Err(tr!(locale, "settings-prefix-empty"));
+    }
+    if prefix.chars().count() > MAX_PREFIX_LENGTH {
+        return Err(tr!(
+            locale,
+            "settings-prefix-too-long",
+            max = MAX_PREFIX_LENGTH
+        ));
+    }
+    if prefix.chars().any(char::is_whitespace) {
+        return Err(tr!(locale, "settings-prefix-whitespace"));
+    }
+    if prefix.starts_with('/') {
+        return Err(tr!(locale, "settings-prefix-slash"));
+    }
+
+    Ok(())
+}
+
+/// Guild settings, loaded on first use and dropped when they change.
+#[derive(Debug, Clone, Default)]
+pub struct SettingsCache {
+    settings: Arc<RwLock<HashMap<GuildId, Arc<GuildSettings>>>>,
+}
+
+impl SettingsCache {
+    pub async fn get(
+        &self,
+        database: &SqlitePool,
+        guild_id: GuildId,
+    ) -> Result<Arc<GuildSettings>> {
+        if let Some(settings) = self.settings.read().unwrap().get(&guild_id) {
+            return Ok(settings.clone());
+        }
+
+        let guild_key = guild_id.to_string();
+        let settings = database::get_guild_settings(database, &guild_key)
+            .await?
+            .unwrap_or_else(|| GuildSettings {
+                guild_id: guild_key,
+                ..Default::default()
+            });
+
+        let settings = Arc::new(settings);
+        self.settings
+            .write()
+            .unwrap()
+            .insert(guild_id, settings.clone());
+
+        Ok(settings)
+    }
+
+    pub fn invalidate(&self, guild_id: GuildId) {
+        self.settings.write().unwrap().remove(&guild_id);
+    }
+}
+
+/// Prefix hook for poise: the guild's own prefix, or the configured one.
+pub async fn dynamic_prefix(
+    ctx: poise::PartialContext<'_, Data, Error>,
+) -> std::result::Result<Option<String>, Error> {
+    let default = ctx.data.config.get().discord.prefix.clone();
+    let Some(guild_id) = ctx.guild_id else {
+        return Ok(Some(default));
+    };
+
+    let settings = ctx.data.settings.get(&ctx.data.database, guild_id).await?;
+
+    Ok(Some(settings.prefix.clone().unwrap_or(default)))
+}
+
+/// Posts a new suggestion to the guild's suggestion channel, unless it was made there.
+pub async fn announce_suggestion(
+    http: &Http,
+    data: &Data,
+    guild_id: GuildId,
+    origin: ChannelId,
+    content: &str,
+) {
+    let settings = match data.settings.get(&data.database, guild_id).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::warn!(guild_id = %guild_id, error = %e, "Failed to load guild settings");
+            return;
+        }
+    };
+    let Some(channel_id) = settings
+        .suggestion_channel_id
+        .as_deref()
+        .and_then(parse_id)
+        .map(ChannelId::new)
+        .filter(|&channel_id| channel_id != origin)
+    else {
+        return;
+    };
+
+    let message = CreateMessage::new().content(content);
+    if let Err(e) = channel_id.send_message(http, message).await {
+        tracing::warn!(guild_id = %guild_id, channel_id = %channel_id, error = %e, "Failed to announce suggestion");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_modules() {
+        let modules = parse_modules("games, admin,unknown,");
+        assert_eq!(modules, BTreeSet::from([Module::Games, Module::Admin]));
+        assert!(parse_modules("").is_empty());
+    }
+
+    #[test]
+    fn test_format_modules() {
+        let modules = BTreeSet::from([Module::Admin, Module::Music]);
+        assert_eq!(format_modules(&modules), "music,admin");
+        assert_eq!(parse_modules(&format_modules(&modules)), modules);
+    }
+
+    #[test]
+    fn test_command_module() {
+        let mut parent = Command {
+            category: Some("Admin".to_string()),
+            ..Default::default()
+        };
+        let mut command = Command::default();
+        assert_eq!(command_module(&command, &[&parent]), Some(Module::Admin));
+
+        // The nearest category wins, so a group can opt out of its parent's module.
+        command.category = Some("Settings".to_string());
+        assert_eq!(command_module(&command, &[&parent]), None);
+
+        command.category = Some("Music".to_string());
+        parent.category = Some("Misc".to_string());
+        assert_eq!(command_module(&command, &[&parent]), Some(Module::Music));
+    }
+
+    #[test]
+    fn test_validate_prefix() {
+        assert!(validate_prefix(Locale::EnglishUs, "!").is_ok());
+        assert!(validate_prefix(Locale::EnglishUs, "bb!").is_ok());
+        assert!(validate_prefix(Locale::EnglishUs, "").is_err());
+        assert!(validate_prefix(Locale::EnglishUs, "a b").is_err());
+        assert!(validate_prefix(Locale::EnglishUs, "/").is_err());
+        assert!(validate_prefix(Locale::EnglishUs, &"!".repeat(MAX_PREFIX_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_locale_codes() {
+        assert_eq!(Locale::from_code("de"), Some(Locale::German));
+        assert_eq!(Locale::from_code("en-US"), Some(Locale::EnglishUs));
+        assert_eq!(Locale::from_code("fr"), None);
+        assert_eq!(Locale::from_discord("en-GB"), Some(Locale::EnglishUs));
+        assert_eq!(Locale::from_discord("de"), Some(Locale::German));
+        assert_eq!(Locale::from_discord("pt-BR"), None);
+    }
+}